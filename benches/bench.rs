@@ -1,3 +1,5 @@
+#![allow(clippy::explicit_auto_deref, clippy::unnecessary_cast)]
+
 use criterion::{criterion_group, criterion_main, Criterion};
 use hdlc::{decode, encode, SpecialChars};
 
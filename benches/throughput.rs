@@ -0,0 +1,145 @@
+use std::io::Cursor;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use hdlc::{decode, decode_slice, encode, Codec, FrameReader, SpecialChars, FEND, FESC};
+
+/// The default special characters, fixed at compile time
+type ConstCodec = Codec<FEND, FESC, 0x5E, 0x5D>;
+
+/// Frame sizes covering a short telemetry message, an Ethernet MTU and a bulk transfer
+const SIZES: [usize; 3] = [64, 1500, 65536];
+
+/// Share of payload bytes that need escaping, in percent
+const DENSITIES: [usize; 4] = [0, 1, 10, 50];
+
+/// Builds a payload of `len` bytes where roughly `density` percent are special characters
+fn payload(len: usize, density: usize) -> Vec<u8> {
+    // A small LCG keeps the special characters spread out but reproducible
+    let mut seed: u32 = 0x2545_F491;
+    (0..len)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            match (seed >> 16) as usize % 100 {
+                r if r < density / 2 => FEND,
+                r if r < density => FESC,
+                _ => (seed >> 8) as u8 & 0x3F,
+            }
+        })
+        .collect()
+}
+
+fn bench_encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+    for size in SIZES {
+        for density in DENSITIES {
+            let data = payload(size, density);
+            group.throughput(Throughput::Bytes(size as u64));
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}%", density), size),
+                &data,
+                |b, data| b.iter(|| encode(data, SpecialChars::default())),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    for size in SIZES {
+        for density in DENSITIES {
+            let frame = encode(&payload(size, density), SpecialChars::default()).unwrap();
+            group.throughput(Throughput::Bytes(size as u64));
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}%", density), size),
+                &frame,
+                |b, frame| b.iter(|| decode(frame, SpecialChars::default())),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_const_encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("const_encode");
+    for size in SIZES {
+        for density in DENSITIES {
+            let data = payload(size, density);
+            group.throughput(Throughput::Bytes(size as u64));
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}%", density), size),
+                &data,
+                |b, data| b.iter(|| ConstCodec::new().encode(data)),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_const_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("const_decode");
+    for size in SIZES {
+        for density in DENSITIES {
+            let frame = ConstCodec::new().encode(&payload(size, density));
+            group.throughput(Throughput::Bytes(size as u64));
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}%", density), size),
+                &frame,
+                |b, frame| b.iter(|| ConstCodec::new().decode(frame)),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_decode_slice(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_slice");
+    for size in SIZES {
+        for density in DENSITIES {
+            let frame = encode(&payload(size, density), SpecialChars::default()).unwrap();
+            group.throughput(Throughput::Bytes(size as u64));
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}%", density), size),
+                &frame,
+                |b, frame| {
+                    b.iter_batched_ref(
+                        || frame.clone(),
+                        |frame| decode_slice(frame, SpecialChars::default()).map(|f| f.len()),
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_frame_reader(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frame_reader");
+    for size in SIZES {
+        // About a megabyte of back to back frames
+        let count = (1 << 20) / size;
+        let stream: Vec<u8> = (0..count)
+            .flat_map(|_| encode(&payload(size, 1), SpecialChars::default()).unwrap())
+            .collect();
+        group.throughput(Throughput::Bytes(stream.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &stream, |b, stream| {
+            b.iter(|| {
+                let mut reader = Cursor::new(stream.as_slice());
+                FrameReader::new(&mut reader, SpecialChars::default()).count()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_encode,
+    bench_decode,
+    bench_const_encode,
+    bench_const_decode,
+    bench_decode_slice,
+    bench_frame_reader
+);
+criterion_main!(benches);
@@ -0,0 +1,81 @@
+/*
+ * C interface of the hdlc crate, built with the `ffi` feature:
+ *
+ *     cargo rustc --release --features ffi --crate-type cdylib
+ *
+ * Kept in sync with src/ffi.rs. Every function returns HDLC_OK or a negative error code.
+ * Output is written into buffers owned by the caller, and the length written, or needed if
+ * the buffer is too small, is stored in out_len.
+ */
+
+#ifndef HDLC_H
+#define HDLC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Success */
+#define HDLC_OK 0
+/* hdlc_decoder_next found no complete frame */
+#define HDLC_NO_FRAME 1
+/* A required pointer was null */
+#define HDLC_ERR_NULL (-1)
+/* The special characters are not unique */
+#define HDLC_ERR_DUPLICATE_SPECIAL_CHAR (-2)
+/* A fend inside the frame */
+#define HDLC_ERR_FEND_IN_DATA (-3)
+/* A fesc not followed by tfend or tfesc */
+#define HDLC_ERR_MISSING_TRADE_CHAR (-4)
+/* The frame doesn't start with fend */
+#define HDLC_ERR_MISSING_FIRST_FEND (-5)
+/* The frame doesn't end with fend */
+#define HDLC_ERR_MISSING_FINAL_FEND (-6)
+/* The output buffer is too small, out_len holds the length needed */
+#define HDLC_ERR_BUFFER_TOO_SMALL (-7)
+/* Any other error */
+#define HDLC_ERR_OTHER (-99)
+
+/* Special characters, a null pointer selects 0x7E, 0x7D, 0x5E, 0x5D */
+typedef struct hdlc_special_chars {
+    uint8_t fend;
+    uint8_t fesc;
+    uint8_t tfend;
+    uint8_t tfesc;
+} hdlc_special_chars;
+
+/* Streaming decoder */
+typedef struct hdlc_decoder hdlc_decoder;
+
+/* Encodes len bytes of data into a frame written to out. */
+int32_t hdlc_encode(const uint8_t *data, size_t len, const hdlc_special_chars *chars,
+                    uint8_t *out, size_t out_cap, size_t *out_len);
+
+/* Decodes the frame of len bytes, writing its payload to out. */
+int32_t hdlc_decode(const uint8_t *frame, size_t len, const hdlc_special_chars *chars,
+                    uint8_t *out, size_t out_cap, size_t *out_len);
+
+/* Creates a streaming decoder, null if the special characters are not unique. */
+hdlc_decoder *hdlc_decoder_new(const hdlc_special_chars *chars);
+
+/* Releases a decoder, null is ignored. */
+void hdlc_decoder_free(hdlc_decoder *decoder);
+
+/* Adds len received bytes to the decoder. */
+int32_t hdlc_decoder_push(hdlc_decoder *decoder, const uint8_t *data, size_t len);
+
+/*
+ * Writes the payload of the next complete frame to out. Returns HDLC_NO_FRAME if none is
+ * buffered. A malformed frame is dropped and reported by its error code, a payload too large
+ * for out is kept for the next call.
+ */
+int32_t hdlc_decoder_next(hdlc_decoder *decoder, uint8_t *out, size_t out_cap, size_t *out_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* HDLC_H */
@@ -0,0 +1,346 @@
+//! Lightweight reliable datagram layer over the framing
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use crate::{
+    decode_ref, encode_ref, FixedBackoff, HDLCError, LinkMetrics, RetryPolicy, SpecialChars,
+};
+
+/// Number of sequence numbers, carried in the low 7 bits of the header
+const SEQ_COUNT: usize = 128;
+
+/// Header bit marking an acknowledgement
+const ACK: u8 = 0x80;
+
+/// Timing and windowing of an [`Arq`] endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArqConfig {
+    /// Time without acknowledgement after which a frame is sent again, in the unit of the `now`
+    /// arguments. Replaced by the policy of [`Arq::with_policy`]
+    pub timeout: u64,
+
+    /// Number of times a frame is sent again before [`Arq::poll`] gives up on it. Replaced by
+    /// the policy of [`Arq::with_policy`]
+    pub retries: u32,
+
+    /// Number of frames in flight at once, 1 for stop-and-wait, at most 64
+    pub window: usize,
+}
+
+impl Default for ArqConfig {
+    /// Stop-and-wait, retrying 3 times after 1000 units of time
+    fn default() -> Self {
+        ArqConfig {
+            timeout: 1000,
+            retries: 3,
+            window: 1,
+        }
+    }
+}
+
+/// A frame sent but not acknowledged yet
+#[derive(Debug, Clone)]
+struct Pending {
+    /// The encoded frame, for retransmission
+    frame: Vec<u8>,
+
+    /// When the frame is sent again unless acknowledged
+    deadline: u64,
+
+    /// Number of times the frame was sent again
+    retries: u32,
+}
+
+/// One end of a reliable datagram link, for users who don't need the full HDLC state machine.
+///
+/// Every payload is sent with a one byte header holding its sequence number. The receiving end
+/// delivers payloads once, in order, and acknowledges them cumulatively with a header holding
+/// the next sequence number it expects. Frames not acknowledged in time are sent again on their
+/// own, and frames received out of order within the window are held until the gap is filled.
+/// How long to wait and how often to retry is up to the [`RetryPolicy`] `P`.
+///
+/// The endpoint does no I/O and reads no clock: frames to send are taken from
+/// [`Arq::poll_transmit`], received frames are handed to [`Arq::receive`], and the current time
+/// is passed in, in any unit matching [`ArqConfig::timeout`].
+///
+/// # Example
+/// ```rust
+/// use hdlc::{Arq, ArqConfig, SpecialChars};
+///
+/// let chars = SpecialChars::default();
+/// let mut alice = Arq::new(chars.clone(), ArqConfig::default()).unwrap();
+/// let mut bob = Arq::new(chars, ArqConfig::default()).unwrap();
+///
+/// alice.send(&[0x01, 0x02], 0).unwrap();
+/// let frame = alice.poll_transmit().unwrap();
+///
+/// // The frame is lost, and sent again once the timeout passed
+/// assert_eq!(alice.poll(1000), Ok(()));
+/// let frame = alice.poll_transmit().unwrap();
+///
+/// bob.receive(&frame, 1010).unwrap();
+/// assert_eq!(bob.recv(), Some(vec![0x01, 0x02]));
+///
+/// let ack = bob.poll_transmit().unwrap();
+/// alice.receive(&ack, 1020).unwrap();
+/// assert_eq!(alice.in_flight(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Arq<P = FixedBackoff> {
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// Windowing
+    config: ArqConfig,
+
+    /// Timeouts and retries
+    policy: P,
+
+    /// Payloads waiting for room in the window
+    backlog: VecDeque<Vec<u8>>,
+
+    /// Frames sent but not acknowledged, oldest first
+    unacked: VecDeque<Pending>,
+
+    /// Sequence number of the oldest frame in `unacked`, or of the next frame sent
+    base_seq: usize,
+
+    /// Encoded frames waiting to be transmitted
+    outgoing: VecDeque<Vec<u8>>,
+
+    /// Sequence number of the next payload to deliver
+    expected: usize,
+
+    /// Payloads received ahead of `expected`, indexed by their distance to it
+    reorder: VecDeque<Option<Vec<u8>>>,
+
+    /// Payloads received in order, waiting to be taken
+    delivered: VecDeque<Vec<u8>>,
+
+    /// Counters retransmissions are recorded into
+    metrics: Option<Arc<LinkMetrics>>,
+}
+
+impl Arq {
+    /// Creates a new Arq instance, retrying after a fixed timeout. The window is kept between
+    /// 1 and 64 frames.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    /// * `config` - Timing and windowing of the link, equal at both ends.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.
+    pub fn new(s_char: SpecialChars, config: ArqConfig) -> Result<Self, HDLCError> {
+        let policy = FixedBackoff {
+            timeout: config.timeout,
+            retries: config.retries,
+        };
+        Arq::with_policy(s_char, config, policy)
+    }
+}
+
+impl<P: RetryPolicy> Arq<P> {
+    /// Creates a new Arq instance, retrying as `policy` decides. The timeout and retries of
+    /// `config` are ignored, and the window is kept between 1 and 64 frames.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    /// * `config` - Windowing of the link, equal at both ends.
+    /// * `policy` - How long to wait for acknowledgements and how often to retry.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.
+    pub fn with_policy(
+        s_char: SpecialChars,
+        mut config: ArqConfig,
+        policy: P,
+    ) -> Result<Self, HDLCError> {
+        // Safety check to make sure the special character values are all unique
+        if s_char.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+        // Half the sequence space tells old frames apart from new ones
+        config.window = config.window.clamp(1, SEQ_COUNT / 2);
+
+        Ok(Arq {
+            s_char,
+            config,
+            policy,
+            backlog: VecDeque::new(),
+            unacked: VecDeque::new(),
+            base_seq: 0,
+            outgoing: VecDeque::new(),
+            expected: 0,
+            reorder: (0..config.window).map(|_| None).collect(),
+            delivered: VecDeque::new(),
+            metrics: None,
+        })
+    }
+
+    /// Records every frame sent again into `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<LinkMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Queues `payload` for reliable delivery, sending it right away if the window has room.
+    ///
+    /// # Arguments
+    /// * `payload` - The payload to deliver.
+    /// * `now` - The current time.
+    ///
+    /// # Error
+    ///
+    /// * Any error [`encode`] returns for the frame.
+    pub fn send(&mut self, payload: &[u8], now: u64) -> Result<(), HDLCError> {
+        self.backlog.push_back(payload.to_vec());
+        self.fill_window(now)
+    }
+
+    /// Handles a frame received from the peer, either delivering its payload or taking note of
+    /// its acknowledgement.
+    ///
+    /// Duplicate and out of window payloads are dropped, but acknowledged again in case the
+    /// previous acknowledgement was lost.
+    ///
+    /// # Arguments
+    /// * `frame` - One encoded frame, flags included.
+    /// * `now` - The current time.
+    ///
+    /// # Error
+    ///
+    /// * Any error [`decode`] returns for the frame. A frame without header is reported as
+    ///   `HDLCError::Incomplete`.
+    pub fn receive(&mut self, frame: &[u8], now: u64) -> Result<(), HDLCError> {
+        let data = decode_ref(frame, &self.s_char)?;
+        let (&header, payload) = data
+            .split_first()
+            .ok_or(HDLCError::Incomplete { needed: Some(1) })?;
+        let seq = usize::from(header & !ACK);
+
+        if header & ACK != 0 {
+            // Everything before the sequence number the peer expects is acknowledged
+            let acked = (seq + SEQ_COUNT - self.base_seq) % SEQ_COUNT;
+            if acked <= self.unacked.len() {
+                self.unacked.drain(..acked);
+                self.base_seq = seq;
+            }
+            return self.fill_window(now);
+        }
+
+        let offset = (seq + SEQ_COUNT - self.expected) % SEQ_COUNT;
+        if offset < self.reorder.len() && self.reorder[offset].is_none() {
+            self.reorder[offset] = Some(payload.to_vec());
+        }
+        while let Some(Some(_)) = self.reorder.front() {
+            if let Some(Some(payload)) = self.reorder.pop_front() {
+                self.delivered.push_back(payload);
+            }
+            self.reorder.push_back(None);
+            self.expected = (self.expected + 1) % SEQ_COUNT;
+        }
+
+        let ack = encode_ref(&[ACK | self.expected as u8], &self.s_char)?;
+        self.outgoing.push_back(ack);
+
+        Ok(())
+    }
+
+    /// Sends again every frame that wasn't acknowledged within the timeout.
+    ///
+    /// Call it at least by [`Arq::next_timeout`].
+    ///
+    /// # Arguments
+    /// * `now` - The current time.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::RetriesExhausted**: A frame wasn't acknowledged and the retry policy gave
+    ///   up on it. The link is considered down, see [`Arq::reset`].
+    pub fn poll(&mut self, now: u64) -> Result<(), HDLCError> {
+        for pending in self.unacked.iter_mut() {
+            if now < pending.deadline {
+                continue;
+            }
+            let timeout = self
+                .policy
+                .timeout(pending.retries + 1)
+                .ok_or(HDLCError::RetriesExhausted)?;
+            pending.retries += 1;
+            pending.deadline = now.saturating_add(timeout);
+            self.outgoing.push_back(pending.frame.clone());
+            if let Some(metrics) = &self.metrics {
+                metrics.record_retransmission();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the time by which [`Arq::poll`] has to be called, None if no frame awaits an
+    /// acknowledgement.
+    pub fn next_timeout(&self) -> Option<u64> {
+        self.unacked.iter().map(|pending| pending.deadline).min()
+    }
+
+    /// Removes the next encoded frame to transmit, data and acknowledgements alike.
+    pub fn poll_transmit(&mut self) -> Option<Vec<u8>> {
+        self.outgoing.pop_front()
+    }
+
+    /// Removes the next payload delivered by the peer, in order.
+    pub fn recv(&mut self) -> Option<Vec<u8>> {
+        self.delivered.pop_front()
+    }
+
+    /// Returns the number of frames sent but not acknowledged yet.
+    pub fn in_flight(&self) -> usize {
+        self.unacked.len()
+    }
+
+    /// Returns the number of payloads waiting for room in the window.
+    pub fn backlog(&self) -> usize {
+        self.backlog.len()
+    }
+
+    /// Drops every queued, unacknowledged and held frame and starts over at sequence number 0,
+    /// for a link restarted at both ends. Payloads already delivered can still be taken.
+    pub fn reset(&mut self) {
+        self.backlog.clear();
+        self.unacked.clear();
+        self.base_seq = 0;
+        self.outgoing.clear();
+        self.expected = 0;
+        self.reorder.iter_mut().for_each(|slot| *slot = None);
+    }
+
+    /// Sends payloads from the backlog while the window has room
+    fn fill_window(&mut self, now: u64) -> Result<(), HDLCError> {
+        while self.unacked.len() < self.config.window {
+            let Some(payload) = self.backlog.pop_front() else {
+                break;
+            };
+            let seq = (self.base_seq + self.unacked.len()) % SEQ_COUNT;
+            let mut data = Vec::with_capacity(payload.len() + 1);
+            data.push(seq as u8);
+            data.extend_from_slice(&payload);
+            let frame = encode_ref(&data, &self.s_char)?;
+
+            self.outgoing.push_back(frame.clone());
+            self.unacked.push_back(Pending {
+                frame,
+                deadline: now.saturating_add(self.policy.timeout(0).unwrap_or(0)),
+                retries: 0,
+            });
+        }
+
+        Ok(())
+    }
+}
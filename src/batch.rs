@@ -0,0 +1,54 @@
+//! Parallel encoding and decoding of many frames at once, enabled by the `rayon` feature
+
+use rayon::prelude::*;
+
+use crate::{decode_ref, encode_ref, HDLCError, SpecialChars};
+
+/// Encodes every payload on the rayon thread pool, keeping the order of `payloads`.
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+///
+/// # Example
+/// ```rust
+/// use hdlc::{encode_batch, SpecialChars};
+///
+/// let payloads = vec![vec![0x01], vec![0x7E]];
+///
+/// let frames = encode_batch(&payloads, SpecialChars::default()).unwrap();
+/// assert_eq!(frames, vec![vec![0x7E, 0x01, 0x7E], vec![0x7E, 0x7D, 0x5E, 0x7E]]);
+/// ```
+pub fn encode_batch<P: AsRef<[u8]> + Sync>(
+    payloads: &[P],
+    s_chars: SpecialChars,
+) -> Result<Vec<Vec<u8>>, HDLCError> {
+    payloads
+        .par_iter()
+        .map(|payload| encode_ref(payload.as_ref(), &s_chars))
+        .collect()
+}
+
+/// Decodes every frame on the rayon thread pool, keeping the order of `frames`.
+///
+/// Each frame gets its own result, so one malformed frame in a capture doesn't hide the rest.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{decode_batch, HDLCError, SpecialChars};
+///
+/// let frames = vec![vec![0x7E, 0x01, 0x7E], vec![0x01, 0x7E]];
+///
+/// let payloads = decode_batch(&frames, SpecialChars::default());
+/// assert_eq!(payloads, vec![Ok(vec![0x01]), Err(HDLCError::MissingFirstFend)]);
+/// ```
+pub fn decode_batch<F: AsRef<[u8]> + Sync>(
+    frames: &[F],
+    s_chars: SpecialChars,
+) -> Vec<Result<Vec<u8>, HDLCError>> {
+    frames
+        .par_iter()
+        .map(|frame| decode_ref(frame.as_ref(), &s_chars))
+        .collect()
+}
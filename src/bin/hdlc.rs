@@ -0,0 +1,218 @@
+//! Command line companion for bench debugging, enabled by the `cli` feature
+//!
+//! ```text
+//! hdlc encode [--hex] [--fcs MODE]        payload on stdin to a frame on stdout
+//! hdlc decode [--hex] [--fcs MODE] [IN]   frames to payloads
+//! hdlc dump [IN]                          hexdump of every frame
+//! hdlc verify --fcs MODE [IN]             FCS check of every frame
+//! ```
+//!
+//! `IN` is a file or a serial device, configured beforehand with `stty`, and defaults to stdin.
+
+use std::fs::File;
+use std::io::{self, BufRead, Read, Write};
+use std::process::ExitCode;
+
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use hdlc::{decode, encode, FcsMode, FrameReader, HDLCError, HexFrame, SpecialChars};
+
+fn main() -> ExitCode {
+    let matches = cli().get_matches();
+    match run(&matches) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("hdlc: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns the command line interface
+fn cli() -> Command {
+    let chars = Arg::new("chars")
+        .long("chars")
+        .value_name("FEND,FESC,TFEND,TFESC")
+        .help("Special characters in hex [default: 7E,7D,5E,5D]")
+        .global(true);
+    let hex = Arg::new("hex")
+        .long("hex")
+        .action(ArgAction::SetTrue)
+        .help("Read or write hex text, one payload per line, instead of binary");
+    let fcs = Arg::new("fcs")
+        .long("fcs")
+        .value_name("MODE")
+        .value_parser(["none", "crc16", "crc32"])
+        .default_value("none")
+        .help("Frame check sequence following the payload");
+    let input = Arg::new("input")
+        .value_name("IN")
+        .value_parser(value_parser!(std::path::PathBuf))
+        .help("File or serial device to read frames from [default: stdin]");
+
+    Command::new("hdlc")
+        .about("Encodes, decodes and inspects HDLC frames")
+        .subcommand_required(true)
+        .arg(chars)
+        .subcommand(
+            Command::new("encode")
+                .about("Encodes the payload on stdin into a frame")
+                .arg(hex.clone())
+                .arg(fcs.clone()),
+        )
+        .subcommand(
+            Command::new("decode")
+                .about("Splits and decodes frames into their payloads")
+                .arg(hex)
+                .arg(fcs.clone())
+                .arg(input.clone()),
+        )
+        .subcommand(
+            Command::new("dump")
+                .about("Prints a hexdump of every frame")
+                .arg(input.clone()),
+        )
+        .subcommand(
+            Command::new("verify")
+                .about("Checks the frame check sequence of every frame")
+                .arg(fcs.default_value("crc16"))
+                .arg(input),
+        )
+}
+
+/// Runs the subcommand, returning whether every frame was valid
+fn run(matches: &ArgMatches) -> Result<bool, HDLCError> {
+    let chars = match matches.get_one::<String>("chars") {
+        Some(chars) => parse_chars(chars)?,
+        None => SpecialChars::default(),
+    };
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    match matches.subcommand() {
+        Some(("encode", args)) => {
+            let fcs = fcs_mode(args);
+            if args.get_flag("hex") {
+                for line in io::stdin().lock().lines() {
+                    let mut payload = parse_hex(&line?)?;
+                    fcs.append(&mut payload);
+                    writeln!(out, "{}", format_hex(&encode(&payload, chars.clone())?))?;
+                }
+            } else {
+                let mut payload = Vec::new();
+                io::stdin().read_to_end(&mut payload)?;
+                fcs.append(&mut payload);
+                out.write_all(&encode(&payload, chars)?)?;
+            }
+            Ok(true)
+        }
+        Some(("decode", args)) => {
+            let fcs = fcs_mode(args);
+            let hex = args.get_flag("hex");
+            for_each_frame(args, chars.clone(), |index, frame| {
+                match decode(frame, chars.clone()).and_then(|data| Ok(fcs.check(&data)?.to_vec())) {
+                    Ok(payload) if hex => writeln!(out, "{}", format_hex(&payload))?,
+                    Ok(payload) => {
+                        out.write_all(&payload)?;
+                        out.flush()?;
+                    }
+                    Err(e) => {
+                        eprintln!("frame {}: {}", index, e);
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            })
+        }
+        Some(("dump", args)) => for_each_frame(args, chars.clone(), |index, frame| {
+            writeln!(out, "frame {}, {} bytes", index, frame.len())?;
+            writeln!(out, "{}", HexFrame::new(frame, chars.clone()))?;
+            Ok(true)
+        }),
+        Some(("verify", args)) => {
+            let fcs = fcs_mode(args);
+            for_each_frame(args, chars.clone(), |index, frame| {
+                match decode(frame, chars.clone()).and_then(|data| Ok(fcs.check(&data)?.len())) {
+                    Ok(len) => {
+                        writeln!(out, "frame {}: ok, {} byte payload", index, len)?;
+                        Ok(true)
+                    }
+                    Err(e) => {
+                        writeln!(out, "frame {}: {}", index, e)?;
+                        Ok(false)
+                    }
+                }
+            })
+        }
+        _ => unreachable!("a subcommand is required"),
+    }
+}
+
+/// Hands every raw frame of the input to `f`, returning whether `f` accepted all of them
+fn for_each_frame<F>(args: &ArgMatches, chars: SpecialChars, mut f: F) -> Result<bool, HDLCError>
+where
+    F: FnMut(usize, &[u8]) -> Result<bool, HDLCError>,
+{
+    let mut input: Box<dyn Read> = match args.get_one::<std::path::PathBuf>("input") {
+        Some(path) => Box::new(File::open(path)?),
+        None => Box::new(io::stdin()),
+    };
+    // Serial devices hand out a few bytes per read, keep frames flowing as they arrive
+    let mut reader = FrameReader::builder(chars).chunk_size(64).build(&mut input);
+
+    let mut valid = true;
+    let mut index = 0;
+    while let Some(frame) = reader.try_read_frame()? {
+        valid &= f(index, &frame)?;
+        index += 1;
+    }
+
+    Ok(valid)
+}
+
+/// Returns the FCS mode picked by `--fcs`
+fn fcs_mode(args: &ArgMatches) -> FcsMode {
+    match args.get_one::<String>("fcs").map(String::as_str) {
+        Some("crc16") => FcsMode::Crc16,
+        Some("crc32") => FcsMode::Crc32,
+        _ => FcsMode::None,
+    }
+}
+
+/// Parses the `--chars` list of four hex bytes
+fn parse_chars(chars: &str) -> Result<SpecialChars, HDLCError> {
+    match parse_hex(chars)?[..] {
+        [fend, fesc, tfend, tfesc] => SpecialChars::try_new(fend, fesc, tfend, tfesc),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "--chars takes four bytes").into()),
+    }
+}
+
+/// Parses a hex string of bytes, groups separated by whitespace, `:` or `,`
+fn parse_hex(hex: &str) -> Result<Vec<u8>, HDLCError> {
+    let mut bytes = Vec::new();
+    for group in hex.split(|c: char| c.is_whitespace() || c == ':' || c == ',') {
+        let digits = group.trim_start_matches("0x").as_bytes();
+        for pair in digits.chunks(2) {
+            let byte = std::str::from_utf8(pair)
+                .ok()
+                .filter(|pair| pair.len() == 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| {
+                    let message = format!("bad hex byte in {:?}", group);
+                    io::Error::new(io::ErrorKind::InvalidInput, message)
+                })?;
+            bytes.push(byte);
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Formats bytes as upper case hex separated by spaces
+fn format_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,182 @@
+//! Decoder handing out frames in chunks, for frames too large to buffer whole
+
+use memchr::memchr;
+
+use crate::{memchr2, HDLCError, SpecialChars};
+
+/// What a [`ChunkedDecoder`] reports to its callback
+#[derive(Debug, PartialEq)]
+pub enum DecodeEvent<'a> {
+    /// The next decoded bytes of the current frame
+    Data(&'a [u8]),
+
+    /// The current frame is complete, the next `Data` belongs to a new frame
+    End,
+
+    /// The current frame is malformed and dropped, the `Data` already delivered for it should be
+    /// discarded
+    Error(HDLCError),
+}
+
+/// Where the [`ChunkedDecoder`] is within the byte stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Waiting for the opening `fend`
+    Hunt,
+    /// Passing on the bytes of a frame
+    InFrame,
+    /// The previous byte was a `fesc`
+    Escaped,
+    /// Dropping the rest of a malformed frame
+    Discard,
+}
+
+/// A streaming decoder that passes decoded bytes to a callback as soon as they are unescaped,
+/// so frames of any size are decoded without being buffered.
+///
+/// Runs of bytes that need no unescaping are handed out as slices of the input, so chunks are
+/// as large as the data fed in allows. The decoder hunts for the opening `fend` of a frame, and
+/// again after each closing `fend`. Empty frames are skipped.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{ChunkedDecoder, DecodeEvent, SpecialChars};
+///
+/// let mut decoder = ChunkedDecoder::new(SpecialChars::default()).unwrap();
+/// let mut file = Vec::new();
+/// let mut done = false;
+///
+/// for piece in [&[0x7E, 0x01, 0x02][..], &[0x7D, 0x5E, 0x03, 0x7E]] {
+///     decoder.feed(piece, |event| match event {
+///         DecodeEvent::Data(chunk) => file.extend_from_slice(chunk),
+///         DecodeEvent::End => done = true,
+///         DecodeEvent::Error(_) => file.clear(),
+///     });
+/// }
+///
+/// assert!(done);
+/// assert_eq!(file, vec![0x01, 0x02, 0x7E, 0x03]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChunkedDecoder {
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// Where the decoder is within the byte stream
+    state: State,
+
+    /// Set once data was delivered for the current frame
+    started: bool,
+
+    /// Number of bytes of the current frame fed so far, opening `fend` included
+    pos: usize,
+}
+
+impl ChunkedDecoder {
+    /// Creates a new ChunkedDecoder instance.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub fn new(s_char: SpecialChars) -> Result<Self, HDLCError> {
+        // Safety check to make sure the special character values are all unique
+        if s_char.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+
+        Ok(ChunkedDecoder {
+            s_char,
+            state: State::Hunt,
+            started: false,
+            pos: 0,
+        })
+    }
+
+    /// Feeds received bytes to the decoder, calling `on_event` for every decoded chunk, completed
+    /// frame and malformed frame.
+    ///
+    /// A `fesc` followed by a byte that isn't a translated byte reports
+    /// `DecodeEvent::Error(HDLCError::MissingTradeChar)`, with the offset of the `fesc` counted
+    /// from the opening `fend`, and drops the rest of the frame.
+    pub fn feed(&mut self, data: &[u8], mut on_event: impl FnMut(DecodeEvent<'_>)) {
+        let fend = self.s_char.fend;
+        let fesc = self.s_char.fesc;
+        let mut rest = data;
+
+        while !rest.is_empty() {
+            match self.state {
+                State::Hunt | State::Discard => match memchr(fend, rest) {
+                    Some(index) => {
+                        self.state = match self.state {
+                            State::Hunt => State::InFrame,
+                            _ => State::Hunt,
+                        };
+                        self.pos = 1;
+                        rest = &rest[index + 1..];
+                    }
+                    None => return,
+                },
+                State::InFrame => {
+                    let index = memchr2(fend, fesc, rest).unwrap_or(rest.len());
+                    if index > 0 {
+                        on_event(DecodeEvent::Data(&rest[..index]));
+                        self.started = true;
+                    }
+
+                    self.pos += index;
+
+                    match rest.get(index) {
+                        // Back-to-back flags, the frame only starts now
+                        Some(&byte) if byte == fend && !self.started => self.pos = 1,
+                        Some(&byte) if byte == fend => {
+                            on_event(DecodeEvent::End);
+                            self.started = false;
+                            self.state = State::Hunt;
+                        }
+                        Some(_) => {
+                            self.pos += 1;
+                            self.state = State::Escaped;
+                        }
+                        None => return,
+                    }
+                    rest = &rest[index + 1..];
+                }
+                State::Escaped => {
+                    let byte = rest[0];
+                    rest = &rest[1..];
+                    self.pos += 1;
+                    match self.s_char.translate.decode(&byte) {
+                        Some(c) => {
+                            on_event(DecodeEvent::Data(core::slice::from_ref(c)));
+                            self.started = true;
+                            self.state = State::InFrame;
+                        }
+                        None => {
+                            on_event(DecodeEvent::Error(HDLCError::MissingTradeChar {
+                                offset: self.pos - 2,
+                            }));
+                            self.started = false;
+                            self.pos = 1;
+                            // A flag right after the escape starts the next frame
+                            self.state = if byte == fend {
+                                State::InFrame
+                            } else {
+                                State::Discard
+                            };
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Drops any partial frame and waits for the next opening `fend`.
+    pub fn reset(&mut self) {
+        self.started = false;
+        self.state = State::Hunt;
+    }
+}
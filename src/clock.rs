@@ -0,0 +1,68 @@
+//! Receive timestamps for decoded frames
+
+/// A source of timestamps for received frames, see [`FrameReader::read_frame_timed`] and
+/// [`Decoder::next_frame_timed`].
+///
+/// Any `FnMut() -> T` closure is a clock, so a tick counter or an RTC works as well as
+/// [`SystemClock`].
+///
+/// [`FrameReader::read_frame_timed`]: crate::FrameReader::read_frame_timed
+/// [`Decoder::next_frame_timed`]: crate::Decoder::next_frame_timed
+///
+/// # Example
+/// ```rust
+/// use hdlc::{Decoder, SpecialChars};
+///
+/// let mut ticks = 0u32;
+/// let mut clock = || {
+///     ticks += 1;
+///     ticks
+/// };
+///
+/// let mut decoder = Decoder::new(SpecialChars::default()).unwrap();
+/// decoder.push(&[0x7E, 0x01, 0x7E]);
+///
+/// let frame = decoder.next_frame_timed(&mut clock).unwrap().unwrap();
+/// assert_eq!(frame.frame, vec![0x01]);
+/// assert_eq!(frame.received, 1);
+/// ```
+pub trait Clock {
+    /// The timestamp taken
+    type Instant;
+
+    /// Returns the current time.
+    fn now(&mut self) -> Self::Instant;
+}
+
+impl<T, F: FnMut() -> T> Clock for F {
+    type Instant = T;
+
+    fn now(&mut self) -> T {
+        self()
+    }
+}
+
+/// A [`Clock`] reading `std::time::Instant::now`.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    type Instant = std::time::Instant;
+
+    fn now(&mut self) -> std::time::Instant {
+        std::time::Instant::now()
+    }
+}
+
+/// A frame along with the time it was received.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TimedFrame<T> {
+    /// The frame, decoded or raw as the reader hands it out
+    pub frame: Vec<u8>,
+
+    /// When the frame was taken from the reader or decoder, right after its closing `fend` was
+    /// received or once it was buffered, whichever is later
+    pub received: T,
+}
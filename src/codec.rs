@@ -0,0 +1,269 @@
+//! Streaming frame codecs and the object-safe interface over them
+
+use crate::{
+    decode_with_ref, encode_with_ref, DecodeOptions, Decoder, EncodeOptions, EscapeMap, FcsMode,
+    Framing, HDLCError, SpecialChars,
+};
+
+/// A streaming codec turning payloads into frames and received bytes back into payloads.
+///
+/// The trait is object safe, so links speaking different framings, such as HDLC on some ports
+/// and SLIP or COBS on others, can be held as `Box<dyn FrameCodec>` and picked at runtime.
+/// [`HdlcCodec`] and [`Decoder`] implement it for HDLC.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{Decoder, FrameCodec, SpecialChars};
+///
+/// let mut codecs: Vec<Box<dyn FrameCodec>> = vec![Box::new(Decoder::new(SpecialChars::default()).unwrap())];
+/// let codec = &mut codecs[0];
+///
+/// let frame = codec.encode(&[0x01, 0x7E]).unwrap();
+/// assert_eq!(frame, vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E]);
+///
+/// codec.push(&frame);
+/// assert_eq!(codec.next_frame(), Some(Ok(vec![0x01, 0x7E])));
+/// assert_eq!(codec.next_frame(), None);
+/// ```
+pub trait FrameCodec {
+    /// Encodes `payload` into a complete frame.
+    ///
+    /// # Error
+    ///
+    /// * Any error of the codec, such as a payload too large for it.
+    fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError>;
+
+    /// Adds received bytes to the codec.
+    fn push(&mut self, data: &[u8]);
+
+    /// Removes the next complete frame from the codec and decodes it.
+    ///
+    /// # Returns
+    /// * `Option<Result<Vec<u8>>>` - The decoded payload or the reason the frame is malformed, or
+    ///   None if no complete frame is buffered.
+    fn next_frame(&mut self) -> Option<Result<Vec<u8>, HDLCError>>;
+}
+
+impl<C: FrameCodec + ?Sized> FrameCodec for Box<C> {
+    fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        (**self).encode(payload)
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        (**self).push(data);
+    }
+
+    fn next_frame(&mut self) -> Option<Result<Vec<u8>, HDLCError>> {
+        (**self).next_frame()
+    }
+}
+
+/// An HDLC encoder and decoder sharing one configuration, so both directions of a link agree
+/// on the special characters, escape map, FCS, framing and length limits.
+///
+/// Frames are encoded by [`HdlcCodec::encode`], and decoded one at a time by
+/// [`HdlcCodec::decode`] or pushed as received bytes in arbitrary pieces to
+/// [`HdlcCodec::push`] and taken by [`HdlcCodec::next_frame`].
+///
+/// # Example
+/// ```rust
+/// use hdlc::{EscapeMap, FcsMode, HdlcCodec, SpecialChars};
+///
+/// let mut codec = HdlcCodec::new(SpecialChars::default())
+///     .unwrap()
+///     .escape_map(EscapeMap::new().with(0x11))
+///     .unwrap()
+///     .fcs(FcsMode::Crc16)
+///     .max_payload_len(64);
+///
+/// let frame = codec.encode(&[0x11, 0x7E]).unwrap();
+/// assert_eq!(&frame[..5], &[0x7E, 0x7D, 0x31, 0x7D, 0x5E]);
+/// assert_eq!(codec.decode(&frame), Ok(vec![0x11, 0x7E]));
+///
+/// codec.push(&frame[..3]);
+/// assert_eq!(codec.next_frame(), None);
+/// codec.push(&frame[3..]);
+/// assert_eq!(codec.next_frame(), Some(Ok(vec![0x11, 0x7E])));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HdlcCodec {
+    /// List of HDLC special chars, escape map included
+    s_char: SpecialChars,
+
+    /// The frame check sequence following the payload
+    fcs: FcsMode,
+
+    /// Where the `fend` characters sit around a frame
+    framing: Framing,
+
+    /// Longest payload accepted, unlimited when unset
+    max_payload_len: Option<usize>,
+
+    /// Longest frame accepted, `fend` characters included, unlimited when unset
+    max_frame_len: Option<usize>,
+
+    /// The received data and framing state
+    decoder: Decoder,
+}
+
+impl HdlcCodec {
+    /// Creates a new HdlcCodec instance without FCS or length limits.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub fn new(s_char: SpecialChars) -> Result<Self, HDLCError> {
+        Ok(HdlcCodec {
+            decoder: Decoder::new(s_char.clone())?,
+            s_char,
+            fcs: FcsMode::None,
+            framing: Framing::Flags,
+            max_payload_len: None,
+            max_frame_len: None,
+        })
+    }
+
+    /// Escapes the bytes of `map` too, see [`SpecialChars::with_escape_map`].
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: The translation of a mapped byte collides with
+    ///   another special character.
+    pub fn escape_map(mut self, map: EscapeMap) -> Result<Self, HDLCError> {
+        self.s_char = self.s_char.with_escape_map(map)?;
+        Ok(self.rebuild())
+    }
+
+    /// Appends and verifies a frame check sequence. Defaults to none.
+    pub fn fcs(mut self, fcs: FcsMode) -> Self {
+        self.fcs = fcs;
+        self.rebuild()
+    }
+
+    /// Sets where the `fend` characters sit around a frame. Defaults to `Framing::Flags`.
+    pub fn framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self.rebuild()
+    }
+
+    /// Sets the longest payload encoded or decoded, FCS excluded. Unlimited by default.
+    pub fn max_payload_len(mut self, max_payload_len: usize) -> Self {
+        self.max_payload_len = Some(max_payload_len);
+        self.rebuild()
+    }
+
+    /// Sets the longest frame encoded or decoded, `fend` characters included. Longer frames
+    /// pushed are dropped without being buffered in full. Unlimited by default.
+    pub fn max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = Some(max_frame_len);
+        self.rebuild()
+    }
+
+    /// Returns the special characters, escape map included.
+    pub fn special_chars(&self) -> &SpecialChars {
+        &self.s_char
+    }
+
+    /// Returns the options frames are decoded with.
+    pub fn decode_options(&self) -> DecodeOptions {
+        DecodeOptions {
+            framing: self.framing,
+            fcs: self.fcs,
+            max_len: self.max_frame_len,
+            ..DecodeOptions::default()
+        }
+    }
+
+    /// Returns the options frames are encoded with, FCS aside.
+    pub fn encode_options(&self) -> EncodeOptions {
+        EncodeOptions {
+            framing: self.framing,
+            max_payload_len: self.max_payload_len,
+            ..EncodeOptions::default()
+        }
+    }
+
+    /// Appends the FCS to `payload` and encodes it into a frame.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::PayloadTooLarge**: The payload is longer than the maximum payload length.
+    /// * **HDLCError::FrameTooLong**: The frame is longer than the maximum frame length, so the
+    ///   peer would drop it.
+    /// * Any error [`encode_with`](crate::encode_with) returns.
+    pub fn encode(&self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        if payload.len() > self.max_payload_len.unwrap_or(usize::MAX) {
+            return Err(HDLCError::PayloadTooLarge);
+        }
+        let mut payload = payload.to_vec();
+        self.fcs.append(&mut payload);
+        let options = EncodeOptions {
+            max_payload_len: None,
+            ..self.encode_options()
+        };
+
+        let frame = encode_with_ref(&payload, &self.s_char, options)?;
+        if frame.len() > self.max_frame_len.unwrap_or(usize::MAX) {
+            return Err(HDLCError::FrameTooLong);
+        }
+        Ok(frame)
+    }
+
+    /// Decodes a single frame and verifies its FCS.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::PayloadTooLarge**: The payload is longer than the maximum payload length.
+    /// * Any error [`decode_with`](crate::decode_with) returns.
+    pub fn decode(&self, frame: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        let payload = decode_with_ref(frame, &self.s_char, self.decode_options())?;
+        self.check_payload(payload)
+    }
+
+    /// Adds received bytes to the codec.
+    pub fn push(&mut self, data: &[u8]) {
+        self.decoder.push(data);
+    }
+
+    /// Removes the next complete frame pushed to the codec and decodes it.
+    ///
+    /// # Returns
+    /// * `Option<Result<Vec<u8>>>` - The decoded payload or the reason the frame is malformed, or
+    ///   None if no complete frame is buffered.
+    pub fn next_frame(&mut self) -> Option<Result<Vec<u8>, HDLCError>> {
+        let payload = self.decoder.next_frame()?;
+        Some(payload.and_then(|payload| self.check_payload(payload)))
+    }
+
+    /// Rejects a decoded payload longer than the maximum payload length
+    fn check_payload(&self, payload: Vec<u8>) -> Result<Vec<u8>, HDLCError> {
+        if payload.len() > self.max_payload_len.unwrap_or(usize::MAX) {
+            return Err(HDLCError::PayloadTooLarge);
+        }
+        Ok(payload)
+    }
+
+    /// Applies the configuration to the decoder, dropping any buffered data
+    fn rebuild(mut self) -> Self {
+        self.decoder = Decoder::checked(self.s_char.clone(), self.decode_options());
+        self
+    }
+}
+
+impl FrameCodec for HdlcCodec {
+    fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        HdlcCodec::encode(self, payload)
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        HdlcCodec::push(self, data);
+    }
+
+    fn next_frame(&mut self) -> Option<Result<Vec<u8>, HDLCError>> {
+        HdlcCodec::next_frame(self)
+    }
+}
@@ -0,0 +1,150 @@
+//! Codec with its special characters fixed at compile time
+
+use crate::{memchr2, HDLCError, Output, SpecialChars};
+
+/// An encoder and decoder taking its special characters as const generics, so the compiler
+/// folds them into the hot loops and checks them for duplicates while building, instead of on
+/// every call.
+///
+/// Only the four usual special characters are supported, use the functions taking
+/// [`SpecialChars`] for custom translation tables or characters picked at runtime.
+///
+/// # Example
+/// ```rust
+/// use hdlc::Codec;
+///
+/// type Hdlc = Codec<0x7E, 0x7D, 0x5E, 0x5D>;
+///
+/// let frame = Hdlc::new().encode(&[0x01, 0x7E]);
+/// assert_eq!(frame, vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E]);
+/// assert_eq!(Hdlc::new().decode(&frame), Ok(vec![0x01, 0x7E]));
+/// ```
+///
+/// ```compile_fail
+/// let codec = hdlc::Codec::<0x7E, 0x7D, 0x5E, 0x5E>::new();
+/// ```
+///
+/// The codec can only be built by [`Codec::new`] or [`Default`], so the check can't be skipped.
+///
+/// ```compile_fail
+/// let codec: hdlc::Codec<0x7E, 0x7E, 0x5E, 0x5E> = hdlc::Codec(());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Codec<const FEND: u8, const FESC: u8, const TFEND: u8, const TFESC: u8>(());
+
+impl<const FEND: u8, const FESC: u8, const TFEND: u8, const TFESC: u8>
+    Codec<FEND, FESC, TFEND, TFESC>
+{
+    /// The special characters of the codec, failing the build if any of them collide
+    pub const CHARS: SpecialChars = SpecialChars::new_checked(FEND, FESC, TFEND, TFESC);
+
+    /// Creates a new Codec instance, failing the build if the special characters collide.
+    pub const fn new() -> Self {
+        // Safety check to make sure the special character values are all unique
+        let _ = Self::CHARS;
+        Codec(())
+    }
+
+    /// Encodes `data` into a frame. Never fails, the special characters are known to be
+    /// unique.
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(data.len() + 2);
+        // Pushing to a vector can't fail
+        let _ = self.encode_into(data, &mut output);
+        output
+    }
+
+    /// Encodes `data` into a frame pushed to `out`, like [`encode_into`](crate::encode_into).
+    ///
+    /// # Output
+    ///
+    /// * **`Result<usize>`**: Number of bytes pushed to `out`
+    ///
+    /// # Error
+    ///
+    /// * Any error `out` returns, such as **HDLCError::BufferTooSmall**.
+    pub fn encode_into(&self, data: &[u8], out: &mut impl Output) -> Result<usize, HDLCError> {
+        out.push(FEND)?;
+        let mut pushed = 1;
+        let mut rest = data;
+
+        // Push the runs of bytes that need no swapping in one go
+        while let Some(index) = memchr2(FEND, FESC, rest) {
+            out.extend_from_slice(&rest[..index])?;
+            let trade = if rest[index] == FEND { TFEND } else { TFESC };
+            out.extend_from_slice(&[FESC, trade])?;
+            pushed += index + 2;
+            rest = &rest[index + 1..];
+        }
+        out.extend_from_slice(rest)?;
+        out.push(FEND)?;
+
+        Ok(pushed + rest.len() + 1)
+    }
+
+    /// Decodes a frame, like [`decode`](crate::decode).
+    ///
+    /// # Error
+    ///
+    /// * Any error [`decode`](crate::decode) returns, but for
+    ///   **HDLCError::DuplicateSpecialChar**.
+    pub fn decode(&self, input: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        let mut output = Vec::with_capacity(input.len().saturating_sub(2));
+        self.decode_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    /// Decodes a frame into `out`, like [`decode_into`](crate::decode_into).
+    ///
+    /// # Output
+    ///
+    /// * **`Result<usize>`**: Number of bytes pushed to `out`
+    ///
+    /// # Error
+    ///
+    /// * Any error [`decode`](crate::decode) returns, but for
+    ///   **HDLCError::DuplicateSpecialChar**.
+    /// * Any error `out` returns, such as **HDLCError::BufferTooSmall**.
+    pub fn decode_into(&self, input: &[u8], out: &mut impl Output) -> Result<usize, HDLCError> {
+        let mut rest = match input.split_first() {
+            Some((&first, rest)) if first == FEND => rest,
+            _ => return Err(HDLCError::MissingFirstFend),
+        };
+        let mut pushed = 0;
+
+        // Push the runs of bytes that need no swapping in one go
+        while let Some(index) = memchr2(FEND, FESC, rest) {
+            out.extend_from_slice(&rest[..index])?;
+            pushed += index;
+
+            let offset = input.len() - rest.len() + index;
+            if rest[index] == FEND {
+                if index + 1 < rest.len() {
+                    return Err(HDLCError::FendCharInData { offset });
+                }
+                return Ok(pushed);
+            }
+
+            let byte = match rest.get(index + 1) {
+                Some(&b) if b == TFEND => FEND,
+                Some(&b) if b == TFESC => FESC,
+                Some(_) => return Err(HDLCError::MissingTradeChar { offset }),
+                // The trade char and the closing FEND are still to come
+                None => return Err(HDLCError::Incomplete { needed: Some(2) }),
+            };
+            out.push(byte)?;
+            pushed += 1;
+            rest = &rest[index + 2..];
+        }
+
+        Err(HDLCError::Incomplete { needed: Some(1) })
+    }
+}
+
+impl<const FEND: u8, const FESC: u8, const TFEND: u8, const TFESC: u8> Default
+    for Codec<FEND, FESC, TFEND, TFESC>
+{
+    fn default() -> Self {
+        Codec::new()
+    }
+}
@@ -0,0 +1,382 @@
+//! Push based decoder for data arriving in arbitrary pieces
+
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+#[cfg(feature = "std")]
+use std::sync::mpsc::{self, Receiver};
+#[cfg(feature = "std")]
+use std::thread::{self, JoinHandle};
+
+use crate::{
+    decode_with_ref, encode_with_ref, Clock, DecodeOptions, EncodeOptions, FrameBuffer, FrameCodec,
+    HDLCError, SpecialChars, TimedFrame,
+};
+
+/// A streaming decoder that is fed received bytes and hands out the decoded frames.
+///
+/// Partial frames are buffered between calls, so the decoder can be fed whatever the transport
+/// has available. [`Decoder::read_available`] drives a non-blocking reader, such as a `mio`
+/// stream after a readiness event.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{Decoder, SpecialChars};
+///
+/// let mut decoder = Decoder::new(SpecialChars::default()).unwrap();
+///
+/// decoder.push(&[0x7E, 0x01, 0x7D]);
+/// assert_eq!(decoder.next_frame(), None);
+///
+/// decoder.push(&[0x5E, 0x7E]);
+/// assert_eq!(decoder.next_frame(), Some(Ok(vec![0x01, 0x7E])));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// The received data and framing state
+    buffer: FrameBuffer,
+
+    /// Set once a reader reported the end of its data
+    eof: bool,
+
+    /// How the frames are decoded
+    options: DecodeOptions,
+
+    /// Age after which a partial frame is dropped by [`Decoder::expire_partial`]
+    max_partial_age: Option<u64>,
+
+    /// When the first byte of the buffered partial frame arrived, as far as known
+    partial_since: Option<u64>,
+}
+
+impl Decoder {
+    /// Creates a new Decoder instance.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub fn new(s_char: SpecialChars) -> Result<Self, HDLCError> {
+        Decoder::with_options(s_char, DecodeOptions::default())
+    }
+
+    /// Creates a new Decoder instance decoding frames as configured by `options`.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    /// * `options` - How the frames are decoded. Longer frames than `DecodeOptions::max_len` are
+    ///   dropped without being buffered in full.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub fn with_options(s_char: SpecialChars, options: DecodeOptions) -> Result<Self, HDLCError> {
+        // Safety check to make sure the special character values are all unique
+        if s_char.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+        Ok(Decoder::checked(s_char, options))
+    }
+
+    /// Creates a Decoder for special characters already checked for duplicates
+    pub(crate) fn checked(s_char: SpecialChars, options: DecodeOptions) -> Self {
+        let mut buffer = FrameBuffer::new(s_char.clone());
+        buffer.apply_options(&options);
+        Decoder {
+            buffer,
+            s_char,
+            eof: false,
+            options,
+            max_partial_age: None,
+            partial_since: None,
+        }
+    }
+
+    /// Drops partial frames older than `age` when [`Decoder::expire_partial`] is called, so a
+    /// lost closing `fend` can't hold back the following frames indefinitely. The age is in the
+    /// unit of the `now` arguments. Partial frames never expire by default.
+    pub fn max_partial_age(mut self, age: u64) -> Self {
+        self.max_partial_age = Some(age);
+        self
+    }
+
+    /// Adds received bytes to the decoder.
+    pub fn push(&mut self, data: &[u8]) {
+        self.buffer.extend(data);
+    }
+
+    /// Works like [`Decoder::push`], but notes `now` as the arrival of a partial frame starting
+    /// in `data`, for [`Decoder::expire_partial`].
+    pub fn push_at(&mut self, data: &[u8], now: u64) {
+        self.buffer.extend(data);
+        if self.partial_since.is_none() && self.buffer.has_partial() {
+            self.partial_since = Some(now);
+        }
+    }
+
+    /// Drops the buffered partial frame if its first byte arrived longer than the age set by
+    /// [`Decoder::max_partial_age`] ago. Call it periodically, after taking the complete
+    /// frames, since nothing is dropped while one is buffered.
+    ///
+    /// Bytes added by [`Decoder::push`] are timed from the first call that finds them, so
+    /// frames may take up to one call period longer to expire than with [`Decoder::push_at`].
+    ///
+    /// # Arguments
+    /// * `now` - The current time.
+    ///
+    /// # Returns
+    /// * `usize` - The number of bytes dropped.
+    pub fn expire_partial(&mut self, now: u64) -> usize {
+        let Some(max_age) = self.max_partial_age else {
+            return 0;
+        };
+        if self.buffer.find_frame().is_some() {
+            return 0;
+        }
+        if !self.buffer.has_partial() {
+            self.partial_since = None;
+            return 0;
+        }
+
+        let since = *self.partial_since.get_or_insert(now);
+        if now.saturating_sub(since) <= max_age {
+            return 0;
+        }
+        self.partial_since = None;
+        self.buffer.discard()
+    }
+
+    /// Removes the next complete frame from the decoder and decodes it.
+    ///
+    /// # Returns
+    /// * `Option<Result<Vec<u8>>>` - The decoded payload or the reason the frame is malformed, or
+    ///   None if no complete frame is buffered.
+    pub fn next_frame(&mut self) -> Option<Result<Vec<u8>, HDLCError>> {
+        loop {
+            let len = self.buffer.find_frame()?;
+            let payload = decode_with_ref(self.buffer.frame(len), &self.s_char, self.options);
+            self.buffer.consume(len);
+            // The arrival of whatever follows the frame is unknown
+            self.partial_since = None;
+
+            match payload {
+                Ok(payload) if payload.is_empty() && !self.options.empty_frames => {}
+                payload => return Some(payload),
+            }
+        }
+    }
+
+    /// Works like [`Decoder::next_frame`], but stamps the frame with the time read from `clock`.
+    ///
+    /// The clock is read when the frame is removed from the decoder, so call this right after
+    /// [`Decoder::push`] for the timestamp to match the arrival of the closing `fend`.
+    ///
+    /// # Returns
+    /// * `Option<Result<TimedFrame>>` - The decoded payload and when it was taken, or the reason
+    ///   the frame is malformed, or None if no complete frame is buffered.
+    pub fn next_frame_timed<C: Clock>(
+        &mut self,
+        clock: &mut C,
+    ) -> Option<Result<TimedFrame<C::Instant>, HDLCError>> {
+        let payload = self.next_frame()?;
+        Some(payload.map(|frame| TimedFrame {
+            frame,
+            received: clock.now(),
+        }))
+    }
+
+    /// Reads everything a non-blocking reader has available and returns all complete frames.
+    ///
+    /// Reading stops when the reader returns `io::ErrorKind::WouldBlock` or reports the end of its
+    /// data, see [`Decoder::is_eof`]. Interrupted reads are retried.
+    ///
+    /// # Error
+    ///
+    /// * Any other error returned by the reader. Bytes read before the error stay buffered.
+    #[cfg(feature = "std")]
+    pub fn read_available(
+        &mut self,
+        reader: &mut impl Read,
+    ) -> io::Result<Vec<Result<Vec<u8>, HDLCError>>> {
+        loop {
+            let result = reader.read(self.buffer.spare());
+            self.buffer.commit(*result.as_ref().unwrap_or(&0));
+            match result {
+                Ok(0) => {
+                    self.eof = true;
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(core::iter::from_fn(|| self.next_frame()).collect())
+    }
+
+    /// Returns a snapshot of the buffered data and framing state.
+    ///
+    /// Together with [`Decoder::from_state_with_options`] this lets a half-received frame
+    /// survive a restart of the process. The options aren't part of the state, they come from
+    /// the configuration of the process. Enable the `serde` feature to serialize the state.
+    pub fn state(&self) -> DecoderState {
+        DecoderState {
+            rest: self.buffer.rest.clone(),
+            overflow: self.buffer.overflow,
+            eof: self.eof,
+            partial_since: self.partial_since,
+        }
+    }
+
+    /// Creates a Decoder with the default options that resumes from a state taken by
+    /// [`Decoder::state`].
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    /// * `state` - The state to resume from.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub fn from_state(s_char: SpecialChars, state: DecoderState) -> Result<Self, HDLCError> {
+        Decoder::from_state_with_options(s_char, DecodeOptions::default(), state)
+    }
+
+    /// Creates a Decoder decoding frames as configured by `options` that resumes from a state
+    /// taken by [`Decoder::state`]. Chain [`Decoder::max_partial_age`] to keep expiring the
+    /// restored partial frame.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    /// * `options` - How the frames are decoded, the ones the state was taken with.
+    /// * `state` - The state to resume from.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub fn from_state_with_options(
+        s_char: SpecialChars,
+        options: DecodeOptions,
+        state: DecoderState,
+    ) -> Result<Self, HDLCError> {
+        let mut decoder = Decoder::with_options(s_char, options)?;
+        decoder.buffer.rest = state.rest;
+        decoder.buffer.overflow = state.overflow;
+        decoder.eof = state.eof;
+        decoder.partial_since = state.partial_since;
+        Ok(decoder)
+    }
+
+    /// Returns true once [`Decoder::read_available`] saw the reader reach the end of its data.
+    pub fn is_eof(&self) -> bool {
+        self.eof
+    }
+}
+
+/// Encodes frames the way the decoder decodes them, with the FCS and framing of its options.
+impl FrameCodec for Decoder {
+    fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        let mut payload = payload.to_vec();
+        self.options.fcs.append(&mut payload);
+        let options = EncodeOptions {
+            framing: self.options.framing,
+            ..EncodeOptions::default()
+        };
+        encode_with_ref(&payload, &self.s_char, options)
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        Decoder::push(self, data);
+    }
+
+    fn next_frame(&mut self) -> Option<Result<Vec<u8>, HDLCError>> {
+        Decoder::next_frame(self)
+    }
+}
+
+/// Snapshot of a [`Decoder`], taken by [`Decoder::state`].
+///
+/// No separate sync or escape flags are needed: a partial frame is kept raw from its opening
+/// `fend` onwards, so a trailing `fesc` is simply unescaped once the next byte arrives.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecoderState {
+    /// Received bytes not yet handed out as a frame
+    pub rest: Vec<u8>,
+
+    /// Set while dropping the bytes of an oversized frame
+    pub overflow: bool,
+
+    /// Set once a reader reported the end of its data
+    pub eof: bool,
+
+    /// When the first byte of the buffered partial frame arrived, if known
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub partial_since: Option<u64>,
+}
+
+/// Receiving end of the channel [`spawn_decoder`] delivers decoded frames on
+#[cfg(feature = "std")]
+pub type FrameReceiver = Receiver<Result<Vec<u8>, HDLCError>>;
+
+/// Spawns a worker thread reading from `reader` and delivering the decoded frames on a channel.
+///
+/// The worker stops when the reader reaches the end of its data, when a read fails, or when the
+/// receiver is dropped. Joining the returned handle gives back the read error, if any.
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+///
+/// # Example
+/// ```rust
+/// use hdlc::{spawn_decoder, SpecialChars};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(vec![0x7E, 0x01, 0x7E, 0x7E, 0x02, 0x7E]);
+/// let (frames, worker) = spawn_decoder(reader, SpecialChars::default()).unwrap();
+///
+/// let frames: Vec<_> = frames.iter().collect();
+/// assert_eq!(frames, vec![Ok(vec![0x01]), Ok(vec![0x02])]);
+/// assert!(worker.join().unwrap().is_ok());
+/// ```
+#[cfg(feature = "std")]
+pub fn spawn_decoder<R: Read + Send + 'static>(
+    mut reader: R,
+    s_char: SpecialChars,
+) -> Result<(FrameReceiver, JoinHandle<io::Result<()>>), HDLCError> {
+    let mut decoder = Decoder::new(s_char)?;
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::spawn(move || {
+        let mut chunk = [0; 1024];
+        loop {
+            let bytes_read = match reader.read(&mut chunk) {
+                Ok(0) => return Ok(()),
+                Ok(bytes_read) => bytes_read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            decoder.push(&chunk[..bytes_read]);
+
+            while let Some(frame) = decoder.next_frame() {
+                if sender.send(frame).is_err() {
+                    // Nobody is listening anymore
+                    return Ok(());
+                }
+            }
+        }
+    });
+
+    Ok((receiver, worker))
+}
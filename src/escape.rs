@@ -0,0 +1,201 @@
+//! Pluggable decision of which bytes are escaped and into what
+
+use crate::{HDLCError, SpecialChars, FEND, FESC};
+
+/// Decides which payload bytes are escaped and into what, computed instead of looked up in the
+/// translation table of [`SpecialChars`], for vendor quirks and other variants implemented
+/// outside the crate.
+///
+/// Used by [`encode_with_policy`] and [`decode_with_policy`]. [`SpecialChars`] is the default
+/// policy, swapping `fend` and `fesc` and the bytes of its [`EscapeMap`](crate::EscapeMap).
+/// A policy must escape `fend` and `fesc`, and never into `fend`.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{decode_with_policy, encode_with_policy, EscapePolicy};
+///
+/// /// Keeps every control character off the wire, like PPP with a full ACCM
+/// struct NoControlChars;
+///
+/// impl EscapePolicy for NoControlChars {
+///     fn escape(&self, byte: u8) -> Option<u8> {
+///         matches!(byte, 0x00..=0x1F | 0x7D | 0x7E).then_some(byte ^ 0x20)
+///     }
+///
+///     fn unescape(&self, translated: u8) -> Option<u8> {
+///         matches!(translated, 0x20..=0x3F | 0x5D | 0x5E).then_some(translated ^ 0x20)
+///     }
+/// }
+///
+/// let frame = encode_with_policy(&[0x01, 0x7E, 0x41], &NoControlChars).unwrap();
+/// assert_eq!(frame, vec![0x7E, 0x7D, 0x21, 0x7D, 0x5E, 0x41, 0x7E]);
+/// assert_eq!(decode_with_policy(&frame, &NoControlChars), Ok(vec![0x01, 0x7E, 0x41]));
+/// ```
+pub trait EscapePolicy {
+    /// Returns the Frame END opening and closing frames. Defaults to [`FEND`].
+    fn fend(&self) -> u8 {
+        FEND
+    }
+
+    /// Returns the Frame ESCape marking a translated byte. Defaults to [`FESC`].
+    fn fesc(&self) -> u8 {
+        FESC
+    }
+
+    /// Returns the byte sent after `fesc` in place of `byte`, or None to send `byte` as is.
+    fn escape(&self, byte: u8) -> Option<u8>;
+
+    /// Returns the byte a `translated` byte following `fesc` stands for, or None if it isn't a
+    /// translation.
+    fn unescape(&self, translated: u8) -> Option<u8>;
+}
+
+impl EscapePolicy for SpecialChars {
+    fn fend(&self) -> u8 {
+        self.fend
+    }
+
+    fn fesc(&self) -> u8 {
+        self.fesc
+    }
+
+    fn escape(&self, byte: u8) -> Option<u8> {
+        self.translate.get(&byte).copied()
+    }
+
+    fn unescape(&self, translated: u8) -> Option<u8> {
+        self.translate.decode(&translated).copied()
+    }
+}
+
+impl<P: EscapePolicy + ?Sized> EscapePolicy for &P {
+    fn fend(&self) -> u8 {
+        (**self).fend()
+    }
+
+    fn fesc(&self) -> u8 {
+        (**self).fesc()
+    }
+
+    fn escape(&self, byte: u8) -> Option<u8> {
+        (**self).escape(byte)
+    }
+
+    fn unescape(&self, translated: u8) -> Option<u8> {
+        (**self).unescape(translated)
+    }
+}
+
+impl<P: EscapePolicy + ?Sized> EscapePolicy for Box<P> {
+    fn fend(&self) -> u8 {
+        (**self).fend()
+    }
+
+    fn fesc(&self) -> u8 {
+        (**self).fesc()
+    }
+
+    fn escape(&self, byte: u8) -> Option<u8> {
+        (**self).escape(byte)
+    }
+
+    fn unescape(&self, translated: u8) -> Option<u8> {
+        (**self).unescape(translated)
+    }
+}
+
+/// Produces the escaped (encoded) message of `data` surrounded with `FEND`, escaping the
+/// bytes `policy` picks.
+///
+/// # Inputs
+/// * **&[u8]**: The payload you want to encode
+/// * **&impl EscapePolicy**: Which bytes are escaped and into what
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: Encoded output message
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: The policy leaves `fend` or `fesc` unescaped, or
+///   translates any byte of `data`, `fend` or `fesc` into `fend`.
+pub fn encode_with_policy(
+    data: &[u8],
+    policy: &(impl EscapePolicy + ?Sized),
+) -> Result<Vec<u8>, HDLCError> {
+    let fend = policy.fend();
+    let fesc = policy.fesc();
+    // Safety check to make sure the special characters survive the trip over the wire
+    match (policy.escape(fend), policy.escape(fesc)) {
+        (Some(tfend), Some(tfesc)) if tfend != fend && tfesc != fend => {}
+        _ => return Err(HDLCError::DuplicateSpecialChar),
+    }
+
+    let mut output = Vec::with_capacity(data.len() + 2);
+    output.push(fend);
+    for &byte in data {
+        match policy.escape(byte) {
+            Some(translated) if translated == fend => return Err(HDLCError::DuplicateSpecialChar),
+            Some(translated) => output.extend_from_slice(&[fesc, translated]),
+            None => output.push(byte),
+        }
+    }
+    output.push(fend);
+
+    Ok(output)
+}
+
+/// Produces the unescaped (decoded) message of a frame without `FEND` characters, reverting
+/// the translations of `policy`.
+///
+/// Bytes the policy escapes are accepted unescaped as well.
+///
+/// # Inputs
+/// * **&[u8]**: The frame you want to decode
+/// * **&impl EscapePolicy**: Which bytes are escaped and into what
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: Decoded output message
+///
+/// # Error
+///
+/// * Any error [`decode`](crate::decode) returns, but for
+///   **HDLCError::DuplicateSpecialChar**. **HDLCError::MissingTradeChar** reports a `fesc`
+///   followed by a byte the policy doesn't unescape.
+pub fn decode_with_policy(
+    input: &[u8],
+    policy: &(impl EscapePolicy + ?Sized),
+) -> Result<Vec<u8>, HDLCError> {
+    let fend = policy.fend();
+    let fesc = policy.fesc();
+    if input.first() != Some(&fend) {
+        return Err(HDLCError::MissingFirstFend);
+    }
+
+    let mut output = Vec::with_capacity(input.len().saturating_sub(2));
+    let mut offset = 1;
+    while let Some(&byte) = input.get(offset) {
+        if byte == fend {
+            if offset + 1 < input.len() {
+                return Err(HDLCError::FendCharInData { offset });
+            }
+            return Ok(output);
+        }
+
+        if byte == fesc {
+            let decoded = match input.get(offset + 1) {
+                Some(&translated) => policy.unescape(translated),
+                // The trade char and the closing FEND are still to come
+                None => return Err(HDLCError::Incomplete { needed: Some(2) }),
+            };
+            output.push(decoded.ok_or(HDLCError::MissingTradeChar { offset })?);
+            offset += 2;
+        } else {
+            output.push(byte);
+            offset += 1;
+        }
+    }
+
+    Err(HDLCError::Incomplete { needed: Some(1) })
+}
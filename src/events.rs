@@ -0,0 +1,171 @@
+//! Event driven decoding through a handler of callbacks
+
+use core::mem;
+
+use crate::{decode_with_ref, DecodeOptions, FrameBuffer, HDLCError, SpecialChars};
+
+/// Callbacks an [`EventDecoder`] invokes while splitting and decoding the bytes fed to it.
+///
+/// Only `on_frame` must be implemented, errors and resyncs are ignored by default. A mutable
+/// reference to a handler is a handler too, so the handler can stay owned by the caller.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{EventDecoder, FrameEvents, HDLCError, SpecialChars};
+///
+/// #[derive(Default)]
+/// struct Counter {
+///     frames: usize,
+///     errors: usize,
+/// }
+///
+/// impl FrameEvents for Counter {
+///     fn on_frame(&mut self, _payload: &[u8]) {
+///         self.frames += 1;
+///     }
+///
+///     fn on_error(&mut self, _error: HDLCError, _offset: u64) {
+///         self.errors += 1;
+///     }
+/// }
+///
+/// let mut counter = Counter::default();
+/// let mut decoder = EventDecoder::new(SpecialChars::default(), &mut counter);
+/// decoder.feed(&[0x7E, 0x01, 0x7E, 0x7E, 0x7D, 0x00, 0x7E]);
+///
+/// assert_eq!((counter.frames, counter.errors), (1, 1));
+/// ```
+pub trait FrameEvents {
+    /// Called with the decoded payload of every well-formed frame.
+    fn on_frame(&mut self, payload: &[u8]);
+
+    /// Called for every malformed frame, which is dropped.
+    ///
+    /// # Arguments
+    /// * `error` - Why the frame is malformed. Offsets in it count from the start of the frame.
+    /// * `offset` - Position of the frame in the byte stream, counting every byte fed.
+    fn on_error(&mut self, error: HDLCError, offset: u64) {
+        let _ = (error, offset);
+    }
+
+    /// Called when bytes are discarded to get back in sync, such as noise in front of a frame
+    /// or the bytes of a frame longer than `DecodeOptions::max_len`. Idle flags between frames
+    /// aren't counted.
+    ///
+    /// # Arguments
+    /// * `discarded` - Number of bytes discarded since the previous call.
+    fn on_resync(&mut self, discarded: usize) {
+        let _ = discarded;
+    }
+}
+
+impl<H: FrameEvents + ?Sized> FrameEvents for &mut H {
+    fn on_frame(&mut self, payload: &[u8]) {
+        (**self).on_frame(payload);
+    }
+
+    fn on_error(&mut self, error: HDLCError, offset: u64) {
+        (**self).on_error(error, offset);
+    }
+
+    fn on_resync(&mut self, discarded: usize) {
+        (**self).on_resync(discarded);
+    }
+}
+
+/// A streaming decoder reporting frames, malformed frames and resyncs to a registered
+/// [`FrameEvents`] handler as bytes are fed to it, for firmware ports and FFI consumers built
+/// around callbacks rather than polling.
+///
+/// Partial frames are buffered between calls to [`EventDecoder::feed`], like with a
+/// [`Decoder`](crate::Decoder).
+#[derive(Debug, Clone)]
+pub struct EventDecoder<H> {
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// The received data and framing state
+    buffer: FrameBuffer,
+
+    /// How the frames are decoded
+    options: DecodeOptions,
+
+    /// Number of bytes fed so far
+    fed: u64,
+
+    /// Receiver of the events
+    handler: H,
+}
+
+impl<H: FrameEvents> EventDecoder<H> {
+    /// Creates a new EventDecoder instance.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    /// * `handler` - The receiver of the events.
+    pub fn new(s_char: SpecialChars, handler: H) -> Self {
+        EventDecoder::with_options(s_char, DecodeOptions::default(), handler)
+    }
+
+    /// Creates a new EventDecoder instance decoding frames as configured by `options`.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    /// * `options` - How the frames are decoded. Longer frames than `DecodeOptions::max_len` are
+    ///   discarded without being buffered in full.
+    /// * `handler` - The receiver of the events.
+    pub fn with_options(s_char: SpecialChars, options: DecodeOptions, handler: H) -> Self {
+        let mut buffer = FrameBuffer::new(s_char.clone());
+        buffer.apply_options(&options);
+        EventDecoder {
+            s_char,
+            buffer,
+            options,
+            fed: 0,
+            handler,
+        }
+    }
+
+    /// Adds received bytes to the decoder and invokes the handler for every frame completed
+    /// by them, in stream order.
+    pub fn feed(&mut self, data: &[u8]) {
+        self.buffer.extend(data);
+        self.fed += data.len() as u64;
+
+        loop {
+            let frame_len = self.buffer.find_frame();
+            if self.buffer.skipped > 0 {
+                self.handler.on_resync(mem::take(&mut self.buffer.skipped));
+            }
+            let Some(len) = frame_len else {
+                return;
+            };
+
+            // The frame starts the buffer, which ends with the last byte fed
+            let offset = self.fed - self.buffer.rest.len() as u64;
+            let payload = decode_with_ref(self.buffer.frame(len), &self.s_char, self.options);
+            self.buffer.consume(len);
+
+            match payload {
+                Ok(payload) if payload.is_empty() && !self.options.empty_frames => {}
+                Ok(payload) => self.handler.on_frame(&payload),
+                Err(error) => self.handler.on_error(error, offset),
+            }
+        }
+    }
+
+    /// Returns a reference to the handler.
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// Returns a mutable reference to the handler.
+    pub fn handler_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+
+    /// Returns the handler, dropping any buffered data.
+    pub fn into_handler(self) -> H {
+        self.handler
+    }
+}
@@ -0,0 +1,149 @@
+//! Frame check sequences protecting the payload
+
+use crate::HDLCError;
+
+/// Which frame check sequence follows the payload, see [`DecodeOptions`](crate::DecodeOptions).
+///
+/// The FCS is computed over the unescaped payload and sent least significant byte first, ahead
+/// of the closing `fend`, as in ISO 13239 and RFC 1662.
+///
+/// # Example
+/// ```rust
+/// use hdlc::FcsMode;
+///
+/// let mut payload = b"123456789".to_vec();
+/// FcsMode::Crc16.append(&mut payload);
+///
+/// assert_eq!(&payload[9..], &[0x6E, 0x90]);
+/// assert_eq!(FcsMode::Crc16.check(&payload), Ok(&b"123456789"[..]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FcsMode {
+    /// No frame check sequence
+    #[default]
+    None,
+    /// 16 bit FCS, CRC-16/X.25
+    Crc16,
+    /// 32 bit FCS, CRC-32 as used by Ethernet
+    Crc32,
+}
+
+impl FcsMode {
+    /// Returns the number of bytes the FCS takes up in the frame.
+    pub const fn size(self) -> usize {
+        match self {
+            FcsMode::None => 0,
+            FcsMode::Crc16 => 2,
+            FcsMode::Crc32 => 4,
+        }
+    }
+
+    /// Appends the FCS of `payload` to it.
+    pub fn append(self, payload: &mut Vec<u8>) {
+        match self {
+            FcsMode::None => {}
+            FcsMode::Crc16 => payload.extend_from_slice(&fcs16(payload).to_le_bytes()),
+            FcsMode::Crc32 => payload.extend_from_slice(&fcs32(payload).to_le_bytes()),
+        }
+    }
+
+    /// Verifies the FCS at the end of a decoded frame and returns the payload in front of it.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::FcsMismatch**: The frame is shorter than the FCS, or the FCS doesn't match
+    ///   the payload.
+    pub fn check(self, frame: &[u8]) -> Result<&[u8], HDLCError> {
+        let split = frame
+            .len()
+            .checked_sub(self.size())
+            .ok_or(HDLCError::FcsMismatch)?;
+        let (payload, fcs) = frame.split_at(split);
+        let matches = match self {
+            FcsMode::None => true,
+            FcsMode::Crc16 => fcs == fcs16(payload).to_le_bytes(),
+            FcsMode::Crc32 => fcs == fcs32(payload).to_le_bytes(),
+        };
+
+        if matches {
+            Ok(payload)
+        } else {
+            Err(HDLCError::FcsMismatch)
+        }
+    }
+}
+
+/// Lookup table for the reflected CRC-16 polynomial 0x1021
+const FCS16_TABLE: [u16; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u16;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0x8408
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Lookup table for the reflected CRC-32 polynomial 0x04C11DB7
+const FCS32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Computes the 16 bit frame check sequence (CRC-16/X.25) of `data`.
+///
+/// # Example
+/// ```rust
+/// assert_eq!(hdlc::fcs16(b"123456789"), 0x906E);
+/// ```
+pub const fn fcs16(data: &[u8]) -> u16 {
+    let mut crc = 0xFFFF;
+    let mut i = 0;
+    while i < data.len() {
+        crc = (crc >> 8) ^ FCS16_TABLE[((crc ^ data[i] as u16) & 0xFF) as usize];
+        i += 1;
+    }
+    !crc
+}
+
+/// Computes the 32 bit frame check sequence (CRC-32) of `data`.
+///
+/// # Example
+/// ```rust
+/// assert_eq!(hdlc::fcs32(b"123456789"), 0xCBF4_3926);
+/// ```
+pub const fn fcs32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF;
+    let mut i = 0;
+    while i < data.len() {
+        crc = (crc >> 8) ^ FCS32_TABLE[((crc ^ data[i] as u32) & 0xFF) as usize];
+        i += 1;
+    }
+    !crc
+}
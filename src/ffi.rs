@@ -0,0 +1,262 @@
+//! C interface, enabled by the `ffi` feature
+//!
+//! The functions mirror [`encode`], [`decode`] and [`Decoder`] for C and C++ callers, declared
+//! in `include/hdlc.h`. Build the shared library with
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+//!
+//! Every function returns [`HDLC_OK`] or a negative error code. Output is written into buffers
+//! owned by the caller, and the length written, or needed if the buffer is too small, is stored
+//! in `out_len`.
+
+use core::ptr;
+use core::slice;
+
+use crate::{decode, encode, Decoder, HDLCError, SpecialChars};
+
+/// Success
+pub const HDLC_OK: i32 = 0;
+
+/// [`hdlc_decoder_next`] found no complete frame
+pub const HDLC_NO_FRAME: i32 = 1;
+
+/// A required pointer was null
+pub const HDLC_ERR_NULL: i32 = -1;
+
+/// `HDLCError::DuplicateSpecialChar`
+pub const HDLC_ERR_DUPLICATE_SPECIAL_CHAR: i32 = -2;
+
+/// `HDLCError::FendCharInData`
+pub const HDLC_ERR_FEND_IN_DATA: i32 = -3;
+
+/// `HDLCError::MissingTradeChar`
+pub const HDLC_ERR_MISSING_TRADE_CHAR: i32 = -4;
+
+/// `HDLCError::MissingFirstFend`
+pub const HDLC_ERR_MISSING_FIRST_FEND: i32 = -5;
+
+/// `HDLCError::MissingFinalFend`
+pub const HDLC_ERR_MISSING_FINAL_FEND: i32 = -6;
+
+/// The output buffer is too small, `out_len` holds the length needed
+pub const HDLC_ERR_BUFFER_TOO_SMALL: i32 = -7;
+
+/// Any other error
+pub const HDLC_ERR_OTHER: i32 = -99;
+
+/// Special characters as passed from C, a null pointer selects the defaults.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HdlcSpecialChars {
+    /// Frame END
+    pub fend: u8,
+
+    /// Frame ESCape
+    pub fesc: u8,
+
+    /// Transposed Frame END
+    pub tfend: u8,
+
+    /// Transposed Frame ESCape
+    pub tfesc: u8,
+}
+
+/// Streaming decoder handle of [`hdlc_decoder_new`], opaque to C.
+#[derive(Debug)]
+pub struct HdlcDecoder {
+    /// The wrapped decoder
+    decoder: Decoder,
+
+    /// Frame that didn't fit the caller's buffer, handed out by the next call
+    pending: Option<Vec<u8>>,
+}
+
+/// Encodes `len` bytes of `data` into a frame written to `out`.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes, or may be null if `len` is 0. `out` must point to
+/// `out_cap` writable bytes, or may be null if `out_cap` is 0. `chars` must be null or point to
+/// valid special characters, and `out_len` must point to a writable `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn hdlc_encode(
+    data: *const u8,
+    len: usize,
+    chars: *const HdlcSpecialChars,
+    out: *mut u8,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> i32 {
+    let Some(data) = (unsafe { input(data, len) }) else {
+        return HDLC_ERR_NULL;
+    };
+    match encode(data, unsafe { special_chars(chars) }) {
+        Ok(frame) => unsafe { copy_out(&frame, out, out_cap, out_len) },
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Decodes the frame of `len` bytes at `frame`, writing its payload to `out`.
+///
+/// # Safety
+///
+/// `frame` must point to `len` readable bytes, or may be null if `len` is 0. `out` must point to
+/// `out_cap` writable bytes, or may be null if `out_cap` is 0. `chars` must be null or point to
+/// valid special characters, and `out_len` must point to a writable `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn hdlc_decode(
+    frame: *const u8,
+    len: usize,
+    chars: *const HdlcSpecialChars,
+    out: *mut u8,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> i32 {
+    let Some(frame) = (unsafe { input(frame, len) }) else {
+        return HDLC_ERR_NULL;
+    };
+    match decode(frame, unsafe { special_chars(chars) }) {
+        Ok(payload) => unsafe { copy_out(&payload, out, out_cap, out_len) },
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Creates a streaming decoder, released with [`hdlc_decoder_free`]. Returns null if the
+/// special characters are duplicate.
+///
+/// # Safety
+///
+/// `chars` must be null or point to valid special characters.
+#[no_mangle]
+pub unsafe extern "C" fn hdlc_decoder_new(chars: *const HdlcSpecialChars) -> *mut HdlcDecoder {
+    let s_chars = unsafe { special_chars(chars) };
+    let Ok(decoder) = Decoder::new(s_chars) else {
+        return ptr::null_mut();
+    };
+
+    Box::into_raw(Box::new(HdlcDecoder {
+        decoder,
+        pending: None,
+    }))
+}
+
+/// Releases a decoder created by [`hdlc_decoder_new`]. Null is ignored.
+///
+/// # Safety
+///
+/// `decoder` must be null or a decoder not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn hdlc_decoder_free(decoder: *mut HdlcDecoder) {
+    if !decoder.is_null() {
+        // The pointer came from `Box::into_raw` in `hdlc_decoder_new`
+        drop(unsafe { Box::from_raw(decoder) });
+    }
+}
+
+/// Adds `len` received bytes at `data` to the decoder.
+///
+/// # Safety
+///
+/// `decoder` must be a live decoder, and `data` must point to `len` readable bytes, or may be
+/// null if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn hdlc_decoder_push(
+    decoder: *mut HdlcDecoder,
+    data: *const u8,
+    len: usize,
+) -> i32 {
+    // The caller guarantees the decoder is live and not used elsewhere meanwhile
+    let (Some(decoder), Some(data)) = (unsafe { decoder.as_mut() }, unsafe { input(data, len) })
+    else {
+        return HDLC_ERR_NULL;
+    };
+    decoder.decoder.push(data);
+
+    HDLC_OK
+}
+
+/// Writes the payload of the next complete frame to `out`.
+///
+/// Returns [`HDLC_NO_FRAME`] if no complete frame is buffered. A malformed frame is dropped and
+/// reported by its error code. A payload that doesn't fit `out` is kept for the next call,
+/// which should pass a buffer of at least `out_len` bytes.
+///
+/// # Safety
+///
+/// `decoder` must be a live decoder, `out` must point to `out_cap` writable bytes, or may be
+/// null if `out_cap` is 0, and `out_len` must point to a writable `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn hdlc_decoder_next(
+    decoder: *mut HdlcDecoder,
+    out: *mut u8,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> i32 {
+    // The caller guarantees the decoder is live and not used elsewhere meanwhile
+    let Some(decoder) = (unsafe { decoder.as_mut() }) else {
+        return HDLC_ERR_NULL;
+    };
+    let payload = match decoder.pending.take() {
+        Some(payload) => payload,
+        None => match decoder.decoder.next_frame() {
+            Some(Ok(payload)) => payload,
+            Some(Err(e)) => return error_code(&e),
+            None => return HDLC_NO_FRAME,
+        },
+    };
+
+    let code = unsafe { copy_out(&payload, out, out_cap, out_len) };
+    if code == HDLC_ERR_BUFFER_TOO_SMALL {
+        decoder.pending = Some(payload);
+    }
+    code
+}
+
+/// Returns the bytes at `data`, None if it is null while `len` isn't 0
+unsafe fn input<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        return Some(&[]);
+    }
+    // The caller guarantees `len` readable bytes at a non-null `data`
+    (!data.is_null()).then(|| unsafe { slice::from_raw_parts(data, len) })
+}
+
+/// Returns the special characters at `chars`, the defaults if it is null
+unsafe fn special_chars(chars: *const HdlcSpecialChars) -> SpecialChars {
+    // The caller guarantees a non-null `chars` points to valid special characters
+    match unsafe { chars.as_ref() } {
+        Some(c) => SpecialChars::new(c.fend, c.fesc, c.tfend, c.tfesc),
+        None => SpecialChars::default(),
+    }
+}
+
+/// Copies `data` to `out` if it fits, storing its length in `out_len` either way
+unsafe fn copy_out(data: &[u8], out: *mut u8, out_cap: usize, out_len: *mut usize) -> i32 {
+    if out_len.is_null() || (out.is_null() && out_cap > 0) {
+        return HDLC_ERR_NULL;
+    }
+    // The caller guarantees `out_len` is writable and `out_cap` bytes at a non-null `out`
+    unsafe {
+        *out_len = data.len();
+        if data.len() > out_cap {
+            return HDLC_ERR_BUFFER_TOO_SMALL;
+        }
+        if !data.is_empty() {
+            ptr::copy_nonoverlapping(data.as_ptr(), out, data.len());
+        }
+    }
+
+    HDLC_OK
+}
+
+/// Returns the error code of `error`
+fn error_code(error: &HDLCError) -> i32 {
+    match error {
+        HDLCError::DuplicateSpecialChar => HDLC_ERR_DUPLICATE_SPECIAL_CHAR,
+        HDLCError::FendCharInData { .. } => HDLC_ERR_FEND_IN_DATA,
+        HDLCError::MissingTradeChar { .. } => HDLC_ERR_MISSING_TRADE_CHAR,
+        HDLCError::MissingFirstFend => HDLC_ERR_MISSING_FIRST_FEND,
+        HDLCError::MissingFinalFend => HDLC_ERR_MISSING_FINAL_FEND,
+        HDLCError::BufferTooSmall => HDLC_ERR_BUFFER_TOO_SMALL,
+        _ => HDLC_ERR_OTHER,
+    }
+}
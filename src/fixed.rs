@@ -0,0 +1,229 @@
+//! Encoder and decoder owning fixed size buffers, for targets without a heap
+
+use crate::{encode_into_ref, HDLCError, SliceOutput, SpecialChars};
+
+/// An encoder owning a buffer of `N` bytes, suitable for a `static` on embedded targets.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{FixedEncoder, SpecialChars};
+///
+/// let mut encoder: FixedEncoder<16> = FixedEncoder::new(SpecialChars::default());
+/// assert_eq!(encoder.encode(&[0x01, 0x7E]), Ok(&[0x7E, 0x01, 0x7D, 0x5E, 0x7E][..]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedEncoder<const N: usize> {
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// Holds the last encoded frame
+    buf: [u8; N],
+}
+
+impl<const N: usize> FixedEncoder<N> {
+    /// Creates a new FixedEncoder instance.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    pub const fn new(s_char: SpecialChars) -> Self {
+        FixedEncoder {
+            s_char,
+            buf: [0; N],
+        }
+    }
+
+    /// Encodes `data` into the owned buffer and returns the encoded frame.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::BufferTooSmall**: The encoded frame is longer than `N` bytes.
+    /// * **HDLCError::DuplicateSpecialChar**: The special characters have duplicates.
+    pub fn encode(&mut self, data: &[u8]) -> Result<&[u8], HDLCError> {
+        let len = encode_into_ref(data, &self.s_char, &mut SliceOutput::new(&mut self.buf))?;
+        Ok(&self.buf[..len])
+    }
+}
+
+/// Where the [`FixedDecoder`] is within the byte stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Waiting for the opening `fend`
+    Hunt,
+    /// Collecting the bytes of a frame
+    InFrame,
+    /// The previous byte was a `fesc`
+    Escaped,
+    /// Dropping the rest of a frame that doesn't fit the buffer
+    Discard,
+}
+
+/// A decoder owning a buffer of `N` bytes that is fed one byte at a time, suitable for a `static`
+/// filled from a UART interrupt.
+///
+/// Bytes are unescaped as they arrive, so `N` is the longest payload accepted. The decoder hunts
+/// for the opening `fend` of a frame, and again after each closing `fend`. Empty frames are
+/// skipped.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{FixedDecoder, SpecialChars, FEND, FESC, TFEND, TFESC};
+/// use std::sync::Mutex;
+///
+/// static DECODER: Mutex<FixedDecoder<16>> =
+///     Mutex::new(FixedDecoder::new_checked(SpecialChars::new(FEND, FESC, TFEND, TFESC)));
+///
+/// let mut decoder = DECODER.lock().unwrap();
+/// for &byte in &[0x7E, 0x01, 0x7D, 0x5E] {
+///     assert_eq!(decoder.push(byte), Ok(None));
+/// }
+/// assert_eq!(decoder.push(0x7E), Ok(Some(&[0x01, 0x7E][..])));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedDecoder<const N: usize> {
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// Holds the payload of the current frame
+    buf: [u8; N],
+
+    /// Length of the payload in `buf`
+    len: usize,
+
+    /// Position of the last byte pushed, counted from the opening `fend` of the frame
+    pos: usize,
+
+    /// Where the decoder is within the byte stream
+    state: State,
+}
+
+impl<const N: usize> FixedDecoder<N> {
+    /// Creates a new FixedDecoder instance.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub const fn new(s_char: SpecialChars) -> Result<Self, HDLCError> {
+        // Safety check to make sure the special character values are all unique
+        if s_char.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+
+        Ok(FixedDecoder {
+            s_char,
+            buf: [0; N],
+            len: 0,
+            pos: 0,
+            state: State::Hunt,
+        })
+    }
+
+    /// Creates a new FixedDecoder instance, panicking if any of the special characters collide.
+    ///
+    /// Used to initialize a `static` the check runs at compile time, so an invalid set of special
+    /// characters fails the build.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    ///
+    /// # Example
+    /// ```compile_fail
+    /// use hdlc::{FixedDecoder, SpecialChars};
+    ///
+    /// static DECODER: FixedDecoder<16> =
+    ///     FixedDecoder::new_checked(SpecialChars::new(0x7E, 0x7E, 0x5E, 0x5D));
+    /// ```
+    pub const fn new_checked(s_char: SpecialChars) -> Self {
+        assert!(!s_char.has_duplicates(), "Duplicate special character");
+        FixedDecoder {
+            s_char,
+            buf: [0; N],
+            len: 0,
+            pos: 0,
+            state: State::Hunt,
+        }
+    }
+
+    /// Feeds one received byte to the decoder.
+    ///
+    /// # Returns
+    /// * `Result<Option<&[u8]>>` - The payload once the byte completes a frame.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::MissingTradeChar**: A `fesc` was followed by a byte that isn't a
+    ///   translated byte. The frame is dropped. The offset counts from the opening `fend`.
+    /// * **HDLCError::BufferTooSmall**: The payload is longer than `N` bytes. The rest of the frame
+    ///   is dropped.
+    pub fn push(&mut self, byte: u8) -> Result<Option<&[u8]>, HDLCError> {
+        self.pos += 1;
+        match self.state {
+            State::Hunt | State::Discard => {
+                if byte == self.s_char.fend {
+                    self.state = match self.state {
+                        State::Hunt => State::InFrame,
+                        _ => State::Hunt,
+                    };
+                    self.len = 0;
+                    self.pos = 0;
+                }
+            }
+            State::InFrame if byte == self.s_char.fend => {
+                // Back-to-back flags, the frame only starts now
+                if self.len == 0 {
+                    self.pos = 0;
+                    return Ok(None);
+                }
+                self.state = State::Hunt;
+                return Ok(Some(&self.buf[..self.len]));
+            }
+            State::InFrame if byte == self.s_char.fesc => self.state = State::Escaped,
+            State::InFrame => return self.store(byte),
+            State::Escaped => match self.s_char.translate.decode(&byte) {
+                Some(&c) => {
+                    self.state = State::InFrame;
+                    return self.store(c);
+                }
+                None => {
+                    // A flag right after the escape starts the next frame
+                    self.state = if byte == self.s_char.fend {
+                        State::InFrame
+                    } else {
+                        State::Discard
+                    };
+                    let offset = self.pos - 1;
+                    self.len = 0;
+                    self.pos = 0;
+                    return Err(HDLCError::MissingTradeChar { offset });
+                }
+            },
+        }
+
+        Ok(None)
+    }
+
+    /// Drops any partial frame and waits for the next opening `fend`.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.state = State::Hunt;
+    }
+
+    /// Appends a payload byte, switching to discard mode when the buffer is full
+    fn store(&mut self, byte: u8) -> Result<Option<&[u8]>, HDLCError> {
+        match self.buf.get_mut(self.len) {
+            Some(slot) => {
+                *slot = byte;
+                self.len += 1;
+                Ok(None)
+            }
+            None => {
+                self.state = State::Discard;
+                self.len = 0;
+                Err(HDLCError::BufferTooSmall)
+            }
+        }
+    }
+}
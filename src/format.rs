@@ -0,0 +1,224 @@
+//! Frame format field of ISO 13239 frames, as used by DLMS/COSEM
+
+use crate::{FcsMode, HDLCError};
+
+/// The two byte frame format field opening the frames of length-delimited HDLC variants: a
+/// 4 bit format type, a segmentation bit, and the 11 bit length of the frame between its flags,
+/// sent most significant bit first.
+///
+/// [`FrameFormat::wrap`] builds the contents of such a frame for [`encode`](crate::encode), and
+/// setting [`DecodeOptions::frame_format`](crate::DecodeOptions::frame_format) checks the
+/// field when decoding.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{decode_with, encode, DecodeOptions, FcsMode, FrameFormat, SpecialChars};
+///
+/// let contents = FrameFormat::wrap(&[0x03, 0x21, 0x93], false, FcsMode::Crc16).unwrap();
+/// let frame = encode(&contents, SpecialChars::default()).unwrap();
+///
+/// let options = DecodeOptions {
+///     fcs: FcsMode::Crc16,
+///     frame_format: true,
+///     ..DecodeOptions::default()
+/// };
+/// let payload = decode_with(&frame, SpecialChars::default(), options).unwrap();
+///
+/// let format = FrameFormat::check(&contents).unwrap();
+/// assert_eq!(format, FrameFormat::new(false, 7));
+/// assert_eq!(&payload[FrameFormat::LEN..], &[0x03, 0x21, 0x93]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameFormat {
+    /// Format type, only the low 4 bits are sent. [`FrameFormat::TYPE_3`] for DLMS
+    pub format_type: u8,
+
+    /// Set on all but the last frame of a segmented payload
+    pub segmented: bool,
+
+    /// Length of the frame between its flags, field and FCS included, unescaped. Only the low
+    /// 11 bits are sent
+    pub length: u16,
+}
+
+impl FrameFormat {
+    /// Format type 3 of ISO 13239, the one used by DLMS/COSEM
+    pub const TYPE_3: u8 = 0b1010;
+
+    /// Number of bytes the field takes up in the frame
+    pub const LEN: usize = 2;
+
+    /// Longest frame length the field can declare
+    pub const MAX_LENGTH: usize = 0x7FF;
+
+    /// Longest address, in bytes
+    const MAX_ADDRESS_LEN: usize = 4;
+
+    /// Creates a new FrameFormat instance of format type 3.
+    ///
+    /// # Arguments
+    /// * `segmented` - Whether more frames of the same payload follow.
+    /// * `length` - Length of the frame between its flags, field and FCS included.
+    pub fn new(segmented: bool, length: u16) -> Self {
+        FrameFormat {
+            format_type: FrameFormat::TYPE_3,
+            segmented,
+            length,
+        }
+    }
+
+    /// Reads the field from its two bytes.
+    pub fn from_bytes(bytes: [u8; 2]) -> Self {
+        let field = u16::from_be_bytes(bytes);
+        FrameFormat {
+            format_type: (field >> 12) as u8,
+            segmented: field & 0x0800 != 0,
+            length: field & 0x07FF,
+        }
+    }
+
+    /// Returns the two bytes of the field.
+    pub fn to_bytes(self) -> [u8; 2] {
+        let field = u16::from(self.format_type & 0x0F) << 12
+            | u16::from(self.segmented) << 11
+            | self.length & 0x07FF;
+        field.to_be_bytes()
+    }
+
+    /// Reads the field opening the decoded contents of a frame, FCS included, and checks the
+    /// length it declares.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::FrameLengthMismatch**: The declared length isn't the length of
+    ///   `contents`. Contents too short to hold the field are reported as declaring
+    ///   [`FrameFormat::LEN`] bytes.
+    pub fn check(contents: &[u8]) -> Result<Self, HDLCError> {
+        let Some(&[high, low]) = contents.get(..FrameFormat::LEN) else {
+            return Err(HDLCError::FrameLengthMismatch {
+                declared: FrameFormat::LEN,
+                actual: contents.len(),
+            });
+        };
+        let format = FrameFormat::from_bytes([high, low]);
+        if usize::from(format.length) != contents.len() {
+            return Err(HDLCError::FrameLengthMismatch {
+                declared: usize::from(format.length),
+                actual: contents.len(),
+            });
+        }
+
+        Ok(format)
+    }
+
+    /// Returns the contents of a format type 3 frame carrying `payload`: the frame format field,
+    /// the payload and the FCS computed over both, ready for [`encode`](crate::encode).
+    ///
+    /// # Arguments
+    /// * `payload` - The bytes following the field, addresses and control field included.
+    /// * `segmented` - Whether more frames of the same payload follow.
+    /// * `fcs` - The frame check sequence closing the frame.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::PayloadTooLarge**: The frame is longer than
+    ///   [`FrameFormat::MAX_LENGTH`].
+    pub fn wrap(payload: &[u8], segmented: bool, fcs: FcsMode) -> Result<Vec<u8>, HDLCError> {
+        let length = FrameFormat::LEN + payload.len() + fcs.size();
+        if length > FrameFormat::MAX_LENGTH {
+            return Err(HDLCError::PayloadTooLarge);
+        }
+
+        let mut contents = Vec::with_capacity(length);
+        contents.extend_from_slice(&FrameFormat::new(segmented, length as u16).to_bytes());
+        contents.extend_from_slice(payload);
+        fcs.append(&mut contents);
+
+        Ok(contents)
+    }
+
+    /// Returns the contents of a format type 3 frame carrying `info` behind a header protected
+    /// by its own header check sequence: the frame format field, the header, the HCS computed
+    /// over both, the information field and the FCS. A frame without information field carries
+    /// no HCS.
+    ///
+    /// # Arguments
+    /// * `header` - The destination and source addresses and the control field.
+    /// * `info` - The information field.
+    /// * `segmented` - Whether more frames of the same payload follow.
+    /// * `fcs` - The frame check sequence closing the frame, also used for the HCS.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::PayloadTooLarge**: The frame is longer than
+    ///   [`FrameFormat::MAX_LENGTH`].
+    pub fn wrap_with_hcs(
+        header: &[u8],
+        info: &[u8],
+        segmented: bool,
+        fcs: FcsMode,
+    ) -> Result<Vec<u8>, HDLCError> {
+        let hcs_len = if info.is_empty() { 0 } else { fcs.size() };
+        let length = FrameFormat::LEN + header.len() + hcs_len + info.len() + fcs.size();
+        if length > FrameFormat::MAX_LENGTH {
+            return Err(HDLCError::PayloadTooLarge);
+        }
+
+        let mut contents = Vec::with_capacity(length);
+        contents.extend_from_slice(&FrameFormat::new(segmented, length as u16).to_bytes());
+        contents.extend_from_slice(header);
+        if !info.is_empty() {
+            fcs.append(&mut contents);
+            contents.extend_from_slice(info);
+        }
+        fcs.append(&mut contents);
+
+        Ok(contents)
+    }
+
+    /// Verifies the header check sequence of the decoded contents of a frame, FCS included,
+    /// and returns where its information field starts.
+    ///
+    /// The header ends after the destination and source addresses, each closed by a byte with
+    /// its lowest bit set, and the control field. A frame without information field carries no
+    /// HCS.
+    ///
+    /// # Arguments
+    /// * `contents` - The frame between its flags, opened by the frame format field.
+    /// * `fcs` - The frame check sequence closing the frame, also used for the HCS.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::HcsMismatch**: The HCS doesn't match the header, an address is longer
+    ///   than 4 bytes, or the frame ends inside the header.
+    pub fn check_hcs(contents: &[u8], fcs: FcsMode) -> Result<usize, HDLCError> {
+        let end = contents.len().saturating_sub(fcs.size());
+        let body = &contents[..end];
+        let destination = address_len(body.get(FrameFormat::LEN..).unwrap_or_default())?;
+        let source = address_len(&body[FrameFormat::LEN + destination..])?;
+        let header_len = FrameFormat::LEN + destination + source + 1;
+        if body.len() < header_len {
+            return Err(HDLCError::HcsMismatch);
+        }
+        if body.len() == header_len {
+            return Ok(header_len);
+        }
+
+        let info = header_len + fcs.size();
+        let header = body.get(..info).ok_or(HDLCError::HcsMismatch)?;
+        fcs.check(header).map_err(|_| HDLCError::HcsMismatch)?;
+
+        Ok(info)
+    }
+}
+
+/// Returns the length of the address opening `bytes`, closed by a byte with its lowest bit set
+fn address_len(bytes: &[u8]) -> Result<usize, HDLCError> {
+    bytes
+        .iter()
+        .take(FrameFormat::MAX_ADDRESS_LEN)
+        .position(|&b| b & 1 == 1)
+        .map(|index| index + 1)
+        .ok_or(HDLCError::HcsMismatch)
+}
@@ -0,0 +1,120 @@
+//! Decoded frames carrying metadata along with the payload
+
+use core::ops::Deref;
+
+use memchr::memchr;
+
+use crate::{decode_with, DecodeOptions, FcsMode, HDLCError, SpecialChars};
+
+/// A decoded frame, owning its payload and recording how it was received.
+///
+/// `TryFrom<&[u8]>` decodes with the default special characters and options, and
+/// [`DecodedFrame::decode`] takes both.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{DecodedFrame, FcsMode};
+///
+/// let frame = DecodedFrame::try_from(&[0x7E, 0x01, 0x7D, 0x5E, 0x7E][..]).unwrap();
+///
+/// assert_eq!(frame.payload(), &[0x01, 0x7E]);
+/// assert_eq!(frame.len(), 2);
+/// assert!(frame.has_escapes());
+/// assert_eq!(frame.fcs(), FcsMode::None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecodedFrame {
+    /// The payload, without its frame check sequence
+    payload: Vec<u8>,
+
+    /// Whether the encoded frame held escape sequences
+    escapes: bool,
+
+    /// The frame check sequence verified when decoding
+    fcs: FcsMode,
+}
+
+impl DecodedFrame {
+    /// Decodes `frame` as configured by `options`, like [`decode_with`].
+    ///
+    /// # Error
+    ///
+    /// * Any error [`decode_with`] returns.
+    pub fn decode(
+        frame: &[u8],
+        s_chars: SpecialChars,
+        options: DecodeOptions,
+    ) -> Result<Self, HDLCError> {
+        let escapes = memchr(s_chars.fesc, frame).is_some();
+        let payload = decode_with(frame, s_chars, options)?;
+
+        Ok(DecodedFrame {
+            // A frame without payload carries no FCS either
+            fcs: if payload.is_empty() {
+                FcsMode::None
+            } else {
+                options.fcs
+            },
+            payload,
+            escapes,
+        })
+    }
+
+    /// Returns the payload.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Returns the payload, consuming the frame.
+    pub fn into_payload(self) -> Vec<u8> {
+        self.payload
+    }
+
+    /// Returns the length of the payload in bytes.
+    pub fn len(&self) -> usize {
+        self.payload.len()
+    }
+
+    /// Returns true if the frame carried no payload.
+    pub fn is_empty(&self) -> bool {
+        self.payload.is_empty()
+    }
+
+    /// Returns true if the encoded frame held escape sequences.
+    pub fn has_escapes(&self) -> bool {
+        self.escapes
+    }
+
+    /// Returns the frame check sequence that was verified, `FcsMode::None` if none was.
+    pub fn fcs(&self) -> FcsMode {
+        self.fcs
+    }
+}
+
+impl TryFrom<&[u8]> for DecodedFrame {
+    type Error = HDLCError;
+
+    fn try_from(frame: &[u8]) -> Result<Self, HDLCError> {
+        DecodedFrame::decode(frame, SpecialChars::default(), DecodeOptions::default())
+    }
+}
+
+impl Deref for DecodedFrame {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+impl AsRef<[u8]> for DecodedFrame {
+    fn as_ref(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+impl From<DecodedFrame> for Vec<u8> {
+    fn from(frame: DecodedFrame) -> Vec<u8> {
+        frame.payload
+    }
+}
@@ -0,0 +1,189 @@
+//! Hexdump rendering and hex-string helpers of frames for debugging
+
+use core::fmt;
+use core::fmt::Write;
+
+use crate::{decode, encode, HDLCError, SpecialChars};
+
+/// Number of bytes rendered per line
+const LINE_LEN: usize = 16;
+
+/// Renders a raw frame as offset-annotated hex through `Display` and `Debug`, for readable
+/// protocol debugging logs.
+///
+/// Every line starts with the offset of its first byte and holds up to 16 bytes. Flags are
+/// rendered in square brackets, and both bytes of an escape sequence in angle brackets.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{HexFrame, SpecialChars};
+///
+/// let frame = [0x7E, 0x01, 0x7D, 0x5E, 0x02, 0x7E];
+/// let hex = HexFrame::new(&frame, SpecialChars::default());
+///
+/// assert_eq!(hex.to_string(), "0000: [7E] 01 <7D> <5E> 02 [7E]");
+/// ```
+#[derive(Clone, Copy)]
+pub struct HexFrame<'a> {
+    /// The frame being rendered
+    frame: &'a [u8],
+
+    /// Frame END character, highlighted as a flag
+    fend: u8,
+
+    /// Frame ESCape character, highlighted along with the byte following it
+    fesc: u8,
+}
+
+impl<'a> HexFrame<'a> {
+    /// Creates a new HexFrame instance rendering `frame`, encoded with `s_chars`.
+    pub fn new(frame: &'a [u8], s_chars: SpecialChars) -> Self {
+        HexFrame {
+            frame,
+            fend: s_chars.fend,
+            fesc: s_chars.fesc,
+        }
+    }
+}
+
+impl fmt::Display for HexFrame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut escaped = false;
+        for (index, &byte) in self.frame.iter().enumerate() {
+            if index % LINE_LEN == 0 {
+                if index > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "{:04X}:", index)?;
+            }
+
+            if escaped {
+                // The trade char of an escape sequence
+                escaped = false;
+                write!(f, " <{:02X}>", byte)?;
+            } else if byte == self.fend {
+                write!(f, " [{:02X}]", byte)?;
+            } else if byte == self.fesc {
+                escaped = true;
+                write!(f, " <{:02X}>", byte)?;
+            } else {
+                write!(f, " {:02X}", byte)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for HexFrame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Encodes the payload given as a hex string into a frame, returned as a hex string, for
+/// payloads copy-pasted from terminals and tickets.
+///
+/// Bytes are read as pairs of hex digits, in either case and optionally prefixed by `0x`.
+/// Whitespace, `:`, `-`, `,` and `_` between bytes are ignored. The frame is returned as upper
+/// case bytes separated by spaces.
+///
+/// # Inputs
+/// * **hex**: The payload as a hex string
+/// * **s_chars**: Special Characters to escape
+///
+/// # Output
+///
+/// * **Result<String>**: Encoded frame as a hex string
+///
+/// # Error
+///
+/// * **HDLCError::InvalidHex**: A character isn't a hex digit or separator, or a byte is
+///   missing its second digit.
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Caught a duplicate special
+///   character."
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+///
+/// let frame = hdlc::encode_hex("01:7e 0x02", chars).unwrap();
+///
+/// assert_eq!(frame, "7E 01 7D 5E 02 7E");
+/// ```
+pub fn encode_hex(hex: &str, s_chars: SpecialChars) -> Result<String, HDLCError> {
+    let data = parse_hex(hex)?;
+    Ok(format_hex(&encode(&data, s_chars)?))
+}
+
+/// Decodes the frame given as a hex string, returning the payload as a hex string, for frames
+/// copy-pasted from terminals and tickets.
+///
+/// The hex string is read as by [`encode_hex`], and the payload returned as upper case bytes
+/// separated by spaces.
+///
+/// # Inputs
+/// * **hex**: The frame as a hex string
+/// * **s_chars**: Special Characters to unescape
+///
+/// # Output
+///
+/// * **Result<String>**: Decoded payload as a hex string
+///
+/// # Error
+///
+/// * **HDLCError::InvalidHex**: A character isn't a hex digit or separator, or a byte is
+///   missing its second digit.
+/// * Any error [`decode`] returns for the frame, with offsets counted in bytes of the frame.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+///
+/// let payload = hdlc::decode_hex("7E 01 7D 5E 02 7E", chars).unwrap();
+///
+/// assert_eq!(payload, "01 7E 02");
+/// ```
+pub fn decode_hex(hex: &str, s_chars: SpecialChars) -> Result<String, HDLCError> {
+    let frame = parse_hex(hex)?;
+    Ok(format_hex(&decode(&frame, s_chars)?))
+}
+
+/// Parses the bytes of a hex string, skipping separators and `0x` prefixes
+fn parse_hex(hex: &str) -> Result<Vec<u8>, HDLCError> {
+    let mut bytes = Vec::with_capacity(hex.len() / 2);
+    let mut chars = hex.char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        if c.is_whitespace() || matches!(c, ':' | '-' | ',' | '_') {
+            continue;
+        }
+        if c == '0' && matches!(chars.peek(), Some((_, 'x' | 'X'))) {
+            chars.next();
+            continue;
+        }
+
+        let high = c.to_digit(16).ok_or(HDLCError::InvalidHex { offset })?;
+        let low = match chars.next() {
+            Some((_, low)) => low.to_digit(16),
+            None => None,
+        }
+        .ok_or(HDLCError::InvalidHex { offset })?;
+        bytes.push((high << 4 | low) as u8);
+    }
+
+    Ok(bytes)
+}
+
+/// Formats bytes as upper case hex separated by spaces
+fn format_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 3);
+    for (index, byte) in bytes.iter().enumerate() {
+        if index > 0 {
+            hex.push(' ');
+        }
+        // Writing into a String can't fail
+        let _ = write!(hex, "{:02X}", byte);
+    }
+
+    hex
+}
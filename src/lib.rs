@@ -65,28 +65,152 @@
 
 #![deny(missing_docs)]
 
+use core::mem::MaybeUninit;
+use core::ops::Range;
+
+use memchr::memchr;
+#[cfg(not(feature = "simd"))]
+use memchr::memchr2;
+#[cfg(feature = "simd")]
+use simd::memchr2;
+
 use thiserror::Error;
 
 use cfg_block::cfg_block;
 
 cfg_block! {
     #[cfg(feature = "std")] {
-        use std::collections::{HashMap, HashSet};
+        use std::collections::HashMap;
         use std::default::Default;
-        use std::hash::Hash;
         use std::vec::Vec;
-        use std::io::Read;
+        use std::io::{self, Read, Write};
     }
     #[cfg(feature="no_std")] {
         extern crate alloc;
-        use hashbrown::collections::{HashMap, HashSet};
+        use hashbrown::collections::HashMap;
         use core::default::Default;
-        use core::hash::Hash;
         use alloc::vec::Vec;
         use embedded_io::Read;
     }
 }
 
+#[cfg(feature = "std")]
+mod arq;
+#[cfg(feature = "rayon")]
+mod batch;
+mod chunked;
+mod clock;
+mod codec;
+mod const_codec;
+mod decoder;
+mod escape;
+mod events;
+mod fcs;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fixed;
+mod format;
+mod frame;
+mod hex;
+#[cfg(feature = "std")]
+mod link;
+#[cfg(feature = "test-utils")]
+mod lossy;
+#[cfg(feature = "test-utils")]
+mod malformed;
+#[cfg(feature = "std")]
+mod metrics;
+#[cfg(feature = "std")]
+mod mux;
+mod options;
+mod output;
+#[cfg(feature = "std")]
+mod pacing;
+mod parse;
+#[cfg(feature = "pcap")]
+mod pcap;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "std")]
+mod priority;
+mod queue;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+mod retry;
+mod ring;
+mod segments;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "std")]
+mod sink;
+mod table;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+mod transform;
+mod validated;
+#[cfg(feature = "std")]
+mod writer;
+
+#[cfg(feature = "std")]
+pub use arq::{Arq, ArqConfig};
+#[cfg(feature = "rayon")]
+pub use batch::{decode_batch, encode_batch};
+pub use chunked::{ChunkedDecoder, DecodeEvent};
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{Clock, TimedFrame};
+pub use codec::{FrameCodec, HdlcCodec};
+pub use const_codec::Codec;
+#[cfg(feature = "std")]
+pub use decoder::{spawn_decoder, FrameReceiver};
+pub use decoder::{Decoder, DecoderState};
+pub use escape::{decode_with_policy, encode_with_policy, EscapePolicy};
+pub use events::{EventDecoder, FrameEvents};
+pub use fcs::{fcs16, fcs32, FcsMode};
+pub use fixed::{FixedDecoder, FixedEncoder};
+pub use format::FrameFormat;
+pub use frame::DecodedFrame;
+pub use hex::{decode_hex, encode_hex, HexFrame};
+#[cfg(feature = "std")]
+pub use link::Link;
+#[cfg(feature = "std")]
+pub use metrics::{
+    LinkMetrics, MetricsSnapshot, SizeHistogram, ThroughputSnapshot, MAX_HISTOGRAM_BOUNDS,
+};
+#[cfg(feature = "std")]
+pub use mux::{Channel, Mux, MUX_PORTS};
+pub use options::{DecodeOptions, EncodeOptions, Framing};
+use output::UninitOutput;
+#[cfg(feature = "std")]
+use output::WriteOutput;
+pub use output::{Output, SliceOutput};
+#[cfg(feature = "std")]
+pub use pacing::Pacing;
+pub use parse::{parse_frame, ParseError};
+#[cfg(feature = "pcap")]
+pub use pcap::{PcapReader, PcapWriter, LINKTYPE_C_HDLC, LINKTYPE_PPP_HDLC, LINKTYPE_USER0};
+#[cfg(feature = "std")]
+pub use pool::{BufferPool, PooledBuffer};
+#[cfg(feature = "std")]
+pub use priority::PrioritySink;
+pub use queue::{FrameConsumer, FrameProducer, FrameQueue};
+#[cfg(feature = "std")]
+pub use reader::DecodingReader;
+#[cfg(feature = "std")]
+pub use retry::{ExponentialBackoff, FixedBackoff, LinearBackoff, RetryPolicy};
+#[cfg(feature = "std")]
+pub use ring::decode_frames_deque;
+pub use ring::decode_frames_split;
+pub use segments::{encode_segments, EncodedSegments, Segments};
+#[cfg(feature = "std")]
+pub use sink::FrameSink;
+pub use table::TableEncoder;
+pub use transform::{decode_transformed, encode_transformed, PayloadTransform};
+pub use validated::ValidatedSpecialChars;
+#[cfg(feature = "std")]
+pub use writer::EncodingWriter;
+
 /// Default Frame Ending character
 pub const FEND: u8 = 0x7E;
 
@@ -108,64 +232,360 @@ pub const TFESC: u8 = 0x5D;
 /// * **FESC**  = 0x7D;
 /// * **TFEND** = 0x5E;
 /// * **TFESC** = 0x5D;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpecialChars {
     /// Frame END. Byte that marks the beginning and end of a packet
     pub fend: u8,
     /// Frame ESCape. Byte that marks the start of a swap byte
     pub fesc: u8,
-    /// a hashmap(u8, u8) of the translation values when in escape mode
-    pub translate: HashMap<u8, u8>,
+    /// the translation values when in escape mode
+    translate: Translations,
 }
 
 impl Default for SpecialChars {
     /// Creates the default SpecialChars structure for encoding/decoding a packet
     fn default() -> SpecialChars {
-        SpecialChars {
-            fend: FEND,
-            fesc: FESC,
-            translate: HashMap::from([(FEND, TFEND), (FESC, TFESC)]),
-        }
+        SpecialChars::new(FEND, FESC, TFEND, TFESC)
     }
 }
 impl SpecialChars {
     /// Creates a new SpecialChars structure for encoding/decoding a packet
-    pub fn new(fend: u8, fesc: u8, tfend: u8, tfesc: u8) -> SpecialChars {
-        let translate = HashMap::from([(fend, tfend), (fesc, tfesc)]);
+    pub const fn new(fend: u8, fesc: u8, tfend: u8, tfesc: u8) -> SpecialChars {
         SpecialChars {
             fend,
             fesc,
-            translate,
+            translate: Translations::new().with(fend, tfend).with(fesc, tfesc),
         }
     }
 
+    /// Creates a new SpecialChars structure, checking the four bytes for collisions once, up front.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Any of the four bytes collide.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hdlc::{HDLCError, SpecialChars};
+    ///
+    /// assert!(SpecialChars::try_new(0x71, 0x70, 0x51, 0x50).is_ok());
+    /// assert_eq!(
+    ///     SpecialChars::try_new(0x71, 0x70, 0x71, 0x50).err(),
+    ///     Some(HDLCError::DuplicateSpecialChar)
+    /// );
+    /// ```
+    pub const fn try_new(
+        fend: u8,
+        fesc: u8,
+        tfend: u8,
+        tfesc: u8,
+    ) -> Result<SpecialChars, HDLCError> {
+        let s_chars = SpecialChars::new(fend, fesc, tfend, tfesc);
+        if s_chars.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+        Ok(s_chars)
+    }
+
+    /// Creates a new SpecialChars structure, panicking if any of the four bytes collide.
+    ///
+    /// Used to initialize a `const` the check runs at compile time, so an invalid set of special
+    /// characters fails the build.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hdlc::SpecialChars;
+    ///
+    /// const CHARS: SpecialChars = SpecialChars::new_checked(0x71, 0x70, 0x51, 0x50);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use hdlc::SpecialChars;
+    ///
+    /// const CHARS: SpecialChars = SpecialChars::new_checked(0x71, 0x70, 0x50, 0x50);
+    /// ```
+    pub const fn new_checked(fend: u8, fesc: u8, tfend: u8, tfesc: u8) -> SpecialChars {
+        let s_chars = SpecialChars::new(fend, fesc, tfend, tfesc);
+        assert!(!s_chars.has_duplicates(), "Duplicate special character");
+        s_chars
+    }
+
     /// Creates a SpecialChars structure for encoding/decoding a packet, but with custom byte translations
     pub fn new_custom(fend: u8, fesc: u8, translations: HashMap<u8, u8>) -> SpecialChars {
-        // println!(
-        //     "Creating specialchars with custom translation: {:#?}",
-        //     translations
-        // );
         SpecialChars {
             fend,
             fesc,
-            translate: translations,
+            translate: translations.into(),
+        }
+    }
+
+    /// Returns the special characters with every byte of `map` escaped too, translated the PPP
+    /// way by flipping bit 5 (`byte ^ 0x20`).
+    ///
+    /// Escaping is transparent to a receiver configured with the same or a larger map, because
+    /// the unescaped form of a mapped byte is still accepted. `fend` and `fesc` keep their own
+    /// translations.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: The translation of a mapped byte collides with
+    ///   `fend`, `fesc` or another translation, such as `0x5E` translated into `0x7E`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hdlc::{encode, EscapeMap, HDLCError, SpecialChars};
+    ///
+    /// // Keep XON and XOFF off the wire for software flow control
+    /// let map = EscapeMap::new().with(0x11).with(0x13);
+    /// let chars = SpecialChars::default().with_escape_map(map).unwrap();
+    ///
+    /// let result = encode(&[0x11, 0x01, 0x13], chars);
+    /// assert_eq!(result.unwrap(), vec![0x7E, 0x7D, 0x31, 0x01, 0x7D, 0x33, 0x7E]);
+    ///
+    /// let colliding = SpecialChars::default().with_escape_map(EscapeMap::new().with(0x5E));
+    /// assert_eq!(colliding, Err(HDLCError::DuplicateSpecialChar));
+    /// ```
+    pub const fn with_escape_map(mut self, map: EscapeMap) -> Result<SpecialChars, HDLCError> {
+        let mut byte = 0;
+        while byte < 256 {
+            let b = byte as u8;
+            if map.contains(b) && b != self.fend && b != self.fesc {
+                self.translate = self.translate.with(b, b ^ 0x20);
+            }
+            byte += 1;
+        }
+        if self.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
         }
+        Ok(self)
+    }
+
+    /// Returns the translation values used in escape mode, `fend` and `fesc` included.
+    pub const fn translations(&self) -> &Translations {
+        &self.translate
     }
 
     /// Takes the translation map and reverses the key:value pairs so we can decode them
     pub fn decodes(&self) -> HashMap<u8, u8> {
-        let mut t = HashMap::new();
-        let decodes: Vec<(u8, u8)> = self
-            .translate
-            .keys()
-            .into_iter()
-            .map(|k| (*self.translate.get(k).unwrap(), *k))
-            .collect();
-        decodes.iter().for_each(|&(k, v)| {
-            t.insert(k, v);
-        });
+        self.translate.iter().map(|(k, v)| (v, k)).collect()
+    }
+
+    /// Returns true if `fend` and `fesc` are the only escaped bytes
+    const fn escapes_flags_only(&self) -> bool {
+        self.translate.len == 2
+            && self.fend != self.fesc
+            && self.translate.contains_key(&self.fend)
+            && self.translate.contains_key(&self.fesc)
+    }
+
+    /// Checks that `fend`, `fesc` and every translated byte are unique
+    const fn has_duplicates(&self) -> bool {
+        // The usual set of four bytes is checked directly, without walking the whole table
+        if self.escapes_flags_only() {
+            if let (Some(tfend), Some(tfesc)) = (
+                self.translate.encodes[self.fend as usize],
+                self.translate.encodes[self.fesc as usize],
+            ) {
+                return tfend == tfesc
+                    || tfend == self.fend
+                    || tfend == self.fesc
+                    || tfesc == self.fend
+                    || tfesc == self.fesc;
+            }
+        }
+
+        let mut seen = [false; 256];
+        seen[self.fend as usize] = true;
+        if seen[self.fesc as usize] {
+            return true;
+        }
+        seen[self.fesc as usize] = true;
+
+        let mut byte = 0;
+        while byte < 256 {
+            if let Some(t) = self.translate.encodes[byte] {
+                if seen[t as usize] {
+                    return true;
+                }
+                seen[t as usize] = true;
+            }
+            byte += 1;
+        }
+
+        false
+    }
+}
+
+/// Table of the byte translations applied in escape mode, usable in `const` contexts.
+///
+/// Maps every byte that is escaped to the byte following `fesc`, and keeps the reverse mapping
+/// for decoding.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Translations {
+    /// Translated byte for every byte that is escaped
+    encodes: [Option<u8>; 256],
+
+    /// Original byte for every translated byte
+    decodes: [Option<u8>; 256],
+
+    /// Number of escaped bytes
+    len: usize,
+}
+
+impl Translations {
+    /// Creates an empty translation table
+    pub const fn new() -> Translations {
+        Translations {
+            encodes: [None; 256],
+            decodes: [None; 256],
+            len: 0,
+        }
+    }
+
+    /// Returns the table with `byte` translated to `translated`, replacing any previous translation
+    pub const fn with(mut self, byte: u8, translated: u8) -> Translations {
+        match self.encodes[byte as usize] {
+            Some(old) => self.decodes[old as usize] = None,
+            None => self.len += 1,
+        }
+        self.encodes[byte as usize] = Some(translated);
+        self.decodes[translated as usize] = Some(byte);
+        self
+    }
 
-        t
+    /// Translates `byte` to `translated`, returning the previous translation
+    pub fn insert(&mut self, byte: u8, translated: u8) -> Option<u8> {
+        let old = self.encodes[byte as usize];
+        *self = self.with(byte, translated);
+        old
+    }
+
+    /// Removes the translation of `byte`, returning it
+    pub fn remove(&mut self, byte: u8) -> Option<u8> {
+        let old = self.encodes[byte as usize].take();
+        if let Some(old) = old {
+            self.decodes[old as usize] = None;
+            self.len -= 1;
+        }
+        old
+    }
+
+    /// Returns the number of escaped bytes
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no byte is escaped
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the translation of `byte`, if it is escaped
+    pub const fn get(&self, byte: &u8) -> Option<&u8> {
+        self.encodes[*byte as usize].as_ref()
+    }
+
+    /// Returns true if `byte` is escaped
+    pub const fn contains_key(&self, byte: &u8) -> bool {
+        self.encodes[*byte as usize].is_some()
+    }
+
+    /// Returns the byte a translated byte stands for
+    pub const fn decode(&self, translated: &u8) -> Option<&u8> {
+        self.decodes[*translated as usize].as_ref()
+    }
+
+    /// Iterates over the `(byte, translated)` pairs in byte order
+    pub fn iter(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        (0..=u8::MAX).filter_map(|b| self.encodes[b as usize].map(|t| (b, t)))
+    }
+}
+
+impl Default for Translations {
+    fn default() -> Translations {
+        Translations::new()
+    }
+}
+
+impl core::fmt::Debug for Translations {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<(u8, u8)> for Translations {
+    fn from_iter<I: IntoIterator<Item = (u8, u8)>>(iter: I) -> Translations {
+        iter.into_iter()
+            .fold(Translations::new(), |t, (byte, translated)| {
+                t.with(byte, translated)
+            })
+    }
+}
+
+/// Serialized as the list of `(byte, translated)` pairs
+#[cfg(feature = "serde")]
+impl serde::Serialize for Translations {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Translations {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pairs = <Vec<(u8, u8)> as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
+
+impl From<HashMap<u8, u8>> for Translations {
+    fn from(translations: HashMap<u8, u8>) -> Translations {
+        translations.into_iter().collect()
+    }
+}
+
+/// Set of bytes escaped on top of `fend` and `fesc`, like the PPP Async-Control-Character-Map
+/// but covering all 256 byte values. Applied with [`SpecialChars::with_escape_map`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EscapeMap {
+    /// One bit per byte value
+    bits: [u32; 8],
+}
+
+impl EscapeMap {
+    /// Creates an empty escape map
+    pub const fn new() -> EscapeMap {
+        EscapeMap { bits: [0; 8] }
+    }
+
+    /// Creates an escape map from a 32 bit PPP ACCM, where bit `n` escapes the byte `n`
+    pub const fn from_accm(accm: u32) -> EscapeMap {
+        EscapeMap {
+            bits: [accm, 0, 0, 0, 0, 0, 0, 0],
+        }
+    }
+
+    /// Returns the map with `byte` added
+    pub const fn with(mut self, byte: u8) -> EscapeMap {
+        self.bits[(byte / 32) as usize] |= 1 << (byte % 32);
+        self
+    }
+
+    /// Returns true if `byte` is in the map
+    pub const fn contains(&self, byte: u8) -> bool {
+        self.bits[(byte / 32) as usize] & (1 << (byte % 32)) != 0
+    }
+
+    /// Returns an iterator over the bytes in the map, in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(|&b| self.contains(b))
+    }
+}
+
+impl FromIterator<u8> for EscapeMap {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> EscapeMap {
+        iter.into_iter().fold(EscapeMap::new(), EscapeMap::with)
     }
 }
 
@@ -177,240 +597,1950 @@ impl SpecialChars {
 ///
 /// # Output
 ///
-/// * **`Result<Vec<u8>>`**: Encoded output message
+/// * **`Result<Vec<u8>>`**: Encoded output message
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+///
+/// # Todo
+///
+/// Catch more errors, like an incomplete packet
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let input: Vec<u8> = vec![0x01, 0x50, 0x00, 0x00, 0x00, 0x05, 0x80, 0x09];
+/// let op_vec = hdlc::encode(&input.to_vec(), chars);
+/// ```
+pub fn encode(data: &[u8], s_chars: SpecialChars) -> Result<Vec<u8>, HDLCError> {
+    encode_ref(data, &s_chars)
+}
+
+/// Works like [`encode`], borrowing the special characters so callers encoding many frames
+/// don't copy them for every frame
+pub(crate) fn encode_ref(data: &[u8], s_chars: &SpecialChars) -> Result<Vec<u8>, HDLCError> {
+    // Allocate once, at the exact size of the encoded message
+    let mut output = Vec::with_capacity(encoded_len_of(data, s_chars));
+    encode_into_ref(data, s_chars, &mut output)?;
+
+    Ok(output)
+}
+
+/// Returns the length of the escaped (encoded) message of `data`, flags included, without
+/// encoding it.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+///
+/// assert_eq!(hdlc::encoded_len(&[0x01, 0x7E, 0x02], chars), 6);
+/// ```
+pub fn encoded_len(data: &[u8], s_chars: SpecialChars) -> usize {
+    encoded_len_of(data, &s_chars)
+}
+
+/// Returns the length of the longest escaped (encoded) message a payload of `payload_len` bytes
+/// can produce, flags included. Use it to size static buffers and DMA regions.
+///
+/// # Example
+/// ```rust
+/// const MAX_FRAME: usize = hdlc::max_encoded_len(64);
+///
+/// let mut buf = [0u8; MAX_FRAME];
+/// let chars = hdlc::SpecialChars::default();
+/// assert!(hdlc::encode_to_slice(&[0x7E; 64], chars, &mut buf).is_ok());
+/// ```
+pub const fn max_encoded_len(payload_len: usize) -> usize {
+    // Every byte may be escaped, plus the opening and closing FEND
+    payload_len.saturating_mul(2).saturating_add(2)
+}
+
+/// Returns the length of the longest unescaped (decoded) message a frame of `frame_len` bytes,
+/// flags included, can hold.
+///
+/// # Example
+/// ```rust
+/// assert_eq!(hdlc::decoded_len_hint(10), 8);
+/// assert_eq!(hdlc::decoded_len_hint(1), 0);
+/// ```
+pub const fn decoded_len_hint(frame_len: usize) -> usize {
+    frame_len.saturating_sub(2)
+}
+
+/// Counts the escaped bytes of `data` to get the length of its encoded message
+fn encoded_len_of(data: &[u8], s_chars: &SpecialChars) -> usize {
+    let flags_only = s_chars.escapes_flags_only();
+    let mut escapes = 0;
+    let mut start = 0;
+    while let Some(offset) = find_escaped(&data[start..], s_chars, flags_only) {
+        escapes += 1;
+        start += offset + 1;
+    }
+
+    data.len().saturating_add(escapes).saturating_add(2)
+}
+
+/// Works like [`encode`], but reports a failed allocation instead of aborting.
+///
+/// The output is allocated once, at its exact size, before anything is encoded.
+///
+/// # Error
+///
+/// * **HDLCError::OutOfMemory**: The output couldn't be allocated.
+/// * Any error [`encode`] returns.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+///
+/// let result = hdlc::try_encode(&[0x01, 0x7E], chars);
+///
+/// assert_eq!(result.unwrap(), vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E]);
+/// ```
+pub fn try_encode(data: &[u8], s_chars: SpecialChars) -> Result<Vec<u8>, HDLCError> {
+    let mut output = Vec::new();
+    output
+        .try_reserve_exact(encoded_len_of(data, &s_chars))
+        .map_err(|_| HDLCError::OutOfMemory)?;
+    encode_into(data, s_chars, &mut output)?;
+
+    Ok(output)
+}
+
+/// Escape statistics of a message encoded by [`encode_with_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EncodeReport {
+    /// Number of bytes swapped for an escape sequence
+    pub escapes: usize,
+
+    /// Length of the encoded message, flags included
+    pub out_len: usize,
+}
+
+/// Works like [`encode`], but also reports how many bytes were escaped, for tracking the escape
+/// density of a link.
+///
+/// # Output
+///
+/// * **`Result<(Vec<u8>, EncodeReport)>`**: Encoded message and its escape statistics
+///
+/// # Error
+///
+/// * Any error [`encode`] returns.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{encode_with_report, EncodeReport, SpecialChars};
+///
+/// let (output, report) = encode_with_report(&[0x01, 0x7E, 0x7D], SpecialChars::default()).unwrap();
+///
+/// assert_eq!(output, vec![0x7E, 0x01, 0x7D, 0x5E, 0x7D, 0x5D, 0x7E]);
+/// assert_eq!(report, EncodeReport { escapes: 2, out_len: 7 });
+/// ```
+pub fn encode_with_report(
+    data: &[u8],
+    s_chars: SpecialChars,
+) -> Result<(Vec<u8>, EncodeReport), HDLCError> {
+    let output = encode(data, s_chars)?;
+    // Every escape sequence takes one byte more than the byte it replaces
+    let report = EncodeReport {
+        escapes: output.len() - data.len() - 2,
+        out_len: output.len(),
+    };
+
+    Ok((output, report))
+}
+
+/// Pushes the escaped (encoded) message surrounded with `FEND` to any [`Output`].
+///
+/// # Inputs
+/// * **&[u8]**: The bytes you want to encode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **impl Output**: The destination the encoded message is pushed to
+///
+/// # Output
+///
+/// * **`Result<usize>`**: Number of bytes pushed
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+/// * **HDLCError::BufferTooSmall**: The output is full. Part of the message may already have
+///   been pushed.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let mut output: Vec<u8> = vec![0x00];
+///
+/// let pushed = hdlc::encode_into(&[0x01, 0x7E], chars, &mut output).unwrap();
+///
+/// assert_eq!(pushed, 5);
+/// assert_eq!(output, vec![0x00, 0x7E, 0x01, 0x7D, 0x5E, 0x7E]);
+/// ```
+pub fn encode_into(
+    data: &[u8],
+    s_chars: SpecialChars,
+    out: &mut impl Output,
+) -> Result<usize, HDLCError> {
+    encode_into_ref(data, &s_chars, out)
+}
+
+/// Works like [`encode_into`], borrowing the special characters
+pub(crate) fn encode_into_ref(
+    data: &[u8],
+    s_chars: &SpecialChars,
+    out: &mut impl Output,
+) -> Result<usize, HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+
+    encode_unchecked(data, s_chars, out)
+}
+
+/// Pushes the encoded message of `data` to `out`, with special characters already known to be
+/// unique
+fn encode_unchecked(
+    data: &[u8],
+    s_chars: &SpecialChars,
+    out: &mut impl Output,
+) -> Result<usize, HDLCError> {
+    out.push(s_chars.fend)?;
+    let pushed = escape_unchecked(data, s_chars, out)?;
+    out.push(s_chars.fend)?;
+
+    Ok(pushed + 2)
+}
+
+/// Pushes the escaped bytes of `data` to `out`, without flags, with special characters already
+/// known to be unique
+fn escape_unchecked(
+    data: &[u8],
+    s_chars: &SpecialChars,
+    out: &mut impl Output,
+) -> Result<usize, HDLCError> {
+    let mut pushed = 0;
+
+    // Push the runs of bytes that need no swapping in one go
+    let flags_only = s_chars.escapes_flags_only();
+    let mut start = 0;
+    while let Some(offset) = find_escaped(&data[start..], s_chars, flags_only) {
+        let index = start + offset;
+        out.extend_from_slice(&data[start..index])?;
+        if let Some(&c) = s_chars.translate.get(&data[index]) {
+            out.extend_from_slice(&[s_chars.fesc, c])?;
+        }
+        pushed += offset + 2;
+        start = index + 1;
+    }
+
+    out.extend_from_slice(&data[start..])?;
+    pushed += data.len() - start;
+
+    Ok(pushed)
+}
+
+/// Returns the index of the first byte of `data` that `s_chars` escapes. With `flags_only` set
+/// the search is done with `memchr2`, skipping over clean runs many bytes at a time.
+fn find_escaped(data: &[u8], s_chars: &SpecialChars, flags_only: bool) -> Option<usize> {
+    if flags_only {
+        memchr2(s_chars.fend, s_chars.fesc, data)
+    } else {
+        data.iter().position(|b| s_chars.translate.contains_key(b))
+    }
+}
+
+/// Writes the escaped (encoded) message surrounded with `FEND` straight into a writer,
+/// without building the encoded message in memory first.
+///
+/// # Inputs
+/// * **&[u8]**: The bytes you want to encode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **impl Write**: The sink the encoded message is written to
+///
+/// # Output
+///
+/// * **`io::Result<usize>`**: Number of bytes written
+///
+/// # Error
+///
+/// * **io::ErrorKind::InvalidInput**: Wraps `HDLCError::DuplicateSpecialChar` if any of the
+///   `SpecialChars` are duplicate.
+/// * Any error returned by the writer.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let mut output: Vec<u8> = Vec::new();
+///
+/// let written = hdlc::encode_to_writer(&[0x01, 0x7E, 0x02], chars, &mut output).unwrap();
+///
+/// assert_eq!(written, 6);
+/// assert_eq!(output, vec![0x7E, 0x01, 0x7D, 0x5E, 0x02, 0x7E]);
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_to_writer(
+    data: &[u8],
+    s_chars: SpecialChars,
+    w: &mut impl Write,
+) -> io::Result<usize> {
+    encode_into(data, s_chars, &mut WriteOutput(w)).map_err(|e| match e {
+        HDLCError::Io(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidInput, e),
+    })
+}
+
+/// Produces escaped (encoded) message surrounded with `FEND` in a caller provided `Vec`, which
+/// is cleared first. Reusing one `Vec` for every frame keeps its allocation around, so a long
+/// running sender stops allocating once the `Vec` fits its largest frame.
+///
+/// Use [`encode_into`] to append to the `Vec` instead.
+///
+/// # Inputs
+/// * **&[u8]**: The bytes you want to encode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **&mut Vec<u8>**: The vector the encoded message is written to
+///
+/// # Output
+///
+/// * **`Result<usize>`**: Length of the encoded message
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let mut output: Vec<u8> = Vec::new();
+///
+/// for payload in [&[0x01, 0x02][..], &[0x7E]] {
+///     hdlc::encode_to_vec(payload, chars.clone(), &mut output).unwrap();
+/// }
+///
+/// assert_eq!(output, vec![0x7E, 0x7D, 0x5E, 0x7E]);
+/// ```
+pub fn encode_to_vec(
+    data: &[u8],
+    s_chars: SpecialChars,
+    out: &mut Vec<u8>,
+) -> Result<usize, HDLCError> {
+    out.clear();
+    encode_into(data, s_chars, out)
+}
+
+/// Escapes a payload within the buffer it was built in and surrounds it with `FEND`, shifting the
+/// bytes behind it to make room. Headers built in front of the payload and trailers behind it
+/// stay where they are, so a message is framed without a second buffer.
+///
+/// # Inputs
+/// * **&mut Vec<u8>**: The buffer holding the payload
+/// * **Range<usize>**: Where the payload sits in the buffer
+/// * **SpecialChars**: The special characters you want to swap
+///
+/// # Output
+///
+/// * **`Result<Range<usize>>`**: Where the encoded message sits in the buffer
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+/// * **HDLCError::BufferTooSmall**: The range isn't within the buffer.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let mut buf = vec![0xAA, 0x01, 0x7E, 0x02, 0xBB];
+///
+/// let frame = hdlc::encode_in_place(&mut buf, 1..4, chars).unwrap();
+///
+/// assert_eq!(frame, 1..7);
+/// assert_eq!(buf, vec![0xAA, 0x7E, 0x01, 0x7D, 0x5E, 0x02, 0x7E, 0xBB]);
+/// ```
+pub fn encode_in_place(
+    buf: &mut Vec<u8>,
+    payload: Range<usize>,
+    s_chars: SpecialChars,
+) -> Result<Range<usize>, HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+
+    let data = buf.get(payload.clone()).ok_or(HDLCError::BufferTooSmall)?;
+    let frame_len = encoded_len_of(data, &s_chars);
+    let growth = frame_len - data.len();
+
+    // Move everything behind the payload out of the way
+    let buf_len = buf.len();
+    buf.resize(buf_len + growth, 0);
+    buf.copy_within(payload.end..buf_len, payload.end + growth);
+
+    // Escape from the back, so the write index never overtakes the bytes still to be read
+    let mut write = payload.start + frame_len - 1;
+    buf[write] = s_chars.fend;
+    for read in payload.clone().rev() {
+        let value = buf[read];
+        match s_chars.translate.get(&value) {
+            Some(&c) => {
+                write -= 2;
+                buf[write] = s_chars.fesc;
+                buf[write + 1] = c;
+            }
+            None => {
+                write -= 1;
+                buf[write] = value;
+            }
+        }
+    }
+    buf[payload.start] = s_chars.fend;
+
+    Ok(payload.start..payload.start + frame_len)
+}
+
+/// Produces escaped (encoded) message surrounded with `FEND` in a caller provided buffer,
+/// without allocating.
+///
+/// # Inputs
+/// * **&[u8]**: The bytes you want to encode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **&mut [u8]**: The buffer the encoded message is written to
+///
+/// # Output
+///
+/// * **`Result<usize>`**: Length of the encoded message at the start of the buffer
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+/// * **HDLCError::BufferTooSmall**: The encoded message doesn't fit in the buffer.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let mut output = [0u8; 16];
+///
+/// let len = hdlc::encode_to_slice(&[0x01, 0x7E, 0x02], chars, &mut output).unwrap();
+///
+/// assert_eq!(output[..len], [0x7E, 0x01, 0x7D, 0x5E, 0x02, 0x7E]);
+/// ```
+pub fn encode_to_slice(
+    data: &[u8],
+    s_chars: SpecialChars,
+    out: &mut [u8],
+) -> Result<usize, HDLCError> {
+    encode_into(data, s_chars, &mut SliceOutput::new(out))
+}
+
+/// Produces escaped (encoded) message surrounded with `FEND` in a caller provided buffer that
+/// doesn't need to be initialized first.
+///
+/// # Inputs
+/// * **&[u8]**: The bytes you want to encode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **&mut [MaybeUninit<u8>]**: The buffer the encoded message is written to
+///
+/// # Output
+///
+/// * **`Result<&[u8]>`**: The encoded message at the start of the buffer
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+/// * **HDLCError::BufferTooSmall**: The encoded message doesn't fit in the buffer.
+///
+/// # Example
+/// ```rust
+/// use std::mem::MaybeUninit;
+///
+/// let chars = hdlc::SpecialChars::default();
+/// let mut output = [MaybeUninit::<u8>::uninit(); 16];
+///
+/// let frame = hdlc::encode_to_uninit(&[0x01, 0x7E, 0x02], chars, &mut output).unwrap();
+///
+/// assert_eq!(frame, [0x7E, 0x01, 0x7D, 0x5E, 0x02, 0x7E]);
+/// ```
+pub fn encode_to_uninit<'a>(
+    data: &[u8],
+    s_chars: SpecialChars,
+    out: &'a mut [MaybeUninit<u8>],
+) -> Result<&'a [u8], HDLCError> {
+    let mut output = UninitOutput::new(out);
+    encode_into(data, s_chars, &mut output)?;
+
+    Ok(output.into_init())
+}
+
+/// Produces escaped (encoded) message surrounded with `FEND`, as configured by
+/// [`EncodeOptions`].
+///
+/// # Inputs
+/// * **&[u8]**: The payload you want to encode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **EncodeOptions**: Where the flags go
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: Encoded output message
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+/// * **HDLCError::PayloadTooLarge**: The payload is longer than
+///   `EncodeOptions::max_payload_len`.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{encode_with, EncodeOptions, Framing, SpecialChars};
+///
+/// let options = EncodeOptions {
+///     framing: Framing::TerminatorOnly,
+///     ..EncodeOptions::default()
+/// };
+///
+/// assert_eq!(
+///     encode_with(&[0x01, 0x02], SpecialChars::default(), options),
+///     Ok(vec![0x01, 0x02, 0x7E])
+/// );
+/// ```
+pub fn encode_with(
+    data: &[u8],
+    s_chars: SpecialChars,
+    options: EncodeOptions,
+) -> Result<Vec<u8>, HDLCError> {
+    encode_with_ref(data, &s_chars, options)
+}
+
+/// Works like [`encode_with`], borrowing the special characters
+pub(crate) fn encode_with_ref(
+    data: &[u8],
+    s_chars: &SpecialChars,
+    options: EncodeOptions,
+) -> Result<Vec<u8>, HDLCError> {
+    encode_frames_ref(&[data], s_chars, options)
+}
+
+/// Encodes several payloads into one contiguous buffer, so a burst of frames goes out in a
+/// single write.
+///
+/// # Inputs
+/// * **&[impl AsRef<[u8]>]**: The payloads you want to encode, in transmit order
+/// * **SpecialChars**: The special characters you want to swap
+/// * **EncodeOptions**: Where the flags go, and whether adjacent frames share them
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: The encoded frames, back to back
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+/// * **HDLCError::PayloadTooLarge**: Any of the payloads is longer than
+///   `EncodeOptions::max_payload_len`. Nothing is encoded.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{encode_frames, EncodeOptions, SpecialChars};
+///
+/// let payloads: [&[u8]; 2] = [&[0x01, 0x7E], &[0x02]];
+/// let burst = encode_frames(&payloads, SpecialChars::default(), EncodeOptions::default());
+///
+/// assert_eq!(
+///     burst.unwrap(),
+///     vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x02, 0x7E]
+/// );
+/// ```
+pub fn encode_frames<P: AsRef<[u8]>>(
+    payloads: &[P],
+    s_chars: SpecialChars,
+    options: EncodeOptions,
+) -> Result<Vec<u8>, HDLCError> {
+    encode_frames_ref(payloads, &s_chars, options)
+}
+
+/// Works like [`encode_frames`], borrowing the special characters
+fn encode_frames_ref<P: AsRef<[u8]>>(
+    payloads: &[P],
+    s_chars: &SpecialChars,
+    options: EncodeOptions,
+) -> Result<Vec<u8>, HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+    let max_payload_len = options.max_payload_len.unwrap_or(usize::MAX);
+    if payloads.iter().any(|p| p.as_ref().len() > max_payload_len) {
+        return Err(HDLCError::PayloadTooLarge);
+    }
+
+    // Allocate once, for every frame
+    let len = payloads.iter().fold(0usize, |len, p| {
+        len.saturating_add(encoded_len_of(p.as_ref(), s_chars))
+    });
+    let mut output = Vec::with_capacity(len);
+    // Number of flags opening and closing every frame
+    let (opening, closing) = match options.framing {
+        Framing::Flags => (1, 1),
+        Framing::TerminatorOnly => (0, 1),
+        Framing::DoubledFlags => (2, 2),
+    };
+    let flags = [s_chars.fend; 2];
+    for payload in payloads {
+        if options.shared_flags {
+            // The opening flags of this frame take the place of the previous closing flags
+            output.truncate(output.len().saturating_sub(opening));
+        }
+        output.extend_from_slice(&flags[..opening]);
+        escape_unchecked(payload.as_ref(), s_chars, &mut output)?;
+        output.extend_from_slice(&flags[..closing]);
+    }
+
+    Ok(output)
+}
+
+/// Swaps the special characters of `data` for their escape sequences, without adding the
+/// opening and closing `FEND`.
+///
+/// For protocols framing their own header around the escaped region.
+///
+/// # Inputs
+/// * **&[u8]**: The bytes you want to escape
+/// * **SpecialChars**: The special characters you want to swap
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: The escaped bytes
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+///
+/// assert_eq!(hdlc::escape(&[0x01, 0x7E], chars), Ok(vec![0x01, 0x7D, 0x5E]));
+/// ```
+pub fn escape(data: &[u8], s_chars: SpecialChars) -> Result<Vec<u8>, HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+
+    let mut output = Vec::with_capacity(encoded_len_of(data, &s_chars) - 2);
+    escape_unchecked(data, &s_chars, &mut output)?;
+
+    Ok(output)
+}
+
+/// Swaps escape sequences in `data` back for the special characters, with no `FEND` expected
+/// around the bytes.
+///
+/// # Inputs
+/// * **&[u8]**: The escaped bytes
+/// * **SpecialChars**: The special characters you want to swap
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: The unescaped bytes
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+/// * **HDLCError::FendCharInData**: Found the `SpecialChars::fend` among the bytes.
+/// * **HDLCError::MissingTradeChar**: Checks to make sure every frame escape character `fesc`
+///   is followed by either a `tfend` or a `tfesc`.
+/// * **HDLCError::Incomplete**: The bytes end with a `fesc`.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+///
+/// assert_eq!(hdlc::unescape(&[0x01, 0x7D, 0x5E], chars), Ok(vec![0x01, 0x7E]));
+/// ```
+pub fn unescape(data: &[u8], s_chars: SpecialChars) -> Result<Vec<u8>, HDLCError> {
+    unescape_ref(data, &s_chars)
+}
+
+/// Works like [`unescape`], borrowing the special characters
+fn unescape_ref(data: &[u8], s_chars: &SpecialChars) -> Result<Vec<u8>, HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+
+    let mut output = Vec::with_capacity(data.len());
+    let mut rest = data;
+
+    // Copy the runs of bytes that need no swapping in one go
+    while let Some(index) = memchr2(s_chars.fend, s_chars.fesc, rest) {
+        output.extend_from_slice(&rest[..index]);
+        let offset = data.len() - rest.len() + index;
+        if rest[index] == s_chars.fend {
+            return Err(HDLCError::FendCharInData { offset });
+        }
+
+        let c = match rest.get(index + 1) {
+            Some(b) => s_chars.translate.decode(b).copied(),
+            // Only the trade char is missing, there is no closing FEND to wait for
+            None => return Err(HDLCError::Incomplete { needed: Some(1) }),
+        };
+        output.push(c.ok_or(HDLCError::MissingTradeChar { offset })?);
+        rest = &rest[index + 2..];
+    }
+    output.extend_from_slice(rest);
+
+    Ok(output)
+}
+
+/// Produces unescaped (decoded) message without `FEND` characters.
+///
+/// # Inputs
+/// * **`Vec<u8>`**: A vector of the bytes you want to decode
+/// * **SpecialChars**: The special characters you want to swap
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: Decoded output message
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+/// * **HDLCError::FendCharInData**: Checks to make sure the full decoded message is the full
+///   length.  Found the `SpecialChars::fend` inside the message.  Use [`decode_first`] for
+///   buffers holding more than the frame.
+/// * **HDLCError::MissingTradeChar**: Checks to make sure every frame escape character `fesc`
+///   is followed by either a `tfend` or a `tfesc`.
+/// * **HDLCError::MissingFirstFend**: Input vector is missing a first `SpecialChars::fend`
+/// * **HDLCError::Incomplete**: Input vector ends before the final `SpecialChars::fend`, more
+///   bytes may complete the frame
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let input: Vec<u8> = vec![ 0x7E, 0x01, 0x50, 0x00, 0x00, 0x00, 0x05, 0x80, 0x09, 0x7E];
+/// let op_vec = hdlc::decode(&input.to_vec(), chars);
+/// ```
+pub fn decode(input: &[u8], s_chars: SpecialChars) -> Result<Vec<u8>, HDLCError> {
+    decode_ref(input, &s_chars)
+}
+
+/// Works like [`decode`], borrowing the special characters so callers decoding many frames
+/// don't copy them for every frame
+pub(crate) fn decode_ref(input: &[u8], s_chars: &SpecialChars) -> Result<Vec<u8>, HDLCError> {
+    // Predefine the vector for speed
+    let mut output: Vec<u8> = Vec::with_capacity(decoded_len_hint(input.len()));
+    decode_into_ref(input, s_chars, &mut output)?;
+
+    Ok(output)
+}
+
+/// Produces unescaped (decoded) message without `FEND` characters, as configured by
+/// [`DecodeOptions`].
+///
+/// # Inputs
+/// * **&[u8]**: The frame you want to decode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **DecodeOptions**: How strictly the frame is checked
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: Decoded output message, without its frame check sequence. A frame
+///   made of its two flags alone is returned empty, without checking an FCS.
+///
+/// # Error
+///
+/// * **HDLCError::FrameTooLong**: The frame is longer than `DecodeOptions::max_len`.
+/// * **HDLCError::FrameLengthMismatch**: The frame format field doesn't match the frame, with
+///   `DecodeOptions::frame_format` set.
+/// * **HDLCError::HcsMismatch**: The header check sequence doesn't match the header, with
+///   `DecodeOptions::hcs` set.
+/// * **HDLCError::FcsMismatch**: The frame check sequence doesn't match the payload.
+/// * Any error [`decode`] returns.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{decode_with, DecodeOptions, SpecialChars};
+///
+/// let input = [0x55, 0x7E, 0x01, 0x7D, 0x5E, 0x7E];
+///
+/// assert!(decode_with(&input, SpecialChars::default(), DecodeOptions::strict()).is_err());
+/// assert_eq!(
+///     decode_with(&input, SpecialChars::default(), DecodeOptions::tolerant()),
+///     Ok(vec![0x01, 0x7E])
+/// );
+/// ```
+pub fn decode_with(
+    input: &[u8],
+    s_chars: SpecialChars,
+    options: DecodeOptions,
+) -> Result<Vec<u8>, HDLCError> {
+    decode_with_ref(input, &s_chars, options)
+}
+
+/// Works like [`decode_with`], borrowing the special characters so callers decoding many frames
+/// don't copy them for every frame
+pub(crate) fn decode_with_ref(
+    input: &[u8],
+    s_chars: &SpecialChars,
+    options: DecodeOptions,
+) -> Result<Vec<u8>, HDLCError> {
+    let mut output = match options.framing {
+        Framing::Flags => decode_flagged(input, s_chars, options)?,
+        Framing::DoubledFlags => {
+            let options = DecodeOptions {
+                fend_fill: true,
+                ..options
+            };
+            decode_flagged(input, s_chars, options)?
+        }
+        Framing::TerminatorOnly => decode_terminated(input, s_chars, options)?,
+    };
+    if output.is_empty() {
+        // A frame without payload carries no FCS either
+        return Ok(output);
+    }
+    if options.frame_format {
+        FrameFormat::check(&output)?;
+    }
+    if options.hcs {
+        FrameFormat::check_hcs(&output, options.fcs)?;
+    }
+    let len = options.fcs.check(&output)?.len();
+    output.truncate(len);
+
+    Ok(output)
+}
+
+/// Locates the frame opened and closed by a `fend` in `input` as configured by `options`, and
+/// decodes it
+fn decode_flagged(
+    input: &[u8],
+    s_chars: &SpecialChars,
+    options: DecodeOptions,
+) -> Result<Vec<u8>, HDLCError> {
+    let mut frame = input;
+    if !options.strict {
+        // Skip everything before the opening FEND
+        let start = memchr(s_chars.fend, frame).ok_or(HDLCError::MissingFirstFend)?;
+        frame = &frame[start..];
+    }
+    if options.fend_fill {
+        // A run of FENDs is idle fill, the last one opens the frame
+        let fill = frame.iter().take_while(|&&b| b == s_chars.fend).count();
+        frame = &frame[fill.saturating_sub(1)..];
+    }
+    let start = input.len() - frame.len();
+    if let Some(len) = frame.get(1..).and_then(|rest| memchr(s_chars.fend, rest)) {
+        // Cut the frame after its closing FEND if what follows is tolerated
+        let after = &frame[len + 2..];
+        if options.trailing_garbage
+            || (options.fend_fill && after.iter().all(|&b| b == s_chars.fend))
+        {
+            frame = &frame[..len + 2];
+        }
+    }
+    if frame.len() > options.max_len.unwrap_or(usize::MAX) {
+        return Err(HDLCError::FrameTooLong);
+    }
+
+    decode_ref(frame, s_chars).map_err(|error| error.shifted(start))
+}
+
+/// Locates the frame closed by a `fend`, but not opened by one, in `input` as configured by
+/// `options`, and decodes it
+fn decode_terminated(
+    input: &[u8],
+    s_chars: &SpecialChars,
+    options: DecodeOptions,
+) -> Result<Vec<u8>, HDLCError> {
+    let mut frame = input;
+    if options.fend_fill {
+        // Flags in front of the frame are idle fill, unless the last one closes an empty frame
+        let fill = frame.iter().take_while(|&&b| b == s_chars.fend).count();
+        frame = &frame[fill.min(frame.len().saturating_sub(1))..];
+    }
+    let start = input.len() - frame.len();
+    let len = memchr(s_chars.fend, frame).ok_or(HDLCError::Incomplete { needed: Some(1) })? + 1;
+    let after = &frame[len..];
+    let tolerated = after.is_empty()
+        || options.trailing_garbage
+        || (options.fend_fill && after.iter().all(|&b| b == s_chars.fend));
+    if !tolerated {
+        return Err(HDLCError::FendCharInData {
+            offset: start + len - 1,
+        });
+    }
+    if len > options.max_len.unwrap_or(usize::MAX) {
+        return Err(HDLCError::FrameTooLong);
+    }
+
+    unescape_ref(&frame[..len - 1], s_chars)
+        .map_err(|error| match error {
+            // The terminating FEND follows a FESC
+            HDLCError::Incomplete { .. } => HDLCError::MissingTradeChar { offset: len - 2 },
+            error => error,
+        })
+        .map_err(|error| error.shifted(start))
+}
+
+/// Decodes the frame at the start of `input`, which may be followed by more data, and reports
+/// how many bytes it spanned.
+///
+/// # Inputs
+/// * **&[u8]**: A buffer starting with the frame you want to decode
+/// * **SpecialChars**: The special characters you want to swap
+///
+/// # Output
+///
+/// * **`Result<(Vec<u8>, usize)>`**: Decoded output message, and the number of input bytes
+///   up to and including its closing `fend`
+///
+/// # Error
+///
+/// * Any error [`decode`] returns for the frame. Bytes after its closing `fend` are never
+///   looked at.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let input = [0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x02];
+///
+/// let (frame, consumed) = hdlc::decode_prefix(&input, chars).unwrap();
+///
+/// assert_eq!(frame, vec![0x01, 0x7E]);
+/// assert_eq!(&input[consumed..], &[0x7E, 0x02]);
+/// ```
+pub fn decode_prefix(input: &[u8], s_chars: SpecialChars) -> Result<(Vec<u8>, usize), HDLCError> {
+    // The frame ends at the first FEND after the opening one
+    let len = match input.get(1..).and_then(|rest| memchr(s_chars.fend, rest)) {
+        Some(index) if input[0] == s_chars.fend => index + 2,
+        _ => input.len(),
+    };
+    let output = decode(&input[..len], s_chars)?;
+
+    Ok((output, len))
+}
+
+/// Decodes the first complete frame of a read buffer, returning the unparsed tail alongside it
+/// instead of failing on the bytes after the closing `fend`.
+///
+/// # Inputs
+/// * **&[u8]**: A buffer starting with the frame you want to decode
+/// * **SpecialChars**: The special characters you want to swap
+///
+/// # Output
+///
+/// * **`Result<(Vec<u8>, &[u8])>`**: Decoded output message, and the bytes following its
+///   closing `fend`
+///
+/// # Error
+///
+/// * Any error [`decode_prefix`] returns.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let input = [0x7E, 0x01, 0x7E, 0x7E, 0x02, 0x7E];
+///
+/// let (first, rest) = hdlc::decode_first(&input, chars).unwrap();
+///
+/// assert_eq!(first, vec![0x01]);
+/// assert_eq!(rest, &[0x7E, 0x02, 0x7E]);
+/// ```
+pub fn decode_first(input: &[u8], s_chars: SpecialChars) -> Result<(Vec<u8>, &[u8]), HDLCError> {
+    let (output, len) = decode_prefix(input, s_chars)?;
+
+    Ok((output, &input[len..]))
+}
+
+/// Works like [`decode`], but reports a failed allocation instead of aborting.
+///
+/// The output is allocated once, at [`decoded_len_hint`] of the input, before anything is
+/// decoded.
+///
+/// # Error
+///
+/// * **HDLCError::OutOfMemory**: The output couldn't be allocated.
+/// * Any error [`decode`] returns.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+///
+/// let result = hdlc::try_decode(&[0x7E, 0x01, 0x7D, 0x5E, 0x7E], chars);
+///
+/// assert_eq!(result.unwrap(), vec![0x01, 0x7E]);
+/// ```
+pub fn try_decode(input: &[u8], s_chars: SpecialChars) -> Result<Vec<u8>, HDLCError> {
+    let mut output = Vec::new();
+    output
+        .try_reserve_exact(decoded_len_hint(input.len()))
+        .map_err(|_| HDLCError::OutOfMemory)?;
+    decode_into(input, s_chars, &mut output)?;
+
+    Ok(output)
+}
+
+/// Pushes the unescaped (decoded) message without `FEND` characters to any [`Output`].
+///
+/// # Inputs
+/// * **&[u8]**: The frame you want to decode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **impl Output**: The destination the decoded message is pushed to
+///
+/// # Output
+///
+/// * **`Result<usize>`**: Number of bytes pushed
+///
+/// # Error
+///
+/// * **HDLCError::BufferTooSmall**: The output is full.
+/// * Any error [`decode`] returns for a malformed frame.
+///
+/// Part of the message may already have been pushed when an error is returned.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let mut output: Vec<u8> = Vec::new();
+///
+/// let pushed = hdlc::decode_into(&[0x7E, 0x01, 0x7D, 0x5E, 0x7E], chars, &mut output);
+///
+/// assert_eq!(pushed, Ok(2));
+/// assert_eq!(output, vec![0x01, 0x7E]);
+/// ```
+pub fn decode_into(
+    input: &[u8],
+    s_chars: SpecialChars,
+    out: &mut impl Output,
+) -> Result<usize, HDLCError> {
+    decode_into_ref(input, &s_chars, out)
+}
+
+/// Works like [`decode_into`], borrowing the special characters
+fn decode_into_ref(
+    input: &[u8],
+    s_chars: &SpecialChars,
+    out: &mut impl Output,
+) -> Result<usize, HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+
+    decode_unchecked(input, s_chars, out)
+}
+
+/// Pushes the decoded message of `input` to `out`, with special characters already known to be
+/// unique
+fn decode_unchecked(
+    input: &[u8],
+    s_chars: &SpecialChars,
+    out: &mut impl Output,
+) -> Result<usize, HDLCError> {
+    let mut input_iter = input.iter();
+
+    // Verify input begins with a FEND
+    if input_iter.next() != Some(&s_chars.fend) {
+        return Err(HDLCError::MissingFirstFend);
+    }
+
+    let mut pushed = 0;
+    let mut rest = input_iter.as_slice();
+
+    // Push the runs of bytes that need no swapping in one go
+    while let Some(index) = memchr2(s_chars.fend, s_chars.fesc, rest) {
+        out.extend_from_slice(&rest[..index])?;
+        pushed += index;
+
+        let offset = input.len() - rest.len() + index;
+
+        // Handle a FEND
+        if rest[index] == s_chars.fend {
+            if index + 1 < rest.len() {
+                return Err(HDLCError::FendCharInData { offset });
+            }
+            return Ok(pushed);
+        }
+
+        // Handle a FESC
+        out.push(unescape_byte(s_chars, rest.get(index + 1), offset)?)?;
+        pushed += 1;
+        rest = &rest[index + 2..];
+    }
+
+    Err(HDLCError::Incomplete { needed: Some(1) })
+}
+
+/// Decodes the byte following a `fesc` found at `offset`, `next` is None when the input ends
+/// right after the `fesc`
+fn unescape_byte(
+    s_chars: &SpecialChars,
+    next: Option<&u8>,
+    offset: usize,
+) -> Result<u8, HDLCError> {
+    match next {
+        // The trade char and the closing FEND are still to come
+        None => Err(HDLCError::Incomplete { needed: Some(2) }),
+        Some(b) => s_chars
+            .translate
+            .decode(b)
+            .copied()
+            .ok_or(HDLCError::MissingTradeChar { offset }),
+    }
+}
+
+/// Writes the unescaped (decoded) message without `FEND` characters straight into a writer,
+/// without building the decoded message in memory first.
+///
+/// # Inputs
+/// * **&[u8]**: The frame you want to decode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **impl Write**: The sink the decoded message is written to
+///
+/// # Output
+///
+/// * **`io::Result<usize>`**: Number of bytes written
+///
+/// # Error
+///
+/// * **io::ErrorKind::InvalidInput**: Wraps `HDLCError::DuplicateSpecialChar` if any of the
+///   `SpecialChars` are duplicate.
+/// * **io::ErrorKind::InvalidData**: Wraps the `HDLCError` describing why the frame is
+///   malformed, see [`decode`]. The part of the message in front of the error may already have
+///   been written.
+/// * Any error returned by the writer.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let mut output: Vec<u8> = Vec::new();
+///
+/// let written = hdlc::decode_to_writer(&[0x7E, 0x01, 0x7D, 0x5E, 0x02, 0x7E], chars, &mut output);
+///
+/// assert_eq!(written.unwrap(), 3);
+/// assert_eq!(output, vec![0x01, 0x7E, 0x02]);
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_to_writer(
+    input: &[u8],
+    s_chars: SpecialChars,
+    w: &mut impl Write,
+) -> io::Result<usize> {
+    let invalid = |e: HDLCError| io::Error::new(io::ErrorKind::InvalidData, e);
+
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            HDLCError::DuplicateSpecialChar,
+        ));
+    }
+
+    // Verify input begins with a FEND
+    if input.first() != Some(&s_chars.fend) {
+        return Err(invalid(HDLCError::MissingFirstFend));
+    }
+
+    let mut written = 0;
+
+    // Write the runs of bytes that need no swapping in one go
+    let mut start = 1;
+    let mut index = 1;
+    while let Some(offset) = memchr2(s_chars.fend, s_chars.fesc, &input[index..]) {
+        index += offset;
+        if input[index] == s_chars.fesc {
+            w.write_all(&input[start..index])?;
+            let c = unescape_byte(&s_chars, input.get(index + 1), index).map_err(invalid)?;
+            w.write_all(&[c])?;
+            written += index - start + 1;
+            index += 2;
+            start = index;
+        } else {
+            if index + 1 < input.len() {
+                return Err(invalid(HDLCError::FendCharInData { offset: index }));
+            }
+            w.write_all(&input[start..index])?;
+            return Ok(written + index - start);
+        }
+    }
+
+    Err(invalid(HDLCError::Incomplete { needed: Some(1) }))
+}
+
+/// Produces unescaped (decoded) message without `FEND` characters in a caller provided `Vec`,
+/// which is cleared first, so its allocation is reused from frame to frame.
+///
+/// Use [`decode_into`] to append to the `Vec` instead.
+///
+/// # Inputs
+/// * **&[u8]**: The frame you want to decode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **&mut Vec<u8>**: The vector the decoded message is written to
+///
+/// # Output
+///
+/// * **`Result<usize>`**: Length of the decoded message
+///
+/// # Error
+///
+/// * Any error [`decode`] returns for a malformed frame. The `Vec` may hold part of the message.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let mut output: Vec<u8> = vec![0xFF; 8];
+///
+/// let len = hdlc::decode_to_vec(&[0x7E, 0x01, 0x7D, 0x5E, 0x7E], chars, &mut output);
+///
+/// assert_eq!(len, Ok(2));
+/// assert_eq!(output, vec![0x01, 0x7E]);
+/// ```
+pub fn decode_to_vec(
+    input: &[u8],
+    s_chars: SpecialChars,
+    out: &mut Vec<u8>,
+) -> Result<usize, HDLCError> {
+    out.clear();
+    decode_into(input, s_chars, out)
+}
+
+/// Produces unescaped (decoded) message without `FEND` characters in a caller provided buffer,
+/// without allocating.
+///
+/// # Inputs
+/// * **&[u8]**: The frame you want to decode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **&mut [u8]**: The buffer the decoded message is written to
+///
+/// # Output
+///
+/// * **`Result<usize>`**: Length of the decoded message at the start of the buffer
+///
+/// # Error
+///
+/// * **HDLCError::BufferTooSmall**: The decoded message doesn't fit in the buffer.
+/// * Any error [`decode`] returns for a malformed frame.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let mut output = [0u8; 16];
+///
+/// let len = hdlc::decode_to_slice(&[0x7E, 0x01, 0x7D, 0x5E, 0x02, 0x7E], chars, &mut output);
+///
+/// assert_eq!(output[..len.unwrap()], [0x01, 0x7E, 0x02]);
+/// ```
+pub fn decode_to_slice(
+    input: &[u8],
+    s_chars: SpecialChars,
+    out: &mut [u8],
+) -> Result<usize, HDLCError> {
+    decode_into(input, s_chars, &mut SliceOutput::new(out))
+}
+
+/// Produces slice (`&[u8]`) unescaped (decoded) message without `FEND` characters, unescaping
+/// the input in place without allocating.
+///
+/// # Inputs
+/// * **&mut [u8]**: A mutable slice of the bytes you want to decode
+/// * **SpecialChars**: The special characters you want to swap
+///
+/// # Output
+///
+/// * **Result<&[u8]>**: Decoded output message
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+/// * **HDLCError::FendCharInData**: Checks to make sure the full decoded message is the full
+///   length.  Found the `SpecialChars::fend` inside the message.
+/// * **HDLCError::MissingTradeChar**: Checks to make sure every frame escape character `fesc`
+///   is followed by either a `tfend` or a `tfesc`.
+/// * **HDLCError::Incomplete**: Input vector ends before the final `SpecialChars::fend`, more
+///   bytes may complete the frame
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let mut input = [ 0x7E, 0x01, 0x50, 0x00, 0x00, 0x00, 0x05, 0x80, 0x09, 0x7E];
+/// let op_vec = hdlc::decode_slice(&mut input, chars);
+/// ```
+pub fn decode_slice(input: &mut [u8], s_chars: SpecialChars) -> Result<&[u8], HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+
+    // Bytes in front of the first FEND are ignored
+    let mut read = match memchr(s_chars.fend, input) {
+        Some(index) => index + 1,
+        None => return Err(HDLCError::Incomplete { needed: None }),
+    };
+    // The decoded message is never longer than what was read, so it is written over the input
+    let mut write = 0;
+
+    // Move the runs of bytes that need no swapping in one go
+    while let Some(offset) = memchr2(s_chars.fend, s_chars.fesc, &input[read..]) {
+        input.copy_within(read..read + offset, write);
+        write += offset;
+        read += offset;
+
+        // Handle a FEND
+        if input[read] == s_chars.fend {
+            if read + 1 < input.len() {
+                return Err(HDLCError::FendCharInData { offset: read });
+            }
+            return Ok(&input[..write]);
+        }
+
+        // Handle a FESC
+        input[write] = unescape_byte(&s_chars, input.get(read + 1), read)?;
+        write += 1;
+        read += 2;
+    }
+
+    Err(HDLCError::Incomplete { needed: Some(1) })
+}
+
+/// Re-escapes a frame from one set of special characters to another in a single pass.
+///
+/// # Inputs
+/// * **&[u8]**: The frame encoded with `from_chars`
+/// * **SpecialChars**: The special characters the frame is encoded with
+/// * **SpecialChars**: The special characters the output frame is encoded with
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: The frame encoded with `to_chars`
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Either set of special characters has duplicates.
+/// * Any error [`decode`] returns for a malformed frame.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{transcode, SpecialChars};
+///
+/// let custom = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+/// let input = [0x71, 0x01, 0x7E, 0x70, 0x51, 0x71];
+///
+/// let result = transcode(&input, custom, SpecialChars::default());
+///
+/// assert_eq!(result.unwrap(), vec![0x7E, 0x01, 0x7D, 0x5E, 0x71, 0x7E]);
+/// ```
+pub fn transcode(
+    input: &[u8],
+    from_chars: SpecialChars,
+    to_chars: SpecialChars,
+) -> Result<Vec<u8>, HDLCError> {
+    let mut output = Vec::with_capacity(input.len());
+    transcode_into(input, &from_chars, &to_chars, &mut output)?;
+
+    Ok(output)
+}
+
+/// Works like [`transcode`], but reports a failed allocation instead of aborting.
+///
+/// The output is allocated once, at the longest frame the input can turn into, before anything
+/// is transcoded.
+///
+/// # Error
+///
+/// * **HDLCError::OutOfMemory**: The output couldn't be allocated.
+/// * Any error [`transcode`] returns.
+pub fn try_transcode(
+    input: &[u8],
+    from_chars: SpecialChars,
+    to_chars: SpecialChars,
+) -> Result<Vec<u8>, HDLCError> {
+    let mut output = Vec::new();
+    output
+        .try_reserve_exact(max_encoded_len(decoded_len_hint(input.len())))
+        .map_err(|_| HDLCError::OutOfMemory)?;
+    transcode_into(input, &from_chars, &to_chars, &mut output)?;
+
+    Ok(output)
+}
+
+/// Re-escapes a frame from one set of special characters to another, pushing it to `output`
+fn transcode_into(
+    input: &[u8],
+    from_chars: &SpecialChars,
+    to_chars: &SpecialChars,
+    output: &mut impl Output,
+) -> Result<(), HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if from_chars.has_duplicates() || to_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+
+    let mut input_iter = input.iter();
+
+    // Verify input begins with a FEND
+    if input_iter.next() != Some(&from_chars.fend) {
+        return Err(HDLCError::MissingFirstFend);
+    }
+    output.push(to_chars.fend)?;
+
+    // Loop over every byte of the message, unescaping and escaping it again
+    while let Some(&value) = input_iter.next() {
+        let offset = input.len() - input_iter.as_slice().len() - 1;
+        let value = match value {
+            // Handle a FESC
+            val if val == from_chars.fesc => unescape_byte(from_chars, input_iter.next(), offset)?,
+            // Handle a FEND
+            val if val == from_chars.fend => {
+                if input_iter.next().is_some() {
+                    return Err(HDLCError::FendCharInData { offset });
+                }
+                return output.push(to_chars.fend);
+            }
+            // Handle any other bytes
+            val => val,
+        };
+
+        match to_chars.translate.get(&value) {
+            Some(&c) => output.extend_from_slice(&[to_chars.fesc, c])?,
+            None => output.push(value)?,
+        }
+    }
+
+    Err(HDLCError::Incomplete { needed: Some(1) })
+}
+
+/// Reads frames until the end of `reader`, re-escapes them from one set of special characters to
+/// another and writes them to `writer`. Frames that fail to transcode are dropped and counted.
+///
+/// # Output
+///
+/// * **`io::Result<(usize, usize)>`**: Number of frames written and number of frames dropped
+///
+/// # Error
+///
+/// * **io::ErrorKind::InvalidInput**: Wraps `HDLCError::DuplicateSpecialChar` if either set of
+///   special characters has duplicates.
+/// * Any error returned by the reader or the writer.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{transcode_stream, SpecialChars};
+/// use std::io::Cursor;
+///
+/// let custom = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+/// let mut input = Cursor::new(vec![0x71, 0x01, 0x71, 0x71, 0x7E, 0x71, 0x71, 0x70, 0x71]);
+/// let mut output = Vec::new();
+///
+/// let result = transcode_stream(&mut input, &mut output, custom, SpecialChars::default());
+///
+/// assert_eq!(result.unwrap(), (2, 1));
+/// assert_eq!(output, vec![0x7E, 0x01, 0x7E, 0x7E, 0x7D, 0x5E, 0x7E]);
+/// ```
+#[cfg(feature = "std")]
+pub fn transcode_stream(
+    reader: &mut dyn Read,
+    writer: &mut impl Write,
+    from_chars: SpecialChars,
+    to_chars: SpecialChars,
+) -> io::Result<(usize, usize)> {
+    // Safety check to make sure the special character values are all unique
+    if from_chars.has_duplicates() || to_chars.has_duplicates() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            HDLCError::DuplicateSpecialChar,
+        ));
+    }
+
+    let mut written = 0;
+    let mut dropped = 0;
+    let mut output = Vec::new();
+    let mut frames = FrameReader::new(reader, from_chars.clone());
+    while let Some(frame) = frames.try_read_frame().map_err(|e| match e {
+        HDLCError::Io(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidData, e),
+    })? {
+        output.clear();
+        match transcode_into(&frame, &from_chars, &to_chars, &mut output) {
+            Ok(()) => {
+                writer.write_all(&output)?;
+                written += 1;
+            }
+            Err(_) => dropped += 1,
+        }
+    }
+
+    Ok((written, dropped))
+}
+
+/// Re-encodes a frame into its canonical form, with single flags and only `fend` and `fesc`
+/// escaped, so frames captured from devices that over-escape can be compared.
+///
+/// # Inputs
+/// * **&[u8]**: The frame you want to normalize
+/// * **SpecialChars**: The special characters the frame was encoded with, including every byte
+///   the device escapes
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: The canonical frame
+///
+/// # Error
+///
+/// * Any error [`decode`] returns. Runs of `fend` characters around the frame are accepted.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{normalize, EscapeMap, SpecialChars};
+///
+/// let chars = SpecialChars::default().with_escape_map(EscapeMap::new().with(0x11)).unwrap();
+/// let frame = [0x7E, 0x7E, 0x7D, 0x31, 0x7D, 0x5E, 0x7E, 0x7E];
+///
+/// assert_eq!(normalize(&frame, chars), Ok(vec![0x7E, 0x11, 0x7D, 0x5E, 0x7E]));
+/// ```
+pub fn normalize(frame: &[u8], s_chars: SpecialChars) -> Result<Vec<u8>, HDLCError> {
+    let options = DecodeOptions {
+        fend_fill: true,
+        ..DecodeOptions::strict()
+    };
+    let payload = decode_with_ref(frame, &s_chars, options)?;
+
+    // Only the flag and the escape character itself need escaping
+    let translate = s_chars
+        .translate
+        .iter()
+        .filter(|&(byte, _)| byte == s_chars.fend || byte == s_chars.fesc)
+        .collect();
+    encode(
+        &payload,
+        SpecialChars {
+            translate,
+            ..s_chars
+        },
+    )
+}
+
+/// Finds the frame boundaries in a byte slice without copying.
+///
+/// Bytes before the first `fend` are skipped, and back-to-back `fend` characters between
+/// frames are treated as the closing flag of one frame and the opening flag of the next.
+///
+/// # Inputs
+/// * **&[u8]**: The raw bytes holding zero or more encoded frames
+/// * **SpecialChars**: The special characters used to frame the data
+///
+/// # Output
+///
+/// * **Frames**: Iterator over the raw frame sub-slices, `fend` characters included. Once
+///   exhausted, `Frames::remainder` holds the trailing partial frame.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let buf = [0x7E, 0x01, 0x02, 0x7E, 0x7E, 0x03, 0x7E, 0x7E, 0x04];
+///
+/// let mut frames = hdlc::frames(&buf, chars);
+/// assert_eq!(frames.next(), Some(&[0x7E, 0x01, 0x02, 0x7E][..]));
+/// assert_eq!(frames.next(), Some(&[0x7E, 0x03, 0x7E][..]));
+/// assert_eq!(frames.next(), None);
+/// assert_eq!(frames.remainder(), &[0x7E, 0x04]);
+/// ```
+pub fn frames(buf: &[u8], s_chars: SpecialChars) -> Frames<'_> {
+    Frames::new(buf, s_chars.fend)
+}
+
+/// Iterator over the raw frames of a byte slice, created by [`frames`].
+#[derive(Debug, Clone)]
+pub struct Frames<'a> {
+    /// The bytes being split into frames
+    buf: &'a [u8],
+
+    /// Frame END character marking the frame boundaries
+    fend: u8,
+
+    /// Start of the bytes not yet consumed
+    pos: usize,
+
+    /// Whether back-to-back `fend` characters are an empty frame instead of two flags
+    empty: bool,
+
+    /// Whether the closing `fend` of a frame may also open the next frame
+    share: bool,
+
+    /// Set right after a frame whose closing `fend` may open the next frame
+    shared: bool,
+
+    /// Whether frames are only closed by a `fend`, without an opening one
+    terminated: bool,
+}
+
+impl<'a> Frames<'a> {
+    /// Splits `buf` at every `fend`, with empty frames skipped
+    pub(crate) fn new(buf: &'a [u8], fend: u8) -> Self {
+        Frames {
+            buf,
+            fend,
+            pos: 0,
+            empty: false,
+            share: false,
+            shared: false,
+            terminated: false,
+        }
+    }
+
+    /// Returns the bytes not consumed by the iterator so far.
+    ///
+    /// After the iterator returns `None` this is the trailing partial frame starting at its
+    /// opening `fend`, or an empty slice if no frame was started.
+    pub fn remainder(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.terminated {
+            // Every frame runs up to and including the next FEND
+            let rest = &self.buf[self.pos..];
+            let end = memchr(self.fend, rest)? + 1;
+            self.pos += end;
+            return Some(&rest[..end]);
+        }
+
+        // The closing FEND of the previous frame also opens the next one if no other FEND follows
+        let from = match self.buf.get(self.pos) {
+            Some(&b) if self.shared && b != self.fend => self.pos - 1,
+            _ => self.pos,
+        };
+        self.shared = false;
+        let rest = &self.buf[from..];
+
+        // Skip everything before the opening FEND
+        let mut start = match memchr(self.fend, rest) {
+            Some(start) => start,
+            None => {
+                self.pos = self.buf.len();
+                return None;
+            }
+        };
+
+        // A run of FENDs is a closing flag followed by an opening flag, use the last one
+        while !self.empty && rest.get(start + 1) == Some(&self.fend) {
+            start += 1;
+        }
+
+        match memchr(self.fend, &rest[start + 1..]) {
+            Some(len) => {
+                let end = start + len + 2;
+                self.pos = from + end;
+                self.shared = self.share && !self.empty;
+                Some(&rest[start..end])
+            }
+            None => {
+                // Keep the partial frame for the caller
+                self.pos = from + start;
+                None
+            }
+        }
+    }
+}
+
+/// Decodes every complete frame in a buffer and reports how many bytes were consumed.
+///
+/// The trailing partial frame, if any, is not consumed so the caller can retain it and
+/// prepend it to the next chunk of received data.
+///
+/// # Inputs
+/// * **&[u8]**: The raw bytes holding zero or more encoded frames
+/// * **SpecialChars**: The special characters you want to swap
+///
+/// # Output
+///
+/// * **`Result<(Vec<Vec<u8>>, usize)>`**: Decoded payloads and the number of bytes consumed
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+/// * **HDLCError::MissingTradeChar**: Checks to make sure every frame escape character `fesc`
+///   is followed by either a `tfend` or a `tfesc`.
+///
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let input = [0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x02, 0x7E, 0x7E, 0x03];
+///
+/// let (payloads, consumed) = hdlc::decode_frames(&input, chars).unwrap();
+/// assert_eq!(payloads, vec![vec![0x01, 0x7E], vec![0x02]]);
+/// assert_eq!(&input[consumed..], &[0x7E, 0x03]);
+/// ```
+pub fn decode_frames(
+    input: &[u8],
+    s_chars: SpecialChars,
+) -> Result<(Vec<Vec<u8>>, usize), HDLCError> {
+    decode_frames_with(input, s_chars, DecodeOptions::default())
+}
+
+/// Works like [`decode_frames`], decoding every frame as configured by [`DecodeOptions`].
+///
+/// With `DecodeOptions::empty_frames` set, back-to-back `fend` characters are an empty frame
+/// that is handed out, otherwise they are the closing flag of one frame and the opening flag of
+/// the next and payloads left empty are skipped.
 ///
 /// # Error
 ///
-/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
-///     the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
-///
-/// # Todo
-///
-/// Catch more errors, like an incomplete packet
+/// * Any error [`decode_frames`] or [`decode_with`] returns.
 ///
 /// # Example
 /// ```rust
-/// let chars = hdlc::SpecialChars::default();
-/// let input: Vec<u8> = vec![0x01, 0x50, 0x00, 0x00, 0x00, 0x05, 0x80, 0x09];
-/// let op_vec = hdlc::encode(&input.to_vec(), chars);
+/// use hdlc::{decode_frames_with, DecodeOptions, SpecialChars};
+///
+/// let input = [0x7E, 0x7E, 0x7E, 0x01, 0x7E, 0x7E, 0x7E];
+/// let options = DecodeOptions {
+///     empty_frames: true,
+///     ..DecodeOptions::default()
+/// };
+///
+/// let (payloads, consumed) = decode_frames_with(&input, SpecialChars::default(), options).unwrap();
+/// assert_eq!(payloads, vec![vec![], vec![0x01], vec![]]);
+/// assert_eq!(consumed, input.len());
 /// ```
-pub fn encode(data: &[u8], s_chars: SpecialChars) -> Result<Vec<u8>, HDLCError> {
+pub fn decode_frames_with(
+    input: &[u8],
+    s_chars: SpecialChars,
+    options: DecodeOptions,
+) -> Result<(Vec<Vec<u8>>, usize), HDLCError> {
     // Safety check to make sure the special character values are all unique
-    if s_chars.fend == s_chars.fesc {
-        // FIXME: quick hack with the newer specialchars handler
+    if s_chars.has_duplicates() {
         return Err(HDLCError::DuplicateSpecialChar);
     }
 
-    // Prealocate for speed.  *2 is the max size it can be if EVERY char is swapped
-    let mut output = Vec::with_capacity(data.len() * 2);
-    // Iterator over the input that allows peeking
-    let input_iter = data.iter();
-
-    //Push initial FEND
-    output.push(s_chars.fend);
-
-    // Loop over every byte of the message
-    for value in input_iter {
-        match *value {
-            // FEND and FESC
-            val if s_chars.translate.contains_key(&val) => {
-                output.push(s_chars.fesc);
-                let t = s_chars.translate.get(&val);
-                if let Some(&c) = t {
-                    output.push(c);
-                } else {
-                    return Err(HDLCError::MissingTradeChar);
-                }
-            }
-            // Handle any other bytes
-            _ => output.push(*value),
+    let mut payloads = Vec::new();
+    let mut iter = Frames::new(input, s_chars.fend);
+    iter.empty = options.empty_frames;
+    iter.share = options.shared_flags;
+    iter.terminated = options.framing == Framing::TerminatorOnly;
+    while let Some(frame) = iter.next() {
+        let start = input.len() - iter.remainder().len() - frame.len();
+        let payload =
+            decode_with_ref(frame, &s_chars, options).map_err(|error| error.shifted(start))?;
+        if options.empty_frames || !payload.is_empty() {
+            payloads.push(payload);
         }
     }
 
-    // Push final FEND
-    output.push(s_chars.fend);
-
-    Ok(output)
+    Ok((payloads, input.len() - iter.remainder().len()))
 }
 
-/// Produces unescaped (decoded) message without `FEND` characters.
+/// Splits a buffer into all of its complete raw frames and the untouched remainder.
+///
+/// A one-shot version of [`frames`] for applications owning the receive buffer: bytes before
+/// the first `fend` are skipped, and the remainder holds the trailing partial frame starting at
+/// its opening `fend`, to be kept for the next chunk of received data.
 ///
 /// # Inputs
-/// * **`Vec<u8>`**: A vector of the bytes you want to decode
-/// * **SpecialChars**: The special characters you want to swap
+/// * **&[u8]**: The raw bytes holding zero or more encoded frames
+/// * **SpecialChars**: The special characters used to frame the data
 ///
 /// # Output
 ///
-/// * **`Result<Vec<u8>>`**: Decoded output message
+/// * **`(Vec<&[u8]>, &[u8])`**: The raw frames, `fend` characters included, and the remainder
 ///
-/// # Error
+/// # Example
+/// ```rust
+/// let chars = hdlc::SpecialChars::default();
+/// let buf = [0x00, 0x7E, 0x01, 0x7E, 0x7E, 0x02, 0x7E, 0x7E, 0x03];
 ///
-/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
-///     the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
-/// * **HDLCError::FendCharInData**: Checks to make sure the full decoded message is the full
-///     length.  Found the `SpecialChars::fend` inside the message.
-/// * **HDLCError::MissingTradeChar**: Checks to make sure every frame escape character `fesc`
-///     is followed by either a `tfend` or a `tfesc`.
-/// * **HDLCError::MissingFirstFend**: Input vector is missing a first `SpecialChars::fend`
-/// * **HDLCError::MissingFinalFend**: Input vector is missing a final `SpecialChars::fend`
+/// let (frames, rest) = hdlc::get_frames(&buf, chars);
+/// assert_eq!(frames, vec![&[0x7E, 0x01, 0x7E][..], &[0x7E, 0x02, 0x7E]]);
+/// assert_eq!(rest, &[0x7E, 0x03]);
+/// ```
+pub fn get_frames(buf: &[u8], s_chars: SpecialChars) -> (Vec<&[u8]>, &[u8]) {
+    let mut iter = frames(buf, s_chars);
+    let frames = iter.by_ref().collect();
+
+    (frames, iter.remainder())
+}
+
+/// Works like [`get_frames`], but hands out the decoded payloads.
 ///
-/// # Todo
+/// # Output
 ///
-/// Catch more errors, like an incomplete packet
+/// * **`Result<(Vec<Vec<u8>>, &[u8])>`**: The decoded payloads and the remainder
+///
+/// # Error
+///
+/// * Any error [`decode_frames`] returns.
 ///
 /// # Example
 /// ```rust
 /// let chars = hdlc::SpecialChars::default();
-/// let input: Vec<u8> = vec![ 0x7E, 0x01, 0x50, 0x00, 0x00, 0x00, 0x05, 0x80, 0x09, 0x7E];
-/// let op_vec = hdlc::decode(&input.to_vec(), chars);
+/// let buf = [0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x02];
+///
+/// let (payloads, rest) = hdlc::get_decoded_frames(&buf, chars).unwrap();
+/// assert_eq!(payloads, vec![vec![0x01, 0x7E]]);
+/// assert_eq!(rest, &[0x7E, 0x02]);
 /// ```
-pub fn decode(input: &[u8], s_chars: SpecialChars) -> Result<Vec<u8>, HDLCError> {
-    // Safety check to make sure the special character values are all unique
-    if s_chars.fend == s_chars.fesc {
-        // FIXME
-        return Err(HDLCError::DuplicateSpecialChar);
-    }
+pub fn get_decoded_frames(
+    buf: &[u8],
+    s_chars: SpecialChars,
+) -> Result<(Vec<Vec<u8>>, &[u8]), HDLCError> {
+    let (payloads, consumed) = decode_frames(buf, s_chars)?;
 
-    // Predefine the vector for speed
-    let mut output: Vec<u8> = Vec::with_capacity(input.len());
-    // Iterator over the input that allows peeking
-    let mut input_iter = input.iter().peekable();
-    // Tracks whether input contains a final FEND
-    let mut has_final_fend = false;
-
-    // Verify input begins with a FEND
-    if input_iter.next() != Some(&s_chars.fend) {
-        return Err(HDLCError::MissingFirstFend);
-    }
-
-    let d = s_chars.decodes();
-    // println!("Encode map: {:#?}", s_chars.translate);
-    // println!("Decode map: {:#?}", d);
+    Ok((payloads, &buf[consumed..]))
+}
 
-    // Loop over every byte of the message
-    while let Some(value) = input_iter.next() {
-        match *value {
-            // Handle a FESC
-            val if val == s_chars.fesc => {
-                let t = d.get(input_iter.next().unwrap());
-                match t {
-                    Some(&c) => output.push(c),
-                    None => return Err(HDLCError::MissingTradeChar),
-                }
-            }
-            // Handle a FEND
-            val if val == s_chars.fend => {
-                if input_iter.peek().is_none() {
-                    has_final_fend = true;
-                } else {
-                    return Err(HDLCError::FendCharInData);
-                }
-            }
-            // Handle any other bytes
-            _ => output.push(*value),
-        }
-    }
+/// A region of the input dropped by [`decode_lenient`], and why.
+#[derive(Debug, PartialEq)]
+pub struct Discarded {
+    /// Where the dropped bytes sit in the input, `fend` characters included
+    pub range: Range<usize>,
 
-    // If the message had a final FEND, return the message
-    if has_final_fend {
-        Ok(output)
-    } else {
-        Err(HDLCError::MissingFinalFend)
-    }
+    /// Why the bytes were dropped
+    pub error: HDLCError,
 }
 
-/// Produces slice (`&[u8]`) unescaped (decoded) message without `FEND` characters.
+/// Decodes every frame in a buffer, dropping malformed frames instead of failing as a whole.
+///
+/// A frame that fails to decode is skipped up to its closing `fend` and reported as a
+/// [`Discarded`] region, then decoding resumes with the next frame. This keeps a single
+/// corrupted byte on a lossy link from costing the frames around it. Bytes before the first
+/// `fend` are skipped silently, a trailing partial frame is reported with
+/// `HDLCError::MissingFinalFend`.
 ///
 /// # Inputs
-/// * **&mut [u8]**: A mutable slice of the bytes you want to decode
+/// * **&[u8]**: The raw bytes holding zero or more encoded frames
 /// * **SpecialChars**: The special characters you want to swap
 ///
 /// # Output
 ///
-/// * **Result<&[u8]>**: Decoded output message
+/// * **`Result<Vec<Result<Vec<u8>, Discarded>>>`**: The decoded payloads and the discarded
+///   regions, in input order
 ///
 /// # Error
 ///
 /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
-///     the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
-/// * **HDLCError::FendCharInData**: Checks to make sure the full decoded message is the full
-///     length.  Found the `SpecialChars::fend` inside the message.
-/// * **HDLCError::MissingTradeChar**: Checks to make sure every frame escape character `fesc`
-///     is followed by either a `tfend` or a `tfesc`.
-/// * **HDLCError::MissingFinalFend**: Input vector is missing a final `SpecialChars::fend`
-///
-/// # Todo
-///
-/// Catch more errors, like an incomplete packet
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
 ///
 /// # Example
 /// ```rust
-/// let chars = hdlc::SpecialChars::default();
-/// let mut input = [ 0x7E, 0x01, 0x50, 0x00, 0x00, 0x00, 0x05, 0x80, 0x09, 0x7E];
-/// let op_vec = hdlc::decode_slice(&mut input, chars);
+/// use hdlc::{decode_lenient, Discarded, HDLCError, SpecialChars};
+///
+/// let input = [0x7E, 0x01, 0x7E, 0x7E, 0x02, 0x7D, 0x03, 0x7E, 0x7E, 0x04, 0x7E];
+///
+/// let frames = decode_lenient(&input, SpecialChars::default()).unwrap();
+/// assert_eq!(
+///     frames,
+///     vec![
+///         Ok(vec![0x01]),
+///         Err(Discarded { range: 3..8, error: HDLCError::MissingTradeChar { offset: 5 } }),
+///         Ok(vec![0x04]),
+///     ]
+/// );
 /// ```
-#[cfg(not(feature = "no_std"))] // FIXME this isn't implemented in a no-std env
-pub fn decode_slice(input: &mut [u8], s_chars: SpecialChars) -> Result<&[u8], HDLCError> {
+pub fn decode_lenient(
+    input: &[u8],
+    s_chars: SpecialChars,
+) -> Result<Vec<Result<Vec<u8>, Discarded>>, HDLCError> {
     // Safety check to make sure the special character values are all unique
-    if s_chars.fend == s_chars.fesc {
-        // FIXME
+    if s_chars.has_duplicates() {
         return Err(HDLCError::DuplicateSpecialChar);
     }
 
-    // Define the counting variables for proper loop functionality
-    let mut sync = 0;
-    let mut swap = 0;
-    let mut last_was_fesc = 0;
-    let input_length = input.len();
-
-    // Predefine the vector for iterator
-    let mut output: Vec<u8> = Vec::with_capacity(input_length);
-    output.extend_from_slice(input);
-
-    let d = s_chars.decodes();
-
-    for (index, byte) in output.iter().enumerate() {
-        println!("D={}, B={} S={}  Output{:?}", index, byte, swap, input);
-        // Handle the special escape characters
-        if last_was_fesc > 0 {
-            // too lazy to fix these for now, not using decode_slice yet
-            // if *byte == s_chars.tfesc {
-            //     swap += 1;
-            //     input[index - swap - 1] = s_chars.fesc;
-            // } else if *byte == s_chars.tfend {
-            //     swap += 1;
-            //     input[index - swap - 1] = s_chars.fend;
-            // } else {
-            //     return Err(HDLCError::MissingTradeChar);
-            // }
-            last_was_fesc = 0
-        } else {
-            // Match based on the special characters, but struct fields are not patterns and cant match
-            if *byte == s_chars.fend {
-                // If we are already synced, this is the closing sync char
-                if sync > 0 {
-                    // Check to make sure the full message was decoded
-                    if (index + 1) < input_length {
-                        return Err(HDLCError::FendCharInData);
-                    }
-                    // Minus 1 because indexing starts at 0
-                    let end = index - swap - 1;
-                    return Ok(&input[..end]);
+    let mut results = Vec::new();
+    let mut iter = Frames::new(input, s_chars.fend);
+    while let Some(frame) = iter.next() {
+        let end = input.len() - iter.remainder().len();
+        let start = end - frame.len();
+        results.push(decode_ref(frame, &s_chars).map_err(|error| Discarded {
+            range: start..end,
+            error: error.shifted(start),
+        }));
+    }
 
-                // Todo: Maybe save for a 2nd message?  I currently throw an error above
-                } else {
-                    sync = 1;
-                }
-            } else if *byte == s_chars.fesc {
-                last_was_fesc = 1;
-            } else if sync > 0 {
-                // Minus 1 because indexing starts at 0
-                input[index - swap - 1] = *byte;
-            }
-        }
+    // A frame was opened but never closed
+    let rest = iter.remainder();
+    if !rest.is_empty() {
+        results.push(Err(Discarded {
+            range: input.len() - rest.len()..input.len(),
+            error: HDLCError::MissingFinalFend,
+        }));
     }
 
-    Err(HDLCError::MissingFinalFend)
+    Ok(results)
+}
+
+/// What a [`FrameReader`] reports to the callback registered with [`FrameReader::on_event`].
+///
+/// # Example
+/// ```rust
+/// use std::cell::RefCell;
+/// use std::io::Cursor;
+///
+/// use hdlc::{FrameReader, HDLCError, ReaderEvent, SpecialChars};
+///
+/// let events = RefCell::new(Vec::new());
+/// let mut data = Cursor::new(vec![0x55, 0x7E, 0x7D, 0x01, 0x7E, 0x7E, 0x02, 0x7E]);
+/// let frames: Vec<Vec<u8>> = FrameReader::builder(SpecialChars::default())
+///     .decode(true)
+///     .build(&mut data)
+///     .on_event(|event| events.borrow_mut().push(event))
+///     .collect();
+///
+/// assert_eq!(frames, vec![vec![0x02]]);
+/// assert_eq!(
+///     events.into_inner(),
+///     vec![
+///         ReaderEvent::Skipped { len: 1 },
+///         ReaderEvent::Dropped {
+///             len: 4,
+///             error: HDLCError::MissingTradeChar { offset: 1 },
+///         },
+///         ReaderEvent::Resynced,
+///     ]
+/// );
+/// ```
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ReaderEvent {
+    /// Bytes were discarded, either while hunting for the opening `fend` of a frame or because
+    /// they belonged to a frame longer than the maximum frame length. Reported as the reader
+    /// goes, so a long run may take several events. Flags used as fill between frames aren't
+    /// counted
+    Skipped {
+        /// Number of bytes discarded
+        len: usize,
+    },
+
+    /// A complete frame failed to decode and was dropped
+    Dropped {
+        /// Length of the frame, `fend` characters included
+        len: usize,
+
+        /// Why the frame failed to decode
+        error: HDLCError,
+    },
+
+    /// A frame was handed out again after bytes were skipped or frames dropped
+    Resynced,
 }
 
 /// A struct representing a reader for HDLC frames.
@@ -455,11 +2585,20 @@ pub struct FrameReader<'a> {
     /// Data source, can be any source that implements the std::io::Read trait
     reader: &'a mut dyn Read,
 
-    /// List of HDLC special chars
-    s_char: SpecialChars,
+    /// The received data and framing state
+    buffer: FrameBuffer,
 
-    /// The rest of received data
-    rest: Vec<u8>,
+    /// Whether frames are returned decoded instead of raw
+    decode: bool,
+
+    /// How frames are decoded in decode mode
+    options: DecodeOptions,
+
+    /// Callback receiving the events of the reader
+    on_event: Option<Box<dyn FnMut(ReaderEvent) + 'a>>,
+
+    /// Cleared when bytes are skipped or frames dropped, until the next frame is handed out
+    synced: bool,
 }
 
 impl<'a> FrameReader<'a> {
@@ -469,74 +2608,483 @@ impl<'a> FrameReader<'a> {
     /// * `reader` - A mutable reference to a reader that implements the `std::io::Read` trait.
     /// * `s_char` - The special characters used for HDLC encoding.
     pub fn new(reader: &'a mut dyn Read, s_char: SpecialChars) -> Self {
-        Self {
-            reader,
-            s_char,
-            rest: Vec::new(),
+        FrameReader::builder(s_char).build(reader)
+    }
+
+    /// Creates a FrameReaderBuilder to configure a FrameReader before creating it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hdlc::{FrameReader, SpecialChars};
+    /// use std::io::Cursor;
+    ///
+    /// let mut reader = Cursor::new(vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E]);
+    /// let mut hdlc_reader = FrameReader::builder(SpecialChars::default())
+    ///     .max_frame_len(4096)
+    ///     .decode(true)
+    ///     .build(&mut reader);
+    ///
+    /// assert_eq!(hdlc_reader.read_frame(), Some(vec![0x01, 0x7E]));
+    /// ```
+    pub fn builder(s_char: SpecialChars) -> FrameReaderBuilder {
+        FrameReaderBuilder {
+            buffer: FrameBuffer::new(s_char),
+            decode: false,
+            options: DecodeOptions::default(),
         }
     }
+
+    /// Registers a callback receiving a [`ReaderEvent`] whenever bytes are skipped, a frame is
+    /// dropped, or the reader resynchronizes, so link degradation can be monitored without
+    /// parsing the stream twice. Replaces any previous callback.
+    pub fn on_event(mut self, callback: impl FnMut(ReaderEvent) + 'a) -> Self {
+        self.on_event = Some(Box::new(callback));
+        self
+    }
 }
 
 impl FrameReader<'_> {
     /// Reads a frame from the reader.
     ///
-    /// The first bytes until the start of a frame are ignored.
+    /// The first bytes until the start of a frame are ignored. The reader is read from
+    /// repeatedly until a full frame has been received or it reports the end of the data.
+    /// In decode mode, frames that fail to decode are dropped.
     ///
     /// # Returns
     /// * `Option<Vec<u8>>` - The frame read from the reader, or None if no more frames are available.
+    ///   An error of the reader also ends the frames, see [`FrameReader::try_read_frame`].
     pub fn read_frame(&mut self) -> Option<Vec<u8>> {
-        let mut buffer = vec![0; 1024];
-        let bytes_read = self.reader.read(&mut buffer).ok().unwrap_or_default();
-        if bytes_read == 0 && self.rest.is_empty() {
-            // No more data to read
-            return None;
-        }
-
-        // Merge the rest with the new data
-        let mut data = self.rest.clone();
-        if bytes_read > 0 {
-            data.extend_from_slice(&buffer[..bytes_read]);
-        }
-
-        // Detect frame starting and ending with FEND
-        let mut in_frame = false;
-        let mut full_frame = false;
-        let mut frame: Vec<u8> = Vec::new();
-        let mut bytes_checked = 0;
-        for byte in data.iter() {
-            bytes_checked += 1;
-            if *byte == self.s_char.fend {
-                frame.push(*byte);
-
-                // End of a frame
-                if in_frame {
-                    self.rest.clear();
-                    full_frame = true;
-                    break;
-                } else {
-                    // If the next byte is also a fend byte, skip it
-                    if let Some(next_byte) = data.get(frame.len()) {
-                        if *next_byte == self.s_char.fend {
-                            continue;
-                        }
+        self.try_read_frame().ok().flatten()
+    }
+
+    /// Works like [`FrameReader::read_frame`], but reports an error of the reader instead of
+    /// taking it for the end of the data.
+    ///
+    /// # Returns
+    /// * `Result<Option<Vec<u8>>>` - The frame read from the reader, or None if no more frames
+    ///   are available.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::Io**: The reader failed. Frames already buffered are handed out first, and
+    ///   reading may be retried.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::io::{self, Read};
+    ///
+    /// use hdlc::{FrameReader, HDLCError, SpecialChars};
+    ///
+    /// struct Unplugged;
+    ///
+    /// impl Read for Unplugged {
+    ///     fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+    ///         Err(io::ErrorKind::BrokenPipe.into())
+    ///     }
+    /// }
+    ///
+    /// fn next_frame(reader: &mut FrameReader) -> Result<Vec<u8>, HDLCError> {
+    ///     Ok(reader.try_read_frame()?.unwrap_or_default())
+    /// }
+    ///
+    /// let mut port = Unplugged;
+    /// let mut reader = FrameReader::new(&mut port, SpecialChars::default());
+    /// assert_eq!(
+    ///     next_frame(&mut reader),
+    ///     Err(HDLCError::Io(io::ErrorKind::BrokenPipe.into()))
+    /// );
+    /// ```
+    pub fn try_read_frame(&mut self) -> Result<Option<Vec<u8>>, HDLCError> {
+        loop {
+            let len = match self.fill_frame()? {
+                Some(len) => len,
+                None => return Ok(None),
+            };
+            let frame = if self.decode {
+                self.decode_frame(len)
+            } else {
+                Some(self.buffer.frame(len).to_vec())
+            };
+            self.buffer.consume(len);
+
+            if frame.is_some() {
+                self.resynced();
+                return Ok(frame);
+            }
+        }
+    }
+
+    /// Works like [`FrameReader::read_frame`], but stamps the frame with the time read from
+    /// `clock` once it was received.
+    ///
+    /// The clock is read right after the frame is complete, so frames arriving in the same read
+    /// from the reader get close timestamps.
+    ///
+    /// # Returns
+    /// * `Option<TimedFrame>` - The frame and when it was received, or None if no more frames
+    ///   are available.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::io::Cursor;
+    ///
+    /// use hdlc::{FrameReader, SpecialChars, SystemClock};
+    ///
+    /// let mut data = Cursor::new(vec![0x7E, 0x01, 0x7E]);
+    /// let mut reader = FrameReader::new(&mut data, SpecialChars::default());
+    ///
+    /// let frame = reader.read_frame_timed(&mut SystemClock).unwrap();
+    /// assert_eq!(frame.frame, vec![0x7E, 0x01, 0x7E]);
+    /// assert!(frame.received.elapsed().as_secs() < 60);
+    /// ```
+    pub fn read_frame_timed<C: Clock>(&mut self, clock: &mut C) -> Option<TimedFrame<C::Instant>> {
+        let frame = self.read_frame()?;
+        Some(TimedFrame {
+            frame,
+            received: clock.now(),
+        })
+    }
+
+    /// Reads a frame from the reader into a caller provided buffer.
+    ///
+    /// Works like [`FrameReader::read_frame`] but copies the frame into `buf` instead of
+    /// allocating a new `Vec` for every frame.
+    ///
+    /// # Returns
+    /// * `Result<Option<usize>>` - The length of the frame written to `buf`, or None if no more
+    ///   frames are available.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::BufferTooSmall**: The frame doesn't fit in `buf`. The frame stays buffered
+    ///   so it can be read again with a larger buffer.
+    /// * **HDLCError::Io**: The reader failed. Frames already buffered are handed out first.
+    pub fn read_frame_into(&mut self, buf: &mut [u8]) -> Result<Option<usize>, HDLCError> {
+        loop {
+            let len = match self.fill_frame()? {
+                Some(len) => len,
+                None => return Ok(None),
+            };
+
+            if !self.decode {
+                if len > buf.len() {
+                    return Err(HDLCError::BufferTooSmall);
+                }
+                buf[..len].copy_from_slice(self.buffer.frame(len));
+                self.buffer.consume(len);
+                self.resynced();
+                return Ok(Some(len));
+            }
+
+            match self.decode_frame(len) {
+                Some(payload) if payload.len() > buf.len() => {
+                    return Err(HDLCError::BufferTooSmall)
+                }
+                Some(payload) => {
+                    buf[..payload.len()].copy_from_slice(&payload);
+                    self.buffer.consume(len);
+                    self.resynced();
+                    return Ok(Some(payload.len()));
+                }
+                None => self.buffer.consume(len),
+            }
+        }
+    }
+
+    /// Decodes the buffered frame of `len` bytes, or returns None if it is dropped.
+    fn decode_frame(&mut self, len: usize) -> Option<Vec<u8>> {
+        let decoded = decode_with_ref(self.buffer.frame(len), &self.buffer.s_char, self.options);
+        match decoded {
+            Ok(payload) => Some(payload).filter(|p| self.options.empty_frames || !p.is_empty()),
+            Err(error) => {
+                self.emit(ReaderEvent::Dropped { len, error });
+                None
+            }
+        }
+    }
+
+    /// Hands `event` to the callback, if one is registered
+    fn emit(&mut self, event: ReaderEvent) {
+        if event != ReaderEvent::Resynced {
+            self.synced = false;
+        }
+        if let Some(on_event) = &mut self.on_event {
+            on_event(event);
+        }
+    }
+
+    /// Reports the bytes the buffer discarded since the last call
+    fn report_skipped(&mut self) {
+        let len = core::mem::take(&mut self.buffer.skipped);
+        if len > 0 {
+            self.emit(ReaderEvent::Skipped { len });
+        }
+    }
+
+    /// Reports a frame handed out after bytes were skipped or frames dropped
+    fn resynced(&mut self) {
+        if !self.synced {
+            self.synced = true;
+            self.emit(ReaderEvent::Resynced);
+        }
+    }
+
+    /// Reads until a full frame sits at the start of the buffered data and returns its length.
+    fn fill_frame(&mut self) -> io::Result<Option<usize>> {
+        loop {
+            // Frames already buffered are handed out before touching the reader again
+            let frame = self.buffer.find_frame();
+            self.report_skipped();
+            if frame.is_some() {
+                return Ok(frame);
+            }
+
+            // Read straight into the buffer, a frame may span several reads
+            let result = self.reader.read(self.buffer.spare());
+            self.buffer.commit(*result.as_ref().unwrap_or(&0));
+            let bytes_read = match result {
+                Ok(bytes_read) => bytes_read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if bytes_read == 0 {
+                // No more data to read
+                return Ok(None);
+            }
+        }
+    }
+}
+
+/// Received data waiting to be split into frames, shared by the readers of this crate.
+#[derive(Debug, Clone)]
+pub(crate) struct FrameBuffer {
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// The rest of received data
+    pub(crate) rest: Vec<u8>,
+
+    /// Number of bytes requested from the reader per read call
+    chunk_size: usize,
+
+    /// Longest frame accepted, longer frames are dropped
+    max_frame_len: Option<usize>,
+
+    /// Set while dropping the bytes of a frame that exceeded `max_frame_len`
+    pub(crate) overflow: bool,
+
+    /// Length of the buffered partial frame already searched for its closing FEND
+    scanned: usize,
+
+    /// Whether back-to-back FENDs are handed out as empty frames
+    pub(crate) empty_frames: bool,
+
+    /// Whether the closing FEND of a frame may also open the next frame
+    pub(crate) shared_flags: bool,
+
+    /// Whether frames are only closed by a FEND, without an opening one
+    pub(crate) terminator_only: bool,
+
+    /// Number of bytes discarded since last taken, in front of frames or as part of oversized
+    /// frames
+    pub(crate) skipped: usize,
+}
+
+impl FrameBuffer {
+    /// Creates an empty buffer reading 1024 bytes at a time with no frame length limit
+    pub(crate) fn new(s_char: SpecialChars) -> Self {
+        FrameBuffer {
+            s_char,
+            rest: Vec::new(),
+            chunk_size: 1024,
+            max_frame_len: None,
+            overflow: false,
+            scanned: 0,
+            empty_frames: false,
+            shared_flags: false,
+            terminator_only: false,
+            skipped: 0,
+        }
+    }
+
+    /// Splits the buffered data into frames the way `options` asks for
+    pub(crate) fn apply_options(&mut self, options: &DecodeOptions) {
+        self.max_frame_len = options.max_len;
+        self.empty_frames = options.empty_frames;
+        self.shared_flags = options.shared_flags;
+        self.terminator_only = options.framing == Framing::TerminatorOnly;
+    }
+
+    /// Returns `chunk_size` bytes of space at the end of the buffer to read into.
+    /// Must be followed by a call to [`FrameBuffer::commit`].
+    pub(crate) fn spare(&mut self) -> &mut [u8] {
+        let buffered = self.rest.len();
+        self.rest.resize(buffered + self.chunk_size, 0);
+        &mut self.rest[buffered..]
+    }
+
+    /// Keeps the first `bytes_read` bytes of the space handed out by [`FrameBuffer::spare`]
+    pub(crate) fn commit(&mut self, bytes_read: usize) {
+        let buffered = self.rest.len() - self.chunk_size;
+        self.rest.truncate(buffered + bytes_read);
+    }
+
+    /// Appends received bytes to the buffer
+    pub(crate) fn extend(&mut self, data: &[u8]) {
+        self.rest.extend_from_slice(data);
+    }
+
+    /// Returns the first `len` bytes of the buffer, as found by [`FrameBuffer::find_frame`]
+    pub(crate) fn frame(&self, len: usize) -> &[u8] {
+        &self.rest[..len]
+    }
+
+    /// Removes the first `len` bytes of the buffer. The closing FEND of a frame is kept when it
+    /// may open the next frame.
+    pub(crate) fn consume(&mut self, len: usize) {
+        let shared = self.shared_flags
+            && !self.empty_frames
+            && !self.terminator_only
+            && self.rest[..len].ends_with(&[self.s_char.fend]);
+        self.rest.drain(..len - usize::from(shared));
+        self.scanned = 0;
+    }
+
+    /// Returns true if bytes of a frame are buffered, or the rest of an oversized frame is being
+    /// dropped. Lone FENDs don't count, they may be fill or the opening of the next frame.
+    pub(crate) fn has_partial(&self) -> bool {
+        let fend = self.s_char.fend;
+        self.overflow || self.rest.iter().any(|&b| b != fend)
+    }
+
+    /// Drops all buffered bytes, ending an oversized frame too, and returns how many were dropped
+    pub(crate) fn discard(&mut self) -> usize {
+        let len = self.rest.len();
+        self.skipped += len;
+        self.rest.clear();
+        self.overflow = false;
+        self.scanned = 0;
+        len
+    }
+
+    /// Drops the bytes in front of the first frame and returns the frame length if it is complete.
+    pub(crate) fn find_frame(&mut self) -> Option<usize> {
+        let max_frame_len = self.max_frame_len.unwrap_or(usize::MAX);
+        loop {
+            // Drop the rest of an oversized frame up to and including its closing FEND
+            if self.overflow {
+                match memchr(self.s_char.fend, &self.rest) {
+                    Some(end) => {
+                        self.rest.drain(..=end);
+                        self.skipped += end + 1;
+                        self.overflow = false;
+                    }
+                    None => {
+                        self.skipped += self.rest.len();
+                        self.rest.clear();
+                        return None;
                     }
-                    // The old bytes except of FEND should be removed
-                    frame.drain(..frame.len().saturating_sub(1));
-                    in_frame = true;
                 }
+            }
+
+            let frame_len = if self.scanned > 1 {
+                // Resume looking for the closing FEND where the last call stopped, so a large
+                // frame arriving in many reads isn't searched from its start every time
+                memchr(self.s_char.fend, &self.rest[self.scanned..])
+                    .map(|len| self.scanned + len + 1)
             } else {
-                frame.push(*byte);
+                let mut iter = Frames {
+                    buf: &self.rest,
+                    fend: self.s_char.fend,
+                    pos: 0,
+                    empty: self.empty_frames,
+                    share: self.shared_flags,
+                    shared: false,
+                    terminated: self.terminator_only,
+                };
+                let frame_len = iter.next().map(<[u8]>::len);
+                let skipped =
+                    self.rest.len() - iter.remainder().len() - frame_len.unwrap_or_default();
+                // Flags between frames are fill, not discarded data
+                let fend = self.s_char.fend;
+                self.skipped += self.rest.drain(..skipped).filter(|&b| b != fend).count();
+                frame_len
+            };
+            self.scanned = 0;
+
+            match frame_len {
+                Some(len) if len > max_frame_len => {
+                    self.rest.drain(..len);
+                    self.skipped += len;
+                }
+                None if self.rest.len() > max_frame_len => {
+                    self.skipped += self.rest.len();
+                    self.rest.clear();
+                    self.overflow = true;
+                    return None;
+                }
+                None => {
+                    // The partial frame holds no FEND but the opening one
+                    self.scanned = self.rest.len();
+                    return None;
+                }
+                Some(_) => return frame_len,
             }
         }
+    }
+}
+
+/// Builder for a [`FrameReader`], created by [`FrameReader::builder`].
+#[derive(Debug, Clone)]
+pub struct FrameReaderBuilder {
+    /// The framing state the reader starts with
+    buffer: FrameBuffer,
 
-        // Save the rest of the data for the next read
-        self.rest.extend_from_slice(&data[bytes_checked..]);
+    /// Whether frames are returned decoded instead of raw
+    decode: bool,
 
-        // If a frame is started and ended with FEND, return it, else its invalid
-        if full_frame {
-            Some(frame)
-        } else {
-            None
+    /// How frames are decoded in decode mode
+    options: DecodeOptions,
+}
+
+impl FrameReaderBuilder {
+    /// Sets the number of bytes requested from the reader per read call. Defaults to 1024.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.buffer.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sets the longest frame accepted, `fend` characters included. Longer frames are dropped
+    /// and never buffered in full. Unlimited by default.
+    pub fn max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.buffer.max_frame_len = Some(max_frame_len);
+        self
+    }
+
+    /// Sets whether frames are returned decoded instead of raw. Defaults to raw frames.
+    pub fn decode(mut self, decode: bool) -> Self {
+        self.decode = decode;
+        self
+    }
+
+    /// Sets how frames are decoded and switches to decode mode. `DecodeOptions::max_len`
+    /// replaces the limit set by [`FrameReaderBuilder::max_frame_len`].
+    pub fn options(mut self, options: DecodeOptions) -> Self {
+        self.buffer.apply_options(&options);
+        self.decode = true;
+        self.options = options;
+        self
+    }
+
+    /// Creates the FrameReader reading from `reader`.
+    pub fn build(self, reader: &mut dyn Read) -> FrameReader<'_> {
+        FrameReader {
+            reader,
+            buffer: self.buffer,
+            decode: self.decode,
+            options: self.options,
+            on_event: None,
+            synced: true,
         }
     }
 }
@@ -549,22 +3097,147 @@ impl Iterator for FrameReader<'_> {
     }
 }
 
-#[derive(Debug, Error, PartialEq)]
+#[derive(Debug, Error)]
 /// Common error for HDLC actions.
+#[non_exhaustive]
 pub enum HDLCError {
     /// Catches duplicate special characters.
     #[error("Caught a duplicate special character.")]
     DuplicateSpecialChar,
     /// Catches a random sync char in the data.
-    #[error("Caught a random sync char in the data.")]
-    FendCharInData,
+    #[error("Caught a random sync char in the data at byte {offset}.")]
+    FendCharInData {
+        /// Position of the `fend` in the input
+        offset: usize,
+    },
     /// Catches a random swap char, `fesc`, in the data with no `tfend` or `tfesc`.
-    #[error("Caught a random swap char in the data.")]
-    MissingTradeChar,
+    #[error("Caught a random swap char in the data at byte {offset}.")]
+    MissingTradeChar {
+        /// Position of the `fesc` in the input
+        offset: usize,
+    },
     /// No first fend on the message.
     #[error("Missing first FEND character.")]
     MissingFirstFend,
-    /// No final fend on the message.
+    /// No final fend on the message, and no more bytes will follow.
     #[error("Missing final FEND character.")]
     MissingFinalFend,
+    /// The input ends inside a frame, more bytes may complete it.
+    #[error("Incomplete frame, more bytes are needed.")]
+    Incomplete {
+        /// Least number of further bytes that could complete the frame, if known
+        needed: Option<usize>,
+    },
+    /// The output buffer is too small to hold the result.
+    #[error("Output buffer is too small.")]
+    BufferTooSmall,
+    /// The frame queue has no free slot left.
+    #[error("Frame queue is full.")]
+    QueueFull,
+    /// Allocating memory for the output failed.
+    #[error("Memory allocation failed.")]
+    OutOfMemory,
+    /// The frame check sequence doesn't match the payload.
+    #[error("Frame check sequence mismatch.")]
+    FcsMismatch,
+    /// The header check sequence doesn't match the header, or the header is cut short.
+    #[error("Header check sequence mismatch.")]
+    HcsMismatch,
+    /// The frame is longer than the configured maximum.
+    #[error("Frame exceeds the maximum length.")]
+    FrameTooLong,
+    /// The length in the frame format field doesn't match the frame, which was truncated or
+    /// merged with another.
+    #[error("Frame format field declares {declared} bytes, the frame has {actual}.")]
+    FrameLengthMismatch {
+        /// Length declared by the frame format field
+        declared: usize,
+        /// Length of the frame between its flags
+        actual: usize,
+    },
+    /// The payload to encode is longer than the configured maximum.
+    #[error("Payload exceeds the maximum length.")]
+    PayloadTooLarge,
+    /// A [`PayloadTransform`] failed to transform the payload.
+    #[error("Payload transform failed.")]
+    TransformFailed,
+    /// A sealing [`PayloadTransform`] found the payload forged or corrupted.
+    #[error("Payload authentication failed.")]
+    AuthenticationFailed,
+    /// The peer didn't acknowledge a frame sent again the configured number of times.
+    #[error("Retries exhausted without acknowledgement.")]
+    RetriesExhausted,
+    /// A hex string holds a character that is no hex digit or separator, or an odd digit.
+    #[error("Caught an invalid hex digit at character {offset}.")]
+    InvalidHex {
+        /// Position of the byte's first digit in the string
+        offset: usize,
+    },
+    /// Reading or writing the underlying stream failed.
+    #[cfg(feature = "std")]
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl PartialEq for HDLCError {
+    fn eq(&self, other: &HDLCError) -> bool {
+        match (self, other) {
+            // `io::Error` has no equality of its own, errors of the same kind compare equal
+            #[cfg(feature = "std")]
+            (HDLCError::Io(a), HDLCError::Io(b)) => a.kind() == b.kind(),
+            (HDLCError::FendCharInData { offset: a }, HDLCError::FendCharInData { offset: b })
+            | (
+                HDLCError::MissingTradeChar { offset: a },
+                HDLCError::MissingTradeChar { offset: b },
+            ) => a == b,
+            (HDLCError::Incomplete { needed: a }, HDLCError::Incomplete { needed: b }) => a == b,
+            (HDLCError::InvalidHex { offset: a }, HDLCError::InvalidHex { offset: b }) => a == b,
+            (
+                HDLCError::FrameLengthMismatch {
+                    declared: a,
+                    actual: c,
+                },
+                HDLCError::FrameLengthMismatch {
+                    declared: b,
+                    actual: d,
+                },
+            ) => a == b && c == d,
+            _ => core::mem::discriminant(self) == core::mem::discriminant(other),
+        }
+    }
+}
+
+impl HDLCError {
+    /// Returns the position in the input the error was detected at, if the error has one.
+    ///
+    /// # Example
+    /// ```rust
+    /// let chars = hdlc::SpecialChars::default();
+    ///
+    /// let error = hdlc::decode(&[0x7E, 0x01, 0x7D, 0x02, 0x7E], chars).unwrap_err();
+    ///
+    /// assert_eq!(error.offset(), Some(2));
+    /// ```
+    pub fn offset(&self) -> Option<usize> {
+        match *self {
+            HDLCError::FendCharInData { offset } | HDLCError::MissingTradeChar { offset } => {
+                Some(offset)
+            }
+            _ => None,
+        }
+    }
+
+    /// Moves the position of the error by `by` bytes, for a frame found `by` bytes into a larger
+    /// input
+    pub(crate) fn shifted(self, by: usize) -> HDLCError {
+        match self {
+            HDLCError::FendCharInData { offset } => HDLCError::FendCharInData {
+                offset: offset + by,
+            },
+            HDLCError::MissingTradeChar { offset } => HDLCError::MissingTradeChar {
+                offset: offset + by,
+            },
+            error => error,
+        }
+    }
 }
@@ -0,0 +1,202 @@
+//! Duplex link sending and receiving frames over one transport
+
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+use crate::pacing::Pacer;
+use crate::{
+    decode_with_ref, encode_with_ref, DecodeOptions, EncodeOptions, FrameBuffer, HDLCError,
+    LinkMetrics, Pacing, SpecialChars,
+};
+
+/// A duplex HDLC link over a reader and a writer, usually the two halves of one serial port.
+///
+/// Payloads are encoded and written by [`Link::send`], and received frames are read, split
+/// and decoded by [`Link::recv`]. Both count into the [`LinkMetrics`] of the link.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{Link, SpecialChars};
+/// use std::io::Cursor;
+///
+/// let incoming = Cursor::new(vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E]);
+/// let mut link = Link::new(incoming, Vec::new(), SpecialChars::default());
+///
+/// link.send(&[0x02, 0x7D]).unwrap();
+/// assert_eq!(link.recv().unwrap(), vec![0x01, 0x7E]);
+///
+/// let (_, outgoing) = link.into_inner();
+/// assert_eq!(outgoing, vec![0x7E, 0x02, 0x7D, 0x5D, 0x7E]);
+/// ```
+#[derive(Debug)]
+pub struct Link<R, W> {
+    /// Source of received frames
+    reader: R,
+
+    /// Sink for sent frames
+    writer: W,
+
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// The received data and framing state
+    buffer: FrameBuffer,
+
+    /// How received frames are decoded, and the FCS and framing of sent ones
+    options: DecodeOptions,
+
+    /// Counters of the frames sent and received
+    metrics: Arc<LinkMetrics>,
+
+    /// Separation of consecutive frames sent
+    pacer: Pacer,
+}
+
+impl<R: Read, W: Write> Link<R, W> {
+    /// Creates a new Link instance.
+    ///
+    /// # Arguments
+    /// * `reader` - The source frames are received from.
+    /// * `writer` - The sink frames are sent to.
+    /// * `s_char` - The special characters used for HDLC encoding.
+    pub fn new(reader: R, writer: W, s_char: SpecialChars) -> Self {
+        Link {
+            reader,
+            writer,
+            buffer: FrameBuffer::new(s_char.clone()),
+            s_char,
+            options: DecodeOptions::default(),
+            metrics: Arc::new(LinkMetrics::new()),
+            pacer: Pacer::default(),
+        }
+    }
+
+    /// Sets how received frames are decoded. Sent frames get the FCS and framing of `options`
+    /// too, so both ends of the link agree.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hdlc::{DecodeOptions, FcsMode, Link, SpecialChars};
+    /// use std::io::Cursor;
+    ///
+    /// let options = DecodeOptions {
+    ///     fcs: FcsMode::Crc16,
+    ///     ..DecodeOptions::default()
+    /// };
+    /// let incoming = Cursor::new(vec![0x7E, 0x01, 0x00, 0x00, 0x7E]);
+    /// let mut link = Link::new(incoming, Vec::new(), SpecialChars::default()).options(options);
+    ///
+    /// assert!(link.recv().is_err());
+    /// assert_eq!(link.metrics().snapshot().crc_failures, 1);
+    /// ```
+    pub fn options(mut self, options: DecodeOptions) -> Self {
+        self.buffer.apply_options(&options);
+        self.options = options;
+        self
+    }
+
+    /// Separates consecutive frames sent by `pacing`.
+    pub fn pacing(mut self, pacing: Pacing) -> Self {
+        self.pacer = Pacer::new(pacing);
+        self
+    }
+
+    /// Counts into `metrics` instead of counters of its own, to share them with other links or
+    /// layers, or to track throughput and frame sizes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// use hdlc::{Link, LinkMetrics, SpecialChars};
+    ///
+    /// // 115200 baud, 8N1
+    /// let metrics = LinkMetrics::new().with_throughput(Duration::from_secs(5), Some(11520));
+    /// let mut link = Link::new(Cursor::new(Vec::new()), Vec::new(), SpecialChars::default())
+    ///     .with_metrics(Arc::new(metrics));
+    ///
+    /// link.send(&[0x01, 0x02]).unwrap();
+    /// let throughput = link.metrics().throughput().unwrap();
+    /// assert!(throughput.utilization_out.unwrap() > 0.0);
+    /// ```
+    pub fn with_metrics(mut self, metrics: Arc<LinkMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Returns the counters of the link, clone the `Arc` to poll them from elsewhere.
+    pub fn metrics(&self) -> &Arc<LinkMetrics> {
+        &self.metrics
+    }
+
+    /// Appends the FCS to `payload`, encodes it into a frame and writes it out, flushing the
+    /// writer. Waits for or writes the gap to the previous frame first, see [`Link::pacing`].
+    ///
+    /// # Error
+    ///
+    /// * **io::ErrorKind::InvalidInput**: The payload couldn't be encoded, wrapping the
+    ///   `HDLCError`.
+    /// * Any error returned by the writer.
+    pub fn send(&mut self, payload: &[u8]) -> io::Result<()> {
+        let mut contents = payload.to_vec();
+        self.options.fcs.append(&mut contents);
+        let options = EncodeOptions {
+            framing: self.options.framing,
+            ..EncodeOptions::default()
+        };
+        let frame = encode_with_ref(&contents, &self.s_char, options)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        self.pacer
+            .before_frame(&mut self.writer, self.s_char.fend)?;
+        self.writer.write_all(&frame)?;
+        self.writer.flush()?;
+        self.pacer.after_frame();
+        self.metrics.record_sent(payload.len(), frame.len());
+
+        Ok(())
+    }
+
+    /// Reads until a full frame is received and returns its decoded payload, FCS verified.
+    ///
+    /// The bytes in front of a frame are ignored. Data following the frame stays buffered for the
+    /// next call. Frames failing their FCS are counted as CRC failures, other malformed frames as
+    /// resyncs.
+    ///
+    /// # Error
+    ///
+    /// * **io::ErrorKind::InvalidData**: The received frame couldn't be decoded, wrapping the
+    ///   `HDLCError`. The frame is dropped and the next call continues with the following data.
+    /// * **io::ErrorKind::UnexpectedEof**: The reader reached its end before a full frame
+    ///   was received.
+    /// * Any error returned by the reader.
+    pub fn recv(&mut self) -> io::Result<Vec<u8>> {
+        loop {
+            if let Some(len) = self.buffer.find_frame() {
+                let payload = decode_with_ref(self.buffer.frame(len), &self.s_char, self.options);
+                self.buffer.consume(len);
+                match &payload {
+                    Ok(payload) => self.metrics.record_received(payload.len(), len),
+                    Err(HDLCError::FcsMismatch) => self.metrics.record_crc_failure(),
+                    Err(_) => self.metrics.record_resync(),
+                }
+                return payload.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+            }
+
+            let result = self.reader.read(self.buffer.spare());
+            self.buffer.commit(*result.as_ref().unwrap_or(&0));
+            match result {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Returns the reader and the writer, dropping any buffered data.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+}
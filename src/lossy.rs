@@ -0,0 +1,212 @@
+//! Channel simulator corrupting the bytes passing through it, enabled by the `test-utils` feature
+
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+
+use crate::SpecialChars;
+
+/// How often a [`LossyChannel`] corrupts the bytes passing through it. Probabilities run from
+/// 0.0, never, to 1.0, always.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LossyConfig {
+    /// Seed of the random number generator, the same seed corrupts the same bytes
+    pub seed: u64,
+
+    /// Probability of a byte having one of its bits flipped
+    pub bit_flip: f64,
+
+    /// Probability of a byte being dropped
+    pub drop: f64,
+
+    /// Probability of a byte being sent twice
+    pub duplicate: f64,
+
+    /// Probability of a frame being cut short at a byte, dropping everything up to the next
+    /// `fend`
+    pub truncate: f64,
+}
+
+/// Number of corruptions a [`LossyChannel`] injected so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LossyStats {
+    /// Bytes with a bit flipped
+    pub flipped: usize,
+
+    /// Bytes dropped, not counting those of truncated frames
+    pub dropped: usize,
+
+    /// Bytes sent twice
+    pub duplicated: usize,
+
+    /// Frames cut short
+    pub truncated: usize,
+}
+
+/// A `Read` and `Write` wrapper injecting bit flips, dropped and duplicated bytes and truncated
+/// frames, for regression testing resynchronization and FCS checks deterministically.
+///
+/// Corruptions are drawn from a seeded generator, so a failing test replays the same way.
+/// Bytes read from the inner reader and bytes written to the inner writer are corrupted alike.
+///
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+///
+/// use hdlc::test_utils::{LossyChannel, LossyConfig};
+/// use hdlc::{FrameReader, SpecialChars};
+///
+/// let config = LossyConfig {
+///     seed: 7,
+///     drop: 0.05,
+///     ..LossyConfig::default()
+/// };
+/// let data = Cursor::new([0x7E, 0x01, 0x02, 0x03, 0x7E].repeat(20));
+/// let mut channel = LossyChannel::new(data, SpecialChars::default(), config);
+///
+/// let frames = FrameReader::new(&mut channel, SpecialChars::default()).count();
+/// assert!(frames <= 20);
+/// assert!(channel.stats().dropped > 0);
+/// ```
+#[derive(Debug)]
+pub struct LossyChannel<T> {
+    /// The wrapped reader or writer
+    inner: T,
+
+    /// Frame END character, kept by truncation
+    fend: u8,
+
+    /// How often bytes are corrupted
+    config: LossyConfig,
+
+    /// State of the xorshift generator, never 0
+    state: u64,
+
+    /// Set while dropping the rest of a truncated frame
+    truncating: bool,
+
+    /// Corrupted bytes read but not handed out yet
+    pending: VecDeque<u8>,
+
+    /// Corruptions injected so far
+    stats: LossyStats,
+}
+
+impl<T> LossyChannel<T> {
+    /// Creates a new LossyChannel instance.
+    ///
+    /// # Arguments
+    /// * `inner` - The reader or writer the bytes pass through.
+    /// * `s_chars` - The special characters used for HDLC encoding, telling truncation where
+    ///   frames end.
+    /// * `config` - How often bytes are corrupted.
+    pub fn new(inner: T, s_chars: SpecialChars, config: LossyConfig) -> Self {
+        LossyChannel {
+            inner,
+            fend: s_chars.fend,
+            config,
+            // Xorshift gets stuck at 0
+            state: config.seed.max(1),
+            truncating: false,
+            pending: VecDeque::new(),
+            stats: LossyStats::default(),
+        }
+    }
+
+    /// Returns the corruptions injected so far.
+    pub fn stats(&self) -> LossyStats {
+        self.stats
+    }
+
+    /// Returns a reference to the wrapped reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the wrapped reader or writer. Corrupted bytes read but not handed out are lost.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the next number of the xorshift generator
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// Returns true with `probability`
+    fn roll(&mut self, probability: f64) -> bool {
+        // The top 53 bits make a uniform float in [0, 1)
+        let sample = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        sample < probability
+    }
+
+    /// Appends the corrupted bytes of `data` to `out`
+    fn corrupt(&mut self, data: &[u8], out: &mut impl Extend<u8>) {
+        for &byte in data {
+            if self.truncating {
+                if byte != self.fend {
+                    continue;
+                }
+                self.truncating = false;
+            } else if self.roll(self.config.drop) {
+                self.stats.dropped += 1;
+                continue;
+            } else if byte != self.fend && self.roll(self.config.truncate) {
+                self.stats.truncated += 1;
+                self.truncating = true;
+                continue;
+            }
+
+            let mut byte = byte;
+            if self.roll(self.config.bit_flip) {
+                self.stats.flipped += 1;
+                byte ^= 1 << (self.next_u64() % 8);
+            }
+            out.extend([byte]);
+            if self.roll(self.config.duplicate) {
+                self.stats.duplicated += 1;
+                out.extend([byte]);
+            }
+        }
+    }
+}
+
+impl<T: Read> Read for LossyChannel<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // Don't report the end of the data while every byte read so far was dropped
+        let mut raw = vec![0; buf.len()];
+        while self.pending.is_empty() {
+            let len = self.inner.read(&mut raw)?;
+            if len == 0 {
+                return Ok(0);
+            }
+            let mut pending = core::mem::take(&mut self.pending);
+            self.corrupt(&raw[..len], &mut pending);
+            self.pending = pending;
+        }
+
+        let len = buf.len().min(self.pending.len());
+        for (slot, byte) in buf.iter_mut().zip(self.pending.drain(..len)) {
+            *slot = byte;
+        }
+        Ok(len)
+    }
+}
+
+impl<T: Write> Write for LossyChannel<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut corrupted = Vec::with_capacity(buf.len());
+        self.corrupt(buf, &mut corrupted);
+        self.inner.write_all(&corrupted)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
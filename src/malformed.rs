@@ -0,0 +1,166 @@
+//! Builder of deliberately malformed frames, enabled by the `test-utils` feature
+
+use crate::{escape_unchecked, FcsMode, SpecialChars};
+
+/// Builds a frame from a valid payload with the faults picked, for verifying the error paths of
+/// receivers against realistic corruption.
+///
+/// Without any fault the frame is the one [`encode`](crate::encode) produces for the payload and
+/// its frame check sequence.
+///
+/// # Example
+/// ```rust
+/// use hdlc::test_utils::MalformedFrame;
+/// use hdlc::{decode, decode_with, DecodeOptions, FcsMode, HDLCError, SpecialChars};
+///
+/// let chars = SpecialChars::default();
+///
+/// let frame = MalformedFrame::new(&[0x01, 0x02], chars.clone())
+///     .stray_fesc(1)
+///     .build();
+/// assert_eq!(
+///     decode(&frame, chars.clone()),
+///     Err(HDLCError::MissingTradeChar { offset: 2 })
+/// );
+///
+/// let frame = MalformedFrame::new(&[0x01, 0x02], chars.clone())
+///     .fcs(FcsMode::Crc16)
+///     .bad_fcs()
+///     .build();
+/// let options = DecodeOptions {
+///     fcs: FcsMode::Crc16,
+///     ..DecodeOptions::default()
+/// };
+/// assert_eq!(decode_with(&frame, chars, options), Err(HDLCError::FcsMismatch));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MalformedFrame {
+    /// The payload, before the frame check sequence
+    payload: Vec<u8>,
+
+    /// List of HDLC special chars
+    s_chars: SpecialChars,
+
+    /// The frame check sequence appended to the payload
+    fcs: FcsMode,
+
+    /// Payload position a `fesc` without trade char is inserted at
+    stray_fesc: Option<usize>,
+
+    /// Whether the opening `fend` is left out
+    no_opening: bool,
+
+    /// Whether the closing `fend` is left out
+    no_closing: bool,
+
+    /// Whether the frame check sequence is corrupted
+    bad_fcs: bool,
+
+    /// Frame length the payload is padded beyond
+    over_length: Option<usize>,
+}
+
+impl MalformedFrame {
+    /// Creates a new MalformedFrame instance without faults.
+    ///
+    /// # Arguments
+    /// * `payload` - The valid payload the frame is built from.
+    /// * `s_chars` - The special characters used for HDLC encoding.
+    pub fn new(payload: &[u8], s_chars: SpecialChars) -> Self {
+        MalformedFrame {
+            payload: payload.to_vec(),
+            s_chars,
+            fcs: FcsMode::None,
+            stray_fesc: None,
+            no_opening: false,
+            no_closing: false,
+            bad_fcs: false,
+            over_length: None,
+        }
+    }
+
+    /// Appends a frame check sequence to the payload.
+    pub fn fcs(mut self, fcs: FcsMode) -> Self {
+        self.fcs = fcs;
+        self
+    }
+
+    /// Inserts a `fesc` followed by a byte that is no trade char in front of payload byte
+    /// `index`, or at the end of the payload if it is shorter.
+    pub fn stray_fesc(mut self, index: usize) -> Self {
+        self.stray_fesc = Some(index);
+        self
+    }
+
+    /// Leaves out the opening `fend`.
+    pub fn without_opening_flag(mut self) -> Self {
+        self.no_opening = true;
+        self
+    }
+
+    /// Leaves out the closing `fend`.
+    pub fn without_closing_flag(mut self) -> Self {
+        self.no_closing = true;
+        self
+    }
+
+    /// Corrupts the frame check sequence. Has no effect without one, see
+    /// [`MalformedFrame::fcs`].
+    pub fn bad_fcs(mut self) -> Self {
+        self.bad_fcs = true;
+        self
+    }
+
+    /// Pads the payload until the frame is longer than `max_len` bytes, keeping the frame check
+    /// sequence valid.
+    pub fn over_length(mut self, max_len: usize) -> Self {
+        self.over_length = Some(max_len);
+        self
+    }
+
+    /// Builds the frame.
+    pub fn build(&self) -> Vec<u8> {
+        let mut data = self.payload.clone();
+        if let Some(max_len) = self.over_length {
+            // Pad with a byte that is never escaped, so every byte counts once
+            let pad = (0..=u8::MAX)
+                .find(|&b| !self.s_chars.translate.contains_key(&b))
+                .unwrap_or(0);
+            let fixed = self.fcs.size() + 2;
+            data.resize(data.len().max((max_len + 1).saturating_sub(fixed)), pad);
+        }
+        let payload_len = data.len();
+        self.fcs.append(&mut data);
+        if self.bad_fcs && data.len() > payload_len {
+            if let Some(last) = data.last_mut() {
+                *last ^= 0x01;
+            }
+        }
+
+        let mut frame = Vec::with_capacity(data.len() * 2 + 4);
+        if !self.no_opening {
+            frame.push(self.s_chars.fend);
+        }
+        let split = self
+            .stray_fesc
+            .map_or(data.len(), |index| index.min(data.len()));
+        // Writing into a Vec can't fail
+        let _ = escape_unchecked(&data[..split], &self.s_chars, &mut frame);
+        if self.stray_fesc.is_some() {
+            let stray = (0..=u8::MAX)
+                .find(|b| {
+                    self.s_chars.translate.decode(b).is_none()
+                        && *b != self.s_chars.fend
+                        && *b != self.s_chars.fesc
+                })
+                .unwrap_or(0);
+            frame.extend_from_slice(&[self.s_chars.fesc, stray]);
+        }
+        let _ = escape_unchecked(&data[split..], &self.s_chars, &mut frame);
+        if !self.no_closing {
+            frame.push(self.s_chars.fend);
+        }
+
+        frame
+    }
+}
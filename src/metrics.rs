@@ -0,0 +1,434 @@
+//! Counters of link activity for monitoring
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Largest number of bucket bounds of a size histogram
+pub const MAX_HISTOGRAM_BOUNDS: usize = 64;
+
+/// Number of slots the throughput window is split into, the window rolls one slot at a time
+const THROUGHPUT_SLOTS: u64 = 10;
+
+/// Counters of a link, updated in place by [`Link`](crate::Link) and [`Arq`](crate::Arq) or
+/// through the `record_*` methods, and read as a whole by [`LinkMetrics::snapshot`].
+///
+/// Every counter is a relaxed atomic, so recording is cheap and a dashboard thread can poll a
+/// shared `Arc<LinkMetrics>` while the link is busy.
+///
+/// # Example
+/// ```rust
+/// use hdlc::LinkMetrics;
+///
+/// let metrics = LinkMetrics::new();
+/// metrics.record_sent(8, 10);
+/// metrics.record_received(4, 8);
+/// metrics.record_crc_failure();
+///
+/// let snapshot = metrics.snapshot();
+/// assert_eq!(snapshot.frames_sent, 1);
+/// assert_eq!(snapshot.wire_bytes_received, 8);
+/// assert_eq!(snapshot.crc_failures, 1);
+/// assert_eq!(snapshot.overhead_ratio(), 1.5);
+/// ```
+#[derive(Debug, Default)]
+pub struct LinkMetrics {
+    /// Frames written
+    frames_sent: AtomicU64,
+
+    /// Frames read and decoded
+    frames_received: AtomicU64,
+
+    /// Payload bytes of the frames written
+    payload_bytes_sent: AtomicU64,
+
+    /// Payload bytes of the frames read
+    payload_bytes_received: AtomicU64,
+
+    /// Encoded bytes written, flags and escapes included
+    wire_bytes_sent: AtomicU64,
+
+    /// Encoded bytes of the frames read, flags and escapes included
+    wire_bytes_received: AtomicU64,
+
+    /// Frames failing their frame check sequence
+    crc_failures: AtomicU64,
+
+    /// Malformed frames skipped to the next flag
+    resyncs: AtomicU64,
+
+    /// Frames sent again for lack of acknowledgement
+    retransmissions: AtomicU64,
+
+    /// Frame sizes, if enabled
+    histogram: Option<Histogram>,
+
+    /// Rolling byte rates, if enabled
+    throughput: Option<Throughput>,
+}
+
+/// Bytes on the wire over a rolling window
+#[derive(Debug)]
+struct Throughput {
+    /// Length of the window
+    window: Duration,
+
+    /// Bytes per second the line carries in each direction, if known
+    line_rate: Option<u64>,
+
+    /// Start of slot 0
+    start: Instant,
+
+    /// Index, bytes received and bytes sent of the slots in the window, oldest first
+    slots: Mutex<VecDeque<(u64, u64, u64)>>,
+}
+
+impl Throughput {
+    /// Returns the length of a slot, never zero
+    fn slot_len(&self) -> Duration {
+        (self.window / THROUGHPUT_SLOTS as u32).max(Duration::from_micros(1))
+    }
+
+    /// Returns the index of the slot `now` falls into
+    fn slot(&self, now: Instant) -> u64 {
+        (now.duration_since(self.start).as_nanos() / self.slot_len().as_nanos()) as u64
+    }
+
+    /// Locks the slots and drops those that left the window by `now`
+    fn slots(&self, now: Instant) -> MutexGuard<'_, VecDeque<(u64, u64, u64)>> {
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let oldest = (self.slot(now) + 1).saturating_sub(THROUGHPUT_SLOTS);
+        while slots.front().is_some_and(|&(index, _, _)| index < oldest) {
+            slots.pop_front();
+        }
+        slots
+    }
+
+    /// Adds bytes received and sent at `now`
+    fn record(&self, received: usize, sent: usize, now: Instant) {
+        let index = self.slot(now);
+        let mut slots = self.slots(now);
+        match slots.back_mut() {
+            Some(slot) if slot.0 == index => {
+                slot.1 += received as u64;
+                slot.2 += sent as u64;
+            }
+            _ => slots.push_back((index, received as u64, sent as u64)),
+        }
+    }
+}
+
+/// Byte rates of a [`LinkMetrics`] over its rolling window, see
+/// [`LinkMetrics::with_throughput`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThroughputSnapshot {
+    /// Encoded bytes received per second
+    pub bytes_in_per_sec: f64,
+
+    /// Encoded bytes sent per second
+    pub bytes_out_per_sec: f64,
+
+    /// Share of the line rate used by received bytes, from 0.0 to 1.0, if the line rate is known
+    pub utilization_in: Option<f64>,
+
+    /// Share of the line rate used by sent bytes, from 0.0 to 1.0, if the line rate is known
+    pub utilization_out: Option<f64>,
+}
+
+/// Buckets of frame sizes
+#[derive(Debug)]
+struct Histogram {
+    /// Largest size counted by every bucket but the last, ascending
+    bounds: Vec<usize>,
+
+    /// Frames written per bucket, one more than `bounds`
+    sent: Vec<AtomicU64>,
+
+    /// Frames read per bucket, one more than `bounds`
+    received: Vec<AtomicU64>,
+}
+
+impl Histogram {
+    /// Counts a frame of `len` bytes into `buckets`
+    fn record(&self, buckets: &[AtomicU64], len: usize) {
+        let index = self.bounds.partition_point(|&bound| bound < len);
+        buckets[index].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Frame sizes counted by a [`LinkMetrics`], see [`LinkMetrics::with_size_histogram`].
+///
+/// Bucket `i` counts frames of up to `bounds[i]` encoded bytes and more than `bounds[i - 1]`,
+/// and the last bucket, one past the bounds, counts longer frames.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SizeHistogram {
+    /// Largest size counted by every bucket but the last, ascending
+    pub bounds: Vec<usize>,
+
+    /// Frames written per bucket
+    pub sent: Vec<u64>,
+
+    /// Frames read per bucket
+    pub received: Vec<u64>,
+}
+
+/// The counters of a [`LinkMetrics`] at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetricsSnapshot {
+    /// Frames written
+    pub frames_sent: u64,
+
+    /// Frames read and decoded
+    pub frames_received: u64,
+
+    /// Payload bytes of the frames written
+    pub payload_bytes_sent: u64,
+
+    /// Payload bytes of the frames read
+    pub payload_bytes_received: u64,
+
+    /// Encoded bytes written, flags and escapes included
+    pub wire_bytes_sent: u64,
+
+    /// Encoded bytes of the frames read, flags and escapes included
+    pub wire_bytes_received: u64,
+
+    /// Frames failing their frame check sequence
+    pub crc_failures: u64,
+
+    /// Malformed frames skipped to the next flag
+    pub resyncs: u64,
+
+    /// Frames sent again for lack of acknowledgement
+    pub retransmissions: u64,
+}
+
+impl LinkMetrics {
+    /// Creates a new LinkMetrics instance with every counter at 0.
+    pub fn new() -> Self {
+        LinkMetrics::default()
+    }
+
+    /// Also counts the sizes of the frames sent and received, in encoded bytes, into buckets
+    /// bounded by `bounds`. Bounds are sorted, and only the lowest [`MAX_HISTOGRAM_BOUNDS`]
+    /// distinct ones kept.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hdlc::LinkMetrics;
+    ///
+    /// let metrics = LinkMetrics::new().with_size_histogram(&[16, 64, 256]);
+    /// metrics.record_sent(10, 12);
+    /// metrics.record_sent(60, 64);
+    /// metrics.record_received(400, 410);
+    ///
+    /// let histogram = metrics.histogram().unwrap();
+    /// assert_eq!(histogram.sent, vec![1, 1, 0, 0]);
+    /// assert_eq!(histogram.received, vec![0, 0, 0, 1]);
+    /// ```
+    pub fn with_size_histogram(mut self, bounds: &[usize]) -> Self {
+        let mut bounds = bounds.to_vec();
+        bounds.sort_unstable();
+        bounds.dedup();
+        bounds.truncate(MAX_HISTOGRAM_BOUNDS);
+        let buckets = || (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect();
+
+        self.histogram = Some(Histogram {
+            sent: buckets(),
+            received: buckets(),
+            bounds,
+        });
+        self
+    }
+
+    /// Also tracks the encoded bytes sent and received per second over the last `window`, and
+    /// the share of `line_rate` they take up if given, in bytes per second per direction. A
+    /// serial line of 115200 baud with 8N1 framing carries 11520 bytes per second.
+    ///
+    /// The window rolls in steps of a tenth of its length.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use hdlc::LinkMetrics;
+    ///
+    /// let metrics = LinkMetrics::new().with_throughput(Duration::from_secs(1), Some(11520));
+    /// metrics.record_sent(100, 104);
+    ///
+    /// let throughput = metrics.throughput().unwrap();
+    /// assert!(throughput.bytes_out_per_sec >= 104.0);
+    /// assert_eq!(throughput.bytes_in_per_sec, 0.0);
+    /// assert_eq!(throughput.utilization_in, Some(0.0));
+    /// ```
+    pub fn with_throughput(mut self, window: Duration, line_rate: Option<u64>) -> Self {
+        self.throughput = Some(Throughput {
+            window,
+            line_rate,
+            start: Instant::now(),
+            slots: Mutex::new(VecDeque::new()),
+        });
+        self
+    }
+
+    /// Counts a frame written, carrying `payload_len` payload bytes in `wire_len` encoded bytes.
+    pub fn record_sent(&self, payload_len: usize, wire_len: usize) {
+        self.frames_sent.fetch_add(1, Ordering::Relaxed);
+        add(&self.payload_bytes_sent, payload_len);
+        add(&self.wire_bytes_sent, wire_len);
+        if let Some(histogram) = &self.histogram {
+            histogram.record(&histogram.sent, wire_len);
+        }
+        if let Some(throughput) = &self.throughput {
+            throughput.record(0, wire_len, Instant::now());
+        }
+    }
+
+    /// Counts a frame read, carrying `payload_len` payload bytes in `wire_len` encoded bytes.
+    pub fn record_received(&self, payload_len: usize, wire_len: usize) {
+        self.frames_received.fetch_add(1, Ordering::Relaxed);
+        add(&self.payload_bytes_received, payload_len);
+        add(&self.wire_bytes_received, wire_len);
+        if let Some(histogram) = &self.histogram {
+            histogram.record(&histogram.received, wire_len);
+        }
+        if let Some(throughput) = &self.throughput {
+            throughput.record(wire_len, 0, Instant::now());
+        }
+    }
+
+    /// Counts a frame failing its frame check sequence.
+    pub fn record_crc_failure(&self) {
+        self.crc_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a malformed frame skipped to the next flag.
+    pub fn record_resync(&self) {
+        self.resyncs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a frame sent again.
+    pub fn record_retransmission(&self) {
+        self.retransmissions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the current counters. Counters recorded meanwhile by other threads may be
+    /// partially included.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            frames_sent: self.frames_sent.load(Ordering::Relaxed),
+            frames_received: self.frames_received.load(Ordering::Relaxed),
+            payload_bytes_sent: self.payload_bytes_sent.load(Ordering::Relaxed),
+            payload_bytes_received: self.payload_bytes_received.load(Ordering::Relaxed),
+            wire_bytes_sent: self.wire_bytes_sent.load(Ordering::Relaxed),
+            wire_bytes_received: self.wire_bytes_received.load(Ordering::Relaxed),
+            crc_failures: self.crc_failures.load(Ordering::Relaxed),
+            resyncs: self.resyncs.load(Ordering::Relaxed),
+            retransmissions: self.retransmissions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the current frame sizes, None unless enabled by
+    /// [`LinkMetrics::with_size_histogram`].
+    pub fn histogram(&self) -> Option<SizeHistogram> {
+        let load = |buckets: &[AtomicU64]| {
+            buckets
+                .iter()
+                .map(|bucket| bucket.load(Ordering::Relaxed))
+                .collect()
+        };
+
+        self.histogram.as_ref().map(|histogram| SizeHistogram {
+            bounds: histogram.bounds.clone(),
+            sent: load(&histogram.sent),
+            received: load(&histogram.received),
+        })
+    }
+
+    /// Returns the current byte rates, None unless enabled by [`LinkMetrics::with_throughput`].
+    ///
+    /// Rates are averaged over the window, or over the time since the metrics were created if
+    /// that is shorter.
+    pub fn throughput(&self) -> Option<ThroughputSnapshot> {
+        let throughput = self.throughput.as_ref()?;
+        let now = Instant::now();
+        let (received, sent) = throughput
+            .slots(now)
+            .iter()
+            .fold((0, 0), |(received, sent), slot| {
+                (received + slot.1, sent + slot.2)
+            });
+
+        let span = now
+            .duration_since(throughput.start)
+            .clamp(
+                throughput.slot_len(),
+                throughput.window.max(throughput.slot_len()),
+            )
+            .as_secs_f64();
+        let bytes_in_per_sec = received as f64 / span;
+        let bytes_out_per_sec = sent as f64 / span;
+        let utilization = |rate: f64| {
+            throughput
+                .line_rate
+                .map(|line_rate| (rate / line_rate.max(1) as f64).min(1.0))
+        };
+
+        Some(ThroughputSnapshot {
+            bytes_in_per_sec,
+            bytes_out_per_sec,
+            utilization_in: utilization(bytes_in_per_sec),
+            utilization_out: utilization(bytes_out_per_sec),
+        })
+    }
+
+    /// Sets every counter and histogram bucket back to 0, and empties the throughput window.
+    pub fn reset(&self) {
+        if let Some(throughput) = &self.throughput {
+            throughput.slots(Instant::now()).clear();
+        }
+        let buckets = self
+            .histogram
+            .iter()
+            .flat_map(|histogram| histogram.sent.iter().chain(&histogram.received));
+        for counter in [
+            &self.frames_sent,
+            &self.frames_received,
+            &self.payload_bytes_sent,
+            &self.payload_bytes_received,
+            &self.wire_bytes_sent,
+            &self.wire_bytes_received,
+            &self.crc_failures,
+            &self.resyncs,
+            &self.retransmissions,
+        ]
+        .into_iter()
+        .chain(buckets)
+        {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+impl MetricsSnapshot {
+    /// Returns the wire bytes per payload byte in both directions, 1.0 for no framing overhead
+    /// at all. 0.0 until a payload byte passed.
+    pub fn overhead_ratio(&self) -> f64 {
+        let payload = self.payload_bytes_sent + self.payload_bytes_received;
+        let wire = self.wire_bytes_sent + self.wire_bytes_received;
+        if payload == 0 {
+            0.0
+        } else {
+            wire as f64 / payload as f64
+        }
+    }
+}
+
+/// Adds a byte count to a counter
+fn add(counter: &AtomicU64, len: usize) {
+    counter.fetch_add(len as u64, Ordering::Relaxed);
+}
@@ -0,0 +1,178 @@
+//! Virtual channels sharing one link, told apart by a port nibble
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{decode_ref, encode_ref, HDLCError, SpecialChars};
+
+/// Number of ports, carried in the upper nibble of the header
+pub const MUX_PORTS: u8 = 16;
+
+/// State shared by a [`Mux`] and its channels
+#[derive(Debug, Default)]
+struct Shared {
+    /// Encoded frames of every channel, in the order they were sent
+    outgoing: VecDeque<Vec<u8>>,
+
+    /// Received payloads, indexed by port
+    incoming: [VecDeque<Vec<u8>>; MUX_PORTS as usize],
+
+    /// Set for every port a channel was opened on
+    open: [bool; MUX_PORTS as usize],
+}
+
+/// Multiplexes up to 16 logical streams over one link, each frame starting with a header byte
+/// holding its port in the upper nibble, like the type byte of KISS. The lower nibble is sent
+/// as 0 and frames received with another value there are not data, and dropped.
+///
+/// Every stream sends and receives through its own [`Channel`] handle. The multiplexer does no
+/// I/O: the link takes the encoded frames of every channel from [`Mux::poll_transmit`] and hands
+/// received frames to [`Mux::receive`], which routes them to their channel.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{Mux, SpecialChars};
+///
+/// let mut local = Mux::new(SpecialChars::default()).unwrap();
+/// let mut remote = Mux::new(SpecialChars::default()).unwrap();
+/// let telemetry = local.channel(1).unwrap();
+/// let remote_telemetry = remote.channel(1).unwrap();
+///
+/// telemetry.send(&[0x01, 0x02]).unwrap();
+/// let frame = local.poll_transmit().unwrap();
+/// assert_eq!(frame, vec![0x7E, 0x10, 0x01, 0x02, 0x7E]);
+///
+/// assert_eq!(remote.receive(&frame), Ok(Some(1)));
+/// assert_eq!(remote_telemetry.recv(), Some(vec![0x01, 0x02]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Mux {
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// Queues shared with the channels
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl Mux {
+    /// Creates a new Mux instance without open channels.
+    ///
+    /// # Arguments
+    /// * `s_char` - The special characters used for HDLC encoding.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.
+    pub fn new(s_char: SpecialChars) -> Result<Self, HDLCError> {
+        // Safety check to make sure the special character values are all unique
+        if s_char.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+
+        Ok(Mux {
+            s_char,
+            shared: Arc::new(Mutex::new(Shared::default())),
+        })
+    }
+
+    /// Opens the channel on `port`, or returns another handle to it if it is open already.
+    ///
+    /// # Returns
+    /// * `Option<Channel>` - The channel, or None if `port` isn't below [`MUX_PORTS`].
+    pub fn channel(&self, port: u8) -> Option<Channel> {
+        if port >= MUX_PORTS {
+            return None;
+        }
+        self.lock().open[usize::from(port)] = true;
+
+        Some(Channel {
+            port,
+            s_char: self.s_char.clone(),
+            shared: Arc::clone(&self.shared),
+        })
+    }
+
+    /// Removes the next encoded frame any channel sent.
+    pub fn poll_transmit(&mut self) -> Option<Vec<u8>> {
+        self.lock().outgoing.pop_front()
+    }
+
+    /// Decodes a received frame and hands its payload to the channel of its port.
+    ///
+    /// # Returns
+    /// * `Result<Option<u8>>` - The port the payload was delivered to, or None if the frame
+    ///   isn't data, has no header, or no channel is open on its port, and it was dropped.
+    ///
+    /// # Error
+    ///
+    /// * Any error [`decode`] returns for the frame.
+    pub fn receive(&mut self, frame: &[u8]) -> Result<Option<u8>, HDLCError> {
+        let data = decode_ref(frame, &self.s_char)?;
+        let Some((&header, payload)) = data.split_first() else {
+            return Ok(None);
+        };
+        let port = header >> 4;
+
+        let mut shared = self.lock();
+        if header & 0x0F != 0 || !shared.open[usize::from(port)] {
+            return Ok(None);
+        }
+        shared.incoming[usize::from(port)].push_back(payload.to_vec());
+
+        Ok(Some(port))
+    }
+
+    /// Locks the shared queues, which stay consistent even if a holder panicked
+    fn lock(&self) -> MutexGuard<'_, Shared> {
+        self.shared.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Sending and receiving handle of one port of a [`Mux`], created by [`Mux::channel`].
+///
+/// Handles can be cloned and moved to other threads, every handle of a port shares its
+/// received payloads.
+#[derive(Debug, Clone)]
+pub struct Channel {
+    /// The port of the channel
+    port: u8,
+
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// Queues shared with the multiplexer
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl Channel {
+    /// Returns the port of the channel.
+    pub fn port(&self) -> u8 {
+        self.port
+    }
+
+    /// Encodes `payload` behind the header of the port and queues it for the link.
+    ///
+    /// # Error
+    ///
+    /// * Any error [`encode`] returns for the frame.
+    pub fn send(&self, payload: &[u8]) -> Result<(), HDLCError> {
+        let mut data = Vec::with_capacity(payload.len() + 1);
+        data.push(self.port << 4);
+        data.extend_from_slice(payload);
+        let frame = encode_ref(&data, &self.s_char)?;
+        self.lock().outgoing.push_back(frame);
+
+        Ok(())
+    }
+
+    /// Removes the next payload received on the port.
+    pub fn recv(&self) -> Option<Vec<u8>> {
+        self.lock().incoming[usize::from(self.port)].pop_front()
+    }
+
+    /// Locks the shared queues, which stay consistent even if a holder panicked
+    fn lock(&self) -> MutexGuard<'_, Shared> {
+        self.shared.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,174 @@
+//! Options tuning how strictly frames are decoded
+
+use crate::FcsMode;
+
+/// How frames are decoded by [`decode_with`](crate::decode_with), a
+/// [`FrameReader`](crate::FrameReader) or a [`Decoder`](crate::Decoder).
+///
+/// `DecodeOptions::default()`, the same as [`DecodeOptions::strict`], decodes exactly like
+/// [`decode`](crate::decode). [`DecodeOptions::tolerant`] accepts the idle flags and stray
+/// bytes found around frames on noisy links.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{decode_with, DecodeOptions, FcsMode, SpecialChars};
+///
+/// let options = DecodeOptions {
+///     fcs: FcsMode::Crc16,
+///     max_len: Some(64),
+///     ..DecodeOptions::tolerant()
+/// };
+/// let input = [0x00, 0x7E, 0x01, 0xF1, 0xE1, 0x7E, 0xFF];
+///
+/// assert_eq!(decode_with(&input, SpecialChars::default(), options), Ok(vec![0x01]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecodeOptions {
+    /// Requires the input to start with its opening `fend`. When unset, bytes in front of the
+    /// opening `fend` are skipped
+    pub strict: bool,
+
+    /// Accepts runs of `fend` characters in front of and after the frame, sent by transmitters
+    /// idling the line with flags. [`FrameReader`](crate::FrameReader) and
+    /// [`Decoder`](crate::Decoder) always treat the flags between frames as fill
+    pub fend_fill: bool,
+
+    /// Accepts bytes after the closing `fend` instead of reporting `HDLCError::FendCharInData`
+    pub trailing_garbage: bool,
+
+    /// Splits a stream so back-to-back `fend` characters are an empty frame, such as a
+    /// keepalive, and hands out frames without payload. When unset, back-to-back `fend`
+    /// characters close one frame and open the next, and frames without payload are skipped
+    pub empty_frames: bool,
+
+    /// Lets the closing `fend` of a frame also open the next frame, as sent with
+    /// [`EncodeOptions::shared_flags`]. Bytes between two frames are then a frame of their own
+    /// instead of being skipped
+    pub shared_flags: bool,
+
+    /// Where the `fend` characters sit around a frame
+    pub framing: Framing,
+
+    /// The frame check sequence following the payload, verified and removed when decoding
+    pub fcs: FcsMode,
+
+    /// Longest frame accepted, `fend` characters included. Unlimited when unset
+    pub max_len: Option<usize>,
+
+    /// Checks the ISO 13239 [`FrameFormat`](crate::FrameFormat) field opening the payload
+    /// against the length of the frame before the FCS, so truncated frames are told apart from
+    /// corrupted ones. The field is kept in the decoded payload
+    pub frame_format: bool,
+
+    /// Verifies the header check sequence following the frame format field, addresses and
+    /// control field of frames with an information field, before the FCS, so a corrupted header
+    /// is told apart from a corrupted information field. The HCS is computed like `fcs` and
+    /// kept in the decoded payload
+    pub hcs: bool,
+}
+
+impl DecodeOptions {
+    /// Options rejecting anything but a single well-formed frame, like [`decode`](crate::decode).
+    pub const fn strict() -> Self {
+        DecodeOptions {
+            strict: true,
+            fend_fill: false,
+            trailing_garbage: false,
+            empty_frames: false,
+            shared_flags: false,
+            framing: Framing::Flags,
+            fcs: FcsMode::None,
+            max_len: None,
+            frame_format: false,
+            hcs: false,
+        }
+    }
+
+    /// Options skipping the idle flags and other bytes around a frame.
+    pub const fn tolerant() -> Self {
+        DecodeOptions {
+            strict: false,
+            fend_fill: true,
+            trailing_garbage: true,
+            ..DecodeOptions::strict()
+        }
+    }
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        DecodeOptions::strict()
+    }
+}
+
+/// How frames are encoded by [`encode_with`](crate::encode_with) and
+/// [`encode_frames`](crate::encode_frames).
+///
+/// # Example
+/// ```rust
+/// use hdlc::{encode_frames, EncodeOptions, SpecialChars};
+///
+/// let options = EncodeOptions {
+///     shared_flags: true,
+///     ..EncodeOptions::default()
+/// };
+/// let burst = encode_frames(&[[0x01], [0x02]], SpecialChars::default(), options).unwrap();
+///
+/// assert_eq!(burst, vec![0x7E, 0x01, 0x7E, 0x02, 0x7E]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EncodeOptions {
+    /// Sends a single boundary between adjacent frames, closing one and opening the next.
+    /// Receivers need [`DecodeOptions::shared_flags`] to split such frames
+    pub shared_flags: bool,
+
+    /// Where the `fend` characters sit around a frame
+    pub framing: Framing,
+
+    /// Longest payload accepted, reported as `HDLCError::PayloadTooLarge` instead of sending a
+    /// frame the peer drops. Unlimited when unset
+    pub max_payload_len: Option<usize>,
+}
+
+/// Where the `fend` characters sit around a frame, set in [`EncodeOptions`] and
+/// [`DecodeOptions`] to match the quirks of other HDLC-like stacks.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{decode_with, encode_with, DecodeOptions, EncodeOptions, Framing, SpecialChars};
+///
+/// let chars = SpecialChars::default();
+/// let frame = encode_with(
+///     &[0x01, 0x7E],
+///     chars.clone(),
+///     EncodeOptions {
+///         framing: Framing::TerminatorOnly,
+///         ..EncodeOptions::default()
+///     },
+/// )
+/// .unwrap();
+/// assert_eq!(frame, vec![0x01, 0x7D, 0x5E, 0x7E]);
+///
+/// let options = DecodeOptions {
+///     framing: Framing::TerminatorOnly,
+///     ..DecodeOptions::default()
+/// };
+/// assert_eq!(decode_with(&frame, chars, options), Ok(vec![0x01, 0x7E]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Framing {
+    /// Frames are opened and closed by a `fend`
+    #[default]
+    Flags,
+
+    /// Frames are only closed by a `fend`, as sent by devices omitting the opening flag. The
+    /// bytes up to the first `fend` of a stream, and between two `fend` characters, are a frame
+    TerminatorOnly,
+
+    /// Frames are opened and closed by two `fend` characters. Decoding takes the second flag
+    /// as idle fill, as with `DecodeOptions::fend_fill`
+    DoubledFlags,
+}
@@ -0,0 +1,191 @@
+//! Destinations the encoders and decoders can write their bytes to
+
+use core::mem::MaybeUninit;
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use crate::HDLCError;
+
+/// A destination for encoded or decoded bytes, see [`encode_into`](crate::encode_into) and
+/// [`decode_into`](crate::decode_into).
+///
+/// Implement it for a ring buffer or a fixed capacity vector to have frames land there directly.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{encode_into, HDLCError, Output, SpecialChars};
+///
+/// /// Keeps a running checksum instead of the bytes
+/// struct Checksum(u8);
+///
+/// impl Output for Checksum {
+///     fn push(&mut self, byte: u8) -> Result<(), HDLCError> {
+///         self.0 = self.0.wrapping_add(byte);
+///         Ok(())
+///     }
+/// }
+///
+/// let mut sum = Checksum(0);
+/// encode_into(&[0x01, 0x02], SpecialChars::default(), &mut sum).unwrap();
+/// assert_eq!(sum.0, 0x7E + 0x01 + 0x02 + 0x7E);
+/// ```
+pub trait Output {
+    /// Appends one byte.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::BufferTooSmall**: The destination is full.
+    fn push(&mut self, byte: u8) -> Result<(), HDLCError>;
+
+    /// Appends a run of bytes. The default implementation pushes them one at a time.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::BufferTooSmall**: The destination is full. Part of the run may already have
+    ///   been appended.
+    fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), HDLCError> {
+        bytes.iter().try_for_each(|&byte| self.push(byte))
+    }
+}
+
+impl Output for Vec<u8> {
+    fn push(&mut self, byte: u8) -> Result<(), HDLCError> {
+        Vec::push(self, byte);
+        Ok(())
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), HDLCError> {
+        Vec::extend_from_slice(self, bytes);
+        Ok(())
+    }
+}
+
+/// Appends to the back of the ring, so frames can be queued for a transmit path draining the
+/// front.
+#[cfg(feature = "std")]
+impl Output for VecDeque<u8> {
+    fn push(&mut self, byte: u8) -> Result<(), HDLCError> {
+        self.push_back(byte);
+        Ok(())
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), HDLCError> {
+        self.extend(bytes);
+        Ok(())
+    }
+}
+
+/// An [`Output`] filling a caller provided buffer from the start.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{Output, SliceOutput};
+///
+/// let mut buf = [0u8; 2];
+/// let mut out = SliceOutput::new(&mut buf);
+///
+/// out.push(0x01).unwrap();
+/// assert_eq!(out.as_slice(), &[0x01]);
+/// ```
+#[derive(Debug)]
+pub struct SliceOutput<'a> {
+    /// The buffer being filled
+    buf: &'a mut [u8],
+
+    /// Number of bytes written to the start of `buf`
+    len: usize,
+}
+
+impl<'a> SliceOutput<'a> {
+    /// Creates a new SliceOutput instance writing to the start of `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        SliceOutput { buf, len: 0 }
+    }
+
+    /// Returns the number of bytes written.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if nothing was written yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bytes written so far.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl Output for SliceOutput<'_> {
+    fn push(&mut self, byte: u8) -> Result<(), HDLCError> {
+        *self
+            .buf
+            .get_mut(self.len)
+            .ok_or(HDLCError::BufferTooSmall)? = byte;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), HDLCError> {
+        let end = self.len + bytes.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(HDLCError::BufferTooSmall)?
+            .copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// An [`Output`] filling a possibly uninitialized buffer from the start
+pub(crate) struct UninitOutput<'a> {
+    /// The buffer being filled
+    buf: &'a mut [MaybeUninit<u8>],
+
+    /// Number of bytes written to the start of `buf`, all of them initialized
+    len: usize,
+}
+
+impl<'a> UninitOutput<'a> {
+    /// Writes to the start of `buf`
+    pub(crate) fn new(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        UninitOutput { buf, len: 0 }
+    }
+
+    /// Returns the bytes written, which are the only ones known to be initialized
+    pub(crate) fn into_init(self) -> &'a [u8] {
+        let written = &self.buf[..self.len];
+        // Every byte up to `len` was written by `push` or `extend_from_slice`
+        unsafe { core::slice::from_raw_parts(written.as_ptr().cast::<u8>(), written.len()) }
+    }
+}
+
+impl Output for UninitOutput<'_> {
+    fn push(&mut self, byte: u8) -> Result<(), HDLCError> {
+        self.buf
+            .get_mut(self.len)
+            .ok_or(HDLCError::BufferTooSmall)?
+            .write(byte);
+        self.len += 1;
+        Ok(())
+    }
+}
+
+/// An [`Output`] writing straight to a writer, its errors reported as `HDLCError::Io`
+#[cfg(feature = "std")]
+pub(crate) struct WriteOutput<'a, W: ?Sized>(pub(crate) &'a mut W);
+
+#[cfg(feature = "std")]
+impl<W: Write + ?Sized> Output for WriteOutput<'_, W> {
+    fn push(&mut self, byte: u8) -> Result<(), HDLCError> {
+        Ok(self.0.write_all(&[byte])?)
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), HDLCError> {
+        Ok(self.0.write_all(bytes)?)
+    }
+}
@@ -0,0 +1,98 @@
+//! Inter-frame gaps on the transmit side
+
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// What separates consecutive frames on the wire, set on an
+/// [`EncodingWriter`](crate::EncodingWriter) or a [`Link`](crate::Link) for half-duplex radios
+/// and modems needing a pause between frames.
+///
+/// The first frame is sent right away, pacing only holds back the frames following it.
+///
+/// # Example
+/// ```rust
+/// use std::io::Write;
+///
+/// use hdlc::{EncodingWriter, Pacing, SpecialChars};
+///
+/// let mut writer = EncodingWriter::new(Vec::new(), SpecialChars::default())
+///     .unwrap()
+///     .pacing(Pacing::IdleFlags(2));
+/// writer.write_all(&[0x01]).unwrap();
+/// writer.finish().unwrap();
+/// writer.write_all(&[0x02]).unwrap();
+/// writer.finish().unwrap();
+///
+/// assert_eq!(
+///     writer.into_inner(),
+///     vec![0x7E, 0x01, 0x7E, 0x7E, 0x7E, 0x7E, 0x02, 0x7E]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pacing {
+    /// Frames are sent back to back
+    #[default]
+    None,
+
+    /// The next frame is held back until this long after the previous one was finished,
+    /// blocking the sending thread
+    Gap(Duration),
+
+    /// This many idle `fend` characters are sent in front of the next frame. Receivers take
+    /// them as fill between frames
+    IdleFlags(usize),
+}
+
+/// Number of idle flags written per call to the writer
+const IDLE_CHUNK: usize = 64;
+
+/// Applies a [`Pacing`] to the frames written to one writer
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Pacer {
+    /// How frames are separated
+    pacing: Pacing,
+
+    /// When the previous frame was finished, None before the first one
+    last: Option<Instant>,
+}
+
+impl Pacer {
+    /// Creates a pacer separating frames by `pacing`
+    pub(crate) fn new(pacing: Pacing) -> Self {
+        Pacer { pacing, last: None }
+    }
+
+    /// Waits or writes idle flags as needed before the next frame is opened
+    pub(crate) fn before_frame(&mut self, writer: &mut impl Write, fend: u8) -> io::Result<()> {
+        let Some(last) = self.last else {
+            return Ok(());
+        };
+        match self.pacing {
+            Pacing::None => {}
+            Pacing::Gap(gap) => {
+                if let Some(wait) = gap.checked_sub(last.elapsed()) {
+                    thread::sleep(wait);
+                }
+            }
+            Pacing::IdleFlags(count) => {
+                // Written in chunks from the stack, so no frame allocates
+                let flags = [fend; IDLE_CHUNK];
+                let mut left = count;
+                while left > 0 {
+                    let chunk = left.min(IDLE_CHUNK);
+                    writer.write_all(&flags[..chunk])?;
+                    left -= chunk;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Takes note that a frame was finished
+    pub(crate) fn after_frame(&mut self) {
+        self.last = Some(Instant::now());
+    }
+}
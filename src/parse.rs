@@ -0,0 +1,62 @@
+//! Streaming parser in the style of parser combinator libraries
+
+use thiserror::Error;
+
+use crate::{decode_first, HDLCError, SpecialChars};
+
+/// Why [`parse_frame`] returned no frame.
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseError {
+    /// The input ends inside a frame, more bytes may complete it.
+    #[error("Incomplete frame, at least {0} more bytes are needed.")]
+    Needed(usize),
+    /// The frame is malformed, no further bytes can complete it.
+    #[error(transparent)]
+    Invalid(HDLCError),
+}
+
+/// Parses the frame at the start of `input`, returning the remaining input before the decoded
+/// payload, like the streaming parsers of `nom`.
+///
+/// A frame cut short by the end of the input is not an error of the frame, and returns
+/// `ParseError::Needed` with the least number of bytes that could complete it. Call again once
+/// more data was received.
+///
+/// # Inputs
+/// * **&[u8]**: A buffer starting with the frame you want to parse
+/// * **SpecialChars**: The special characters you want to swap
+///
+/// # Output
+///
+/// * **`Result<(&[u8], Vec<u8>)>`**: The bytes following the closing `fend`, and the decoded
+///   payload
+///
+/// # Error
+///
+/// * **ParseError::Needed**: The input ends before the closing `fend`.
+/// * **ParseError::Invalid**: Any other error [`decode_first`] returns.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{parse_frame, ParseError, SpecialChars};
+///
+/// let chars = SpecialChars::default();
+///
+/// assert_eq!(parse_frame(&[0x7E, 0x01, 0x7D], chars.clone()), Err(ParseError::Needed(2)));
+/// assert_eq!(
+///     parse_frame(&[0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E], chars),
+///     Ok((&[0x7E][..], vec![0x01, 0x7E]))
+/// );
+/// ```
+pub fn parse_frame(input: &[u8], s_chars: SpecialChars) -> Result<(&[u8], Vec<u8>), ParseError> {
+    if input.is_empty() {
+        // An empty frame is made of its two flags
+        return Err(ParseError::Needed(2));
+    }
+
+    match decode_first(input, s_chars) {
+        Ok((payload, rest)) => Ok((rest, payload)),
+        Err(HDLCError::Incomplete { needed }) => Err(ParseError::Needed(needed.unwrap_or(1))),
+        Err(error) => Err(ParseError::Invalid(error)),
+    }
+}
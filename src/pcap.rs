@@ -0,0 +1,536 @@
+//! pcap and pcapng capture files of frames, enabled by the `pcap` feature
+
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::TimedFrame;
+
+/// Link type of decoded PPP frames in HDLC-like framing, RFC 1662
+pub const LINKTYPE_PPP_HDLC: u16 = 50;
+
+/// Link type of decoded Cisco HDLC frames
+pub const LINKTYPE_C_HDLC: u16 = 104;
+
+/// First link type reserved for private use, for raw frames or other protocols. Wireshark
+/// dissects it as configured in its DLT_USER preferences.
+pub const LINKTYPE_USER0: u16 = 147;
+
+/// Block type of the section header block
+const SECTION_HEADER: u32 = 0x0A0D_0D0A;
+
+/// Block type of the interface description block
+const INTERFACE_DESCRIPTION: u32 = 1;
+
+/// Block type of the simple packet block
+const SIMPLE_PACKET: u32 = 3;
+
+/// Block type of the enhanced packet block
+const ENHANCED_PACKET: u32 = 6;
+
+/// Byte order magic of pcapng sections
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+/// Magic of pcap files with microsecond timestamps
+const PCAP_MICROS: u32 = 0xA1B2_C3D4;
+
+/// Magic of pcap files with nanosecond timestamps
+const PCAP_NANOS: u32 = 0xA1B2_3C4D;
+
+/// Interface option holding the timestamp resolution
+const IF_TSRESOL: u16 = 9;
+
+/// Largest block or record read, guarding against corrupt lengths
+const MAX_BLOCK_LEN: usize = 1 << 24;
+
+/// Writes frames into a pcapng capture, so they can be opened in Wireshark for protocol
+/// analysis.
+///
+/// The capture holds one interface of the given link type, and every frame is written with
+/// a timestamp in microseconds. Write decoded frames with a link type matching their protocol,
+/// or raw frames with [`LINKTYPE_USER0`].
+///
+/// # Example
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use hdlc::{PcapWriter, LINKTYPE_PPP_HDLC};
+///
+/// let mut capture = PcapWriter::new(Vec::new(), LINKTYPE_PPP_HDLC).unwrap();
+/// capture
+///     .write_frame(&[0xFF, 0x03, 0xC0, 0x21], UNIX_EPOCH + Duration::from_secs(1))
+///     .unwrap();
+///
+/// let file = capture.into_inner();
+/// assert_eq!(&file[..4], &[0x0A, 0x0D, 0x0D, 0x0A]);
+/// assert_eq!(file.len(), 28 + 20 + 36);
+/// ```
+#[derive(Debug)]
+pub struct PcapWriter<W> {
+    /// Sink of the capture file
+    writer: W,
+}
+
+impl<W: Write> PcapWriter<W> {
+    /// Creates a new PcapWriter instance, writing the section header and the interface
+    /// description of the capture.
+    ///
+    /// # Arguments
+    /// * `writer` - The sink of the capture file.
+    /// * `link_type` - The link type of the frames, like [`LINKTYPE_PPP_HDLC`].
+    ///
+    /// # Error
+    ///
+    /// * Any error returned by the writer.
+    pub fn new(mut writer: W, link_type: u16) -> io::Result<Self> {
+        let mut section = Vec::with_capacity(16);
+        // Byte order magic, version 1.0 and unknown section length
+        section.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        section.extend_from_slice(&1u16.to_le_bytes());
+        section.extend_from_slice(&0u16.to_le_bytes());
+        section.extend_from_slice(&(-1i64).to_le_bytes());
+        write_block(&mut writer, SECTION_HEADER, &section)?;
+
+        let mut interface = Vec::with_capacity(8);
+        // Link type, reserved, and no snapshot length limit
+        interface.extend_from_slice(&link_type.to_le_bytes());
+        interface.extend_from_slice(&0u16.to_le_bytes());
+        interface.extend_from_slice(&0u32.to_le_bytes());
+        write_block(&mut writer, INTERFACE_DESCRIPTION, &interface)?;
+
+        Ok(PcapWriter { writer })
+    }
+
+    /// Writes `frame` into the capture, received at `timestamp`.
+    ///
+    /// Timestamps before the Unix epoch are written as the epoch.
+    ///
+    /// # Error
+    ///
+    /// * Any error returned by the writer.
+    pub fn write_frame(&mut self, frame: &[u8], timestamp: SystemTime) -> io::Result<()> {
+        let micros = timestamp
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_micros() as u64);
+        let len = u32::try_from(frame.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame too long"))?;
+
+        let mut packet = Vec::with_capacity(20 + frame.len() + 3);
+        // Interface, timestamp, captured and original length
+        packet.extend_from_slice(&0u32.to_le_bytes());
+        packet.extend_from_slice(&((micros >> 32) as u32).to_le_bytes());
+        packet.extend_from_slice(&(micros as u32).to_le_bytes());
+        packet.extend_from_slice(&len.to_le_bytes());
+        packet.extend_from_slice(&len.to_le_bytes());
+        packet.extend_from_slice(frame);
+        write_block(&mut self.writer, ENHANCED_PACKET, &packet)
+    }
+
+    /// Writes `frame` into the capture, received now.
+    ///
+    /// # Error
+    ///
+    /// * Any error returned by the writer.
+    pub fn write_frame_now(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.write_frame(frame, SystemTime::now())
+    }
+
+    /// Flushes the writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns a reference to the writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Writes a block of `block_type` around `body`, padded to 32 bits
+fn write_block(writer: &mut impl Write, block_type: u32, body: &[u8]) -> io::Result<()> {
+    let padding = (4 - body.len() % 4) % 4;
+    let total = (12 + body.len() + padding) as u32;
+
+    writer.write_all(&block_type.to_le_bytes())?;
+    writer.write_all(&total.to_le_bytes())?;
+    writer.write_all(body)?;
+    writer.write_all(&[0; 3][..padding])?;
+    writer.write_all(&total.to_le_bytes())
+}
+
+/// Layout of the capture being read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// pcap, with the number of timestamp units per second
+    Pcap { units: u64 },
+
+    /// pcapng
+    PcapNg,
+}
+
+/// An interface of a pcapng section
+#[derive(Debug, Clone, Copy)]
+struct Interface {
+    /// Link type of the frames captured on it
+    link_type: u16,
+
+    /// Number of timestamp units per second
+    units: u64,
+}
+
+/// Reads the frames of a pcap or pcapng capture, like those written by [`PcapWriter`], as an
+/// iterator of frames along with their timestamps.
+///
+/// The format and byte order are detected from the start of the capture. Blocks other than
+/// packets are skipped, and packets of every interface are handed out alike, see
+/// [`PcapReader::link_type`].
+///
+/// # Example
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use hdlc::{PcapReader, PcapWriter, LINKTYPE_PPP_HDLC};
+///
+/// let mut capture = PcapWriter::new(Vec::new(), LINKTYPE_PPP_HDLC).unwrap();
+/// let timestamp = UNIX_EPOCH + Duration::from_secs(1);
+/// capture.write_frame(&[0xFF, 0x03, 0xC0, 0x21], timestamp).unwrap();
+/// let file = capture.into_inner();
+///
+/// let mut reader = PcapReader::new(file.as_slice()).unwrap();
+/// let frame = reader.next().unwrap().unwrap();
+/// assert_eq!(frame.frame, vec![0xFF, 0x03, 0xC0, 0x21]);
+/// assert_eq!(frame.received, timestamp);
+/// assert_eq!(reader.link_type(), Some(LINKTYPE_PPP_HDLC));
+/// assert!(reader.next().is_none());
+/// ```
+#[derive(Debug)]
+pub struct PcapReader<R> {
+    /// Source of the capture file
+    reader: R,
+
+    /// Layout of the capture
+    format: Format,
+
+    /// Whether the current section or file is big endian
+    big_endian: bool,
+
+    /// Interfaces of the current pcapng section, or the one link of a pcap file
+    interfaces: Vec<Interface>,
+
+    /// Link type of the frame handed out last
+    link_type: Option<u16>,
+
+    /// Set once the end of the capture or an error was reached
+    done: bool,
+}
+
+impl<R: Read> PcapReader<R> {
+    /// Creates a new PcapReader instance, reading the header of the capture.
+    ///
+    /// # Arguments
+    /// * `reader` - The source of the capture file, in pcap or pcapng format.
+    ///
+    /// # Error
+    ///
+    /// * **io::ErrorKind::InvalidData**: The capture is neither pcap nor pcapng.
+    /// * Any error returned by the reader.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+
+        let mut capture = PcapReader {
+            reader,
+            format: Format::PcapNg,
+            big_endian: false,
+            interfaces: Vec::new(),
+            link_type: None,
+            done: false,
+        };
+        if u32::from_le_bytes(magic) == SECTION_HEADER {
+            capture.read_section()?;
+            return Ok(capture);
+        }
+
+        let (big_endian, units) = match (u32::from_le_bytes(magic), u32::from_be_bytes(magic)) {
+            (PCAP_MICROS, _) => (false, 1_000_000),
+            (PCAP_NANOS, _) => (false, 1_000_000_000),
+            (_, PCAP_MICROS) => (true, 1_000_000),
+            (_, PCAP_NANOS) => (true, 1_000_000_000),
+            _ => return Err(invalid("not a pcap or pcapng capture")),
+        };
+        // Version, time zone, accuracy, snapshot length and link type
+        let mut header = [0; 20];
+        capture.reader.read_exact(&mut header)?;
+        capture.format = Format::Pcap { units };
+        capture.big_endian = big_endian;
+        capture.interfaces.push(Interface {
+            link_type: capture.u32_at(&header, 16) as u16,
+            units,
+        });
+        Ok(capture)
+    }
+
+    /// Returns the link type of the frame handed out last, or of the capture if it is a pcap
+    /// file. None before the first frame of a pcapng capture.
+    pub fn link_type(&self) -> Option<u16> {
+        self.link_type.or_else(|| match self.format {
+            Format::Pcap { .. } => self.interfaces.first().map(|i| i.link_type),
+            Format::PcapNg => None,
+        })
+    }
+
+    /// Hands every remaining frame to `send`, like `|frame| link.send(frame)` for a
+    /// [`Link`](crate::Link) or `|frame| writer.write_all(frame)` for an
+    /// [`EncodingWriter`](crate::EncodingWriter), returning the number of frames replayed.
+    ///
+    /// With `realtime` set, frames are handed out as far apart as they were captured, counted
+    /// from the first one. Otherwise they are handed out as fast as `send` takes them.
+    ///
+    /// # Error
+    ///
+    /// * Any error reading the capture, or returned by `send`.
+    pub fn replay<F>(self, realtime: bool, mut send: F) -> io::Result<usize>
+    where
+        F: FnMut(&[u8]) -> io::Result<()>,
+    {
+        let mut start: Option<(Instant, SystemTime)> = None;
+        let mut count = 0;
+        for frame in self {
+            let frame = frame?;
+            if realtime {
+                let (started, first) = *start.get_or_insert((Instant::now(), frame.received));
+                let offset = frame.received.duration_since(first).unwrap_or_default();
+                if let Some(wait) = offset.checked_sub(started.elapsed()) {
+                    std::thread::sleep(wait);
+                }
+            }
+            send(&frame.frame)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Returns the reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the next frame, None at the end of the capture
+    fn read_frame(&mut self) -> io::Result<Option<TimedFrame<SystemTime>>> {
+        match self.format {
+            Format::Pcap { units } => {
+                let Some(header) = self.read_head::<16>()? else {
+                    return Ok(None);
+                };
+                let ticks =
+                    u64::from(self.u32_at(&header, 0)) * units + u64::from(self.u32_at(&header, 4));
+                let len = self.u32_at(&header, 8) as usize;
+                if len > MAX_BLOCK_LEN {
+                    return Err(invalid("pcap record too long"));
+                }
+                let mut frame = vec![0; len];
+                self.reader.read_exact(&mut frame)?;
+                self.link_type = self.interfaces.first().map(|i| i.link_type);
+
+                Ok(Some(TimedFrame {
+                    frame,
+                    received: timestamp(ticks, units),
+                }))
+            }
+            Format::PcapNg => loop {
+                let Some(head) = self.read_head::<4>()? else {
+                    return Ok(None);
+                };
+                if u32::from_le_bytes(head) == SECTION_HEADER {
+                    self.read_section()?;
+                    continue;
+                }
+                let block_type = self.u32_at(&head, 0);
+                let body = self.read_body()?;
+                match block_type {
+                    INTERFACE_DESCRIPTION if body.len() >= 8 => {
+                        let mut units = 1_000_000;
+                        for (code, value) in self.options(&body[8..]) {
+                            if code == IF_TSRESOL && !value.is_empty() {
+                                units = resolution(value[0]);
+                            }
+                        }
+                        self.interfaces.push(Interface {
+                            link_type: self.u16_at(&body, 0),
+                            units,
+                        });
+                    }
+                    ENHANCED_PACKET if body.len() >= 20 => {
+                        let interface = self.interface(self.u32_at(&body, 0))?;
+                        let ticks = u64::from(self.u32_at(&body, 4)) << 32
+                            | u64::from(self.u32_at(&body, 8));
+                        let len = self.u32_at(&body, 12) as usize;
+                        let frame = body
+                            .get(20..20 + len)
+                            .ok_or_else(|| invalid("pcapng packet longer than its block"))?;
+                        self.link_type = Some(interface.link_type);
+
+                        return Ok(Some(TimedFrame {
+                            frame: frame.to_vec(),
+                            received: timestamp(ticks, interface.units),
+                        }));
+                    }
+                    SIMPLE_PACKET if body.len() >= 4 => {
+                        let interface = self.interface(0)?;
+                        let len = (self.u32_at(&body, 0) as usize).min(body.len() - 4);
+                        self.link_type = Some(interface.link_type);
+
+                        // Simple packets carry no timestamp
+                        return Ok(Some(TimedFrame {
+                            frame: body[4..4 + len].to_vec(),
+                            received: UNIX_EPOCH,
+                        }));
+                    }
+                    _ => {}
+                }
+            },
+        }
+    }
+
+    /// Reads the rest of a section header block, after its block type
+    fn read_section(&mut self) -> io::Result<()> {
+        let mut head = [0; 8];
+        self.reader.read_exact(&mut head)?;
+        self.big_endian = match u32::from_le_bytes([head[4], head[5], head[6], head[7]]) {
+            BYTE_ORDER_MAGIC => false,
+            magic if magic.swap_bytes() == BYTE_ORDER_MAGIC => true,
+            _ => return Err(invalid("bad pcapng byte order magic")),
+        };
+        // The length is read again along with the rest of the block
+        let total = self.u32_at(&head, 0) as usize;
+        if !(28..=MAX_BLOCK_LEN).contains(&total) || total & 3 != 0 {
+            return Err(invalid("bad pcapng block length"));
+        }
+        let mut rest = vec![0; total - 12];
+        self.reader.read_exact(&mut rest)?;
+        self.interfaces.clear();
+
+        Ok(())
+    }
+
+    /// Reads the length, body and trailing length of a block, returning the body
+    fn read_body(&mut self) -> io::Result<Vec<u8>> {
+        let mut head = [0; 4];
+        self.reader.read_exact(&mut head)?;
+        let total = self.u32_at(&head, 0) as usize;
+        if !(12..=MAX_BLOCK_LEN).contains(&total) || total & 3 != 0 {
+            return Err(invalid("bad pcapng block length"));
+        }
+        let mut body = vec![0; total - 8];
+        self.reader.read_exact(&mut body)?;
+        body.truncate(total - 12);
+
+        Ok(body)
+    }
+
+    /// Reads `N` bytes, None if the capture ends right before them
+    fn read_head<const N: usize>(&mut self) -> io::Result<Option<[u8; N]>> {
+        let mut head = [0; N];
+        let mut filled = 0;
+        while filled < N {
+            match self.reader.read(&mut head[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(len) => filled += len,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(Some(head))
+    }
+
+    /// Returns the interface of a packet
+    fn interface(&self, id: u32) -> io::Result<Interface> {
+        self.interfaces
+            .get(id as usize)
+            .copied()
+            .ok_or_else(|| invalid("pcapng packet of an undescribed interface"))
+    }
+
+    /// Returns the code and value of every option in `data`
+    fn options<'d>(&self, mut data: &'d [u8]) -> Vec<(u16, &'d [u8])> {
+        let mut options = Vec::new();
+        while data.len() >= 4 {
+            let code = self.u16_at(data, 0);
+            let len = usize::from(self.u16_at(data, 2));
+            if code == 0 || data.len() < 4 + len {
+                break;
+            }
+            options.push((code, &data[4..4 + len]));
+            data = &data[(4 + len + 3) & !3..];
+        }
+
+        options
+    }
+
+    /// Reads a u16 at `offset` in the byte order of the capture
+    fn u16_at(&self, data: &[u8], offset: usize) -> u16 {
+        let bytes = [data[offset], data[offset + 1]];
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    /// Reads a u32 at `offset` in the byte order of the capture
+    fn u32_at(&self, data: &[u8], offset: usize) -> u32 {
+        let bytes = [
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ];
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+    type Item = io::Result<TimedFrame<SystemTime>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let frame = self.read_frame().transpose();
+        self.done = !matches!(frame, Some(Ok(_)));
+        frame
+    }
+}
+
+/// Returns the number of timestamp units per second of an `if_tsresol` value
+fn resolution(value: u8) -> u64 {
+    let exponent = u32::from(value & 0x7F).min(63);
+    if value & 0x80 != 0 {
+        1 << exponent
+    } else {
+        10u64.checked_pow(exponent).unwrap_or(u64::MAX)
+    }
+}
+
+/// Returns the time `ticks` units of which `units` make a second after the Unix epoch
+fn timestamp(ticks: u64, units: u64) -> SystemTime {
+    let units = units.max(1);
+    let nanos = u128::from(ticks % units) * 1_000_000_000 / u128::from(units);
+    UNIX_EPOCH + Duration::new(ticks / units, nanos as u32)
+}
+
+/// Returns an `InvalidData` error
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
@@ -0,0 +1,133 @@
+//! Pool of reusable buffers for decoded and encoded frames
+
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+
+use crate::{decode_to_vec, encode_to_vec, HDLCError, SpecialChars};
+
+/// A pool of byte buffers that return to it when dropped.
+///
+/// Relays handling thousands of frames a second decode every frame into a [`PooledBuffer`]
+/// instead of a new `Vec`, so once the pool is warm no frame allocates. Clones share the same
+/// pool and can be handed to other threads.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{BufferPool, SpecialChars};
+///
+/// let pool = BufferPool::new(8);
+///
+/// let frame = pool.decode(&[0x7E, 0x01, 0x7D, 0x5E, 0x7E], SpecialChars::default()).unwrap();
+/// assert_eq!(&frame[..], &[0x01, 0x7E]);
+///
+/// drop(frame);
+/// assert_eq!(pool.idle(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BufferPool {
+    /// Buffers waiting to be handed out again
+    free: Arc<Mutex<Vec<Vec<u8>>>>,
+
+    /// Most buffers kept around, further returned buffers are freed
+    max_idle: usize,
+}
+
+impl BufferPool {
+    /// Creates a new, empty BufferPool instance keeping at most `max_idle` buffers around.
+    pub fn new(max_idle: usize) -> Self {
+        BufferPool {
+            free: Arc::new(Mutex::new(Vec::new())),
+            max_idle,
+        }
+    }
+
+    /// Hands out an empty buffer, reusing an idle one if there is any.
+    pub fn get(&self) -> PooledBuffer {
+        let buf = self.lock().pop().unwrap_or_default();
+        PooledBuffer {
+            buf,
+            pool: self.clone(),
+        }
+    }
+
+    /// Returns the number of idle buffers in the pool.
+    pub fn idle(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Decodes `input` into a buffer from the pool, see [`decode`](crate::decode).
+    ///
+    /// # Error
+    ///
+    /// * Any error [`decode`](crate::decode) returns. The buffer goes back to the pool.
+    pub fn decode(&self, input: &[u8], s_chars: SpecialChars) -> Result<PooledBuffer, HDLCError> {
+        let mut buf = self.get();
+        decode_to_vec(input, s_chars, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Encodes `data` into a buffer from the pool, see [`encode`](crate::encode).
+    ///
+    /// # Error
+    ///
+    /// * Any error [`encode`](crate::encode) returns. The buffer goes back to the pool.
+    pub fn encode(&self, data: &[u8], s_chars: SpecialChars) -> Result<PooledBuffer, HDLCError> {
+        let mut buf = self.get();
+        encode_to_vec(data, s_chars, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Takes back a buffer unless the pool is full
+    fn put(&self, mut buf: Vec<u8>) {
+        let mut free = self.lock();
+        if free.len() < self.max_idle {
+            buf.clear();
+            free.push(buf);
+        }
+    }
+
+    /// Locks the idle buffers, a panic while holding the lock leaves them usable
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Vec<u8>>> {
+        self.free.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A buffer handed out by a [`BufferPool`], returned to it when dropped.
+#[derive(Debug)]
+pub struct PooledBuffer {
+    /// The bytes held
+    buf: Vec<u8>,
+
+    /// The pool the buffer goes back to
+    pool: BufferPool,
+}
+
+impl PooledBuffer {
+    /// Takes the bytes out of the pool for good.
+    pub fn into_inner(mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+impl Deref for PooledBuffer {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.buf
+    }
+}
+
+impl DerefMut for PooledBuffer {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buf
+    }
+}
+
+impl Drop for PooledBuffer {
+    fn drop(&mut self) {
+        // A buffer taken out with into_inner leaves an empty Vec behind, not worth keeping
+        if self.buf.capacity() > 0 {
+            self.pool.put(std::mem::take(&mut self.buf));
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Transmit queue draining frames by priority class
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+use crate::{encode_ref, HDLCError, SpecialChars};
+
+/// A transmit queue in front of a writer, sending frames of higher priority classes first.
+///
+/// Every frame is enqueued with a class, higher classes going out first and frames of the same
+/// class in order. A frame is always written out whole before the next one is picked, so a
+/// control frame waits at most for the frame already on the wire instead of a whole bulk
+/// transfer.
+///
+/// With [`PrioritySink::starvation_limit`] set, the oldest frame of a lower class goes out once
+/// that many frames were sent ahead of it, so bulk traffic keeps moving under a steady stream of
+/// high priority frames.
+///
+/// Like [`FrameSink`](crate::FrameSink), the writer may be non-blocking: a `WouldBlock` error
+/// just leaves the data queued until [`PrioritySink::writable`] is called again.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{PrioritySink, SpecialChars};
+///
+/// let mut sink = PrioritySink::new(Vec::new(), SpecialChars::default()).unwrap();
+///
+/// sink.enqueue(&[0x01], 0).unwrap();
+/// sink.enqueue(&[0x02], 0).unwrap();
+/// sink.enqueue(&[0x03], 7).unwrap();
+/// sink.writable().unwrap();
+///
+/// assert_eq!(
+///     sink.get_ref(),
+///     &vec![0x7E, 0x03, 0x7E, 0x7E, 0x01, 0x7E, 0x7E, 0x02, 0x7E]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct PrioritySink<W> {
+    /// Sink for the encoded frames
+    writer: W,
+
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// Encoded frames waiting to be written, indexed by class
+    classes: Vec<VecDeque<Vec<u8>>>,
+
+    /// Number of frames sent ahead of the oldest frame of every class, indexed by class
+    passed: Vec<usize>,
+
+    /// Number of frames sent ahead of a waiting frame before it goes out regardless of its class
+    starvation_limit: Option<usize>,
+
+    /// The frame being written
+    current: Vec<u8>,
+
+    /// Number of bytes of `current` accepted by the writer
+    written: usize,
+}
+
+impl<W: Write> PrioritySink<W> {
+    /// Creates a new PrioritySink instance without starvation protection.
+    ///
+    /// # Arguments
+    /// * `writer` - The sink frames are written to.
+    /// * `s_char` - The special characters used for HDLC encoding.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub fn new(writer: W, s_char: SpecialChars) -> Result<Self, HDLCError> {
+        // Safety check to make sure the special character values are all unique
+        if s_char.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+
+        Ok(PrioritySink {
+            writer,
+            s_char,
+            classes: Vec::new(),
+            passed: Vec::new(),
+            starvation_limit: None,
+            current: Vec::new(),
+            written: 0,
+        })
+    }
+
+    /// Sends the oldest frame of a lower class once `frames` frames of higher classes were sent
+    /// ahead of it. A limit of 0 is taken as 1.
+    pub fn starvation_limit(mut self, frames: usize) -> Self {
+        self.starvation_limit = Some(frames.max(1));
+        self
+    }
+
+    /// Encodes `payload` and queues it in priority `class`, higher classes being sent first.
+    ///
+    /// Nothing is written, call [`PrioritySink::writable`] or [`PrioritySink::flush`] to send
+    /// the queued frames.
+    ///
+    /// # Error
+    ///
+    /// * Any error returned by [`encode`](crate::encode).
+    pub fn enqueue(&mut self, payload: &[u8], class: u8) -> Result<(), HDLCError> {
+        let frame = encode_ref(payload, &self.s_char)?;
+        let class = usize::from(class);
+        if class >= self.classes.len() {
+            self.classes.resize_with(class + 1, VecDeque::new);
+            self.passed.resize(class + 1, 0);
+        }
+        self.classes[class].push_back(frame);
+
+        Ok(())
+    }
+
+    /// Returns the number of frames waiting to be written, the one partly written included.
+    pub fn queued(&self) -> usize {
+        let current = usize::from(self.written < self.current.len());
+        self.classes.iter().map(VecDeque::len).sum::<usize>() + current
+    }
+
+    /// Returns the number of encoded bytes waiting to be written.
+    pub fn buffered(&self) -> usize {
+        let queued: usize = self.classes.iter().flatten().map(Vec::len).sum();
+        queued + self.current.len() - self.written
+    }
+
+    /// Notifies the sink that the writer can take more data, writing frames by priority until
+    /// the queue is empty or the writer would block.
+    ///
+    /// # Error
+    ///
+    /// * Any error returned by the writer other than `WouldBlock` and `Interrupted`.
+    pub fn writable(&mut self) -> io::Result<()> {
+        loop {
+            if self.written == self.current.len() {
+                match self.next_frame() {
+                    Some(frame) => self.current = frame,
+                    None => return Ok(()),
+                }
+                self.written = 0;
+            }
+            match self.writer.write(&self.current[self.written..]) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => self.written += n,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Writes every queued frame and flushes the writer.
+    ///
+    /// # Error
+    ///
+    /// * **io::ErrorKind::WouldBlock**: A non-blocking writer couldn't take every frame, the
+    ///   rest stays queued.
+    /// * Any error returned by the writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writable()?;
+        if self.queued() > 0 {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        self.writer.flush()
+    }
+
+    /// Returns a reference to the writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns the writer, dropping any queued frames.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Removes the frame to send next, from the highest class that starved or else the highest
+    /// class holding a frame
+    fn next_frame(&mut self) -> Option<Vec<u8>> {
+        let limit = self.starvation_limit.unwrap_or(usize::MAX);
+        let waiting = |class: &usize| !self.classes[*class].is_empty();
+        let class = (0..self.classes.len())
+            .rev()
+            .filter(waiting)
+            .find(|&class| self.passed[class] >= limit)
+            .or_else(|| (0..self.classes.len()).rev().find(waiting))?;
+
+        // Every lower class waiting had one more frame sent ahead of it
+        for lower in 0..class {
+            if !self.classes[lower].is_empty() {
+                self.passed[lower] += 1;
+            }
+        }
+        self.passed[class] = 0;
+
+        self.classes[class].pop_front()
+    }
+}
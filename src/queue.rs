@@ -0,0 +1,202 @@
+//! Lock-free single producer single consumer queue of decoded frames
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::HDLCError;
+
+/// One entry of a [`FrameQueue`]
+struct Slot<const N: usize> {
+    /// Holds the frame
+    buf: [u8; N],
+
+    /// Length of the frame in `buf`
+    len: usize,
+}
+
+/// A fixed capacity queue handing `M` frames of up to `N` bytes from an interrupt handler to
+/// the main loop, without locking or allocating.
+///
+/// [`FrameQueue::split`] hands out the two ends: the interrupt handler owns the
+/// [`FrameProducer`] and deposits the frames its [`FixedDecoder`](crate::FixedDecoder) completes,
+/// while the main loop owns the [`FrameConsumer`] and pops them.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{FixedDecoder, FrameQueue, SpecialChars};
+///
+/// let mut queue: FrameQueue<16, 4> = FrameQueue::new();
+/// let (mut producer, mut consumer) = queue.split();
+/// let mut decoder: FixedDecoder<16> = FixedDecoder::new(SpecialChars::default()).unwrap();
+///
+/// // Interrupt handler side
+/// for &byte in &[0x7E, 0x01, 0x02, 0x7E] {
+///     if let Ok(Some(frame)) = decoder.push(byte) {
+///         producer.enqueue(frame).unwrap();
+///     }
+/// }
+///
+/// // Main loop side
+/// assert_eq!(consumer.peek(), Some(&[0x01, 0x02][..]));
+/// consumer.pop();
+/// assert_eq!(consumer.peek(), None);
+/// ```
+pub struct FrameQueue<const N: usize, const M: usize> {
+    /// The frames, indexed by the head and tail counters modulo `M`
+    slots: [UnsafeCell<Slot<N>>; M],
+
+    /// Number of frames popped so far, only written by the consumer
+    head: AtomicUsize,
+
+    /// Number of frames enqueued so far, only written by the producer
+    tail: AtomicUsize,
+}
+
+// The producer only writes the slot past the tail and the consumer only reads the slot at the
+// head, and the counters hand each slot from one side to the other.
+unsafe impl<const N: usize, const M: usize> Sync for FrameQueue<N, M> {}
+
+impl<const N: usize, const M: usize> FrameQueue<N, M> {
+    /// Creates a new, empty FrameQueue instance.
+    pub const fn new() -> Self {
+        FrameQueue {
+            slots: [const {
+                UnsafeCell::new(Slot {
+                    buf: [0; N],
+                    len: 0,
+                })
+            }; M],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Splits the queue into its producing and consuming ends.
+    pub fn split(&mut self) -> (FrameProducer<'_, N, M>, FrameConsumer<'_, N, M>) {
+        (FrameProducer { queue: self }, FrameConsumer { queue: self })
+    }
+
+    /// Returns the number of queued frames.
+    pub fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        tail.wrapping_sub(self.head.load(Ordering::Acquire))
+    }
+
+    /// Returns true if no frame is queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<const N: usize, const M: usize> Default for FrameQueue<N, M> {
+    fn default() -> Self {
+        FrameQueue::new()
+    }
+}
+
+impl<const N: usize, const M: usize> core::fmt::Debug for FrameQueue<N, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FrameQueue")
+            .field("len", &self.len())
+            .field("capacity", &M)
+            .finish()
+    }
+}
+
+/// Producing end of a [`FrameQueue`].
+#[derive(Debug)]
+pub struct FrameProducer<'q, const N: usize, const M: usize> {
+    /// The queue frames are deposited in
+    queue: &'q FrameQueue<N, M>,
+}
+
+impl<const N: usize, const M: usize> FrameProducer<'_, N, M> {
+    /// Copies `frame` into the queue.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::BufferTooSmall**: The frame is longer than `N` bytes.
+    /// * **HDLCError::QueueFull**: All `M` slots hold frames the consumer hasn't popped yet.
+    pub fn enqueue(&mut self, frame: &[u8]) -> Result<(), HDLCError> {
+        if frame.len() > N {
+            return Err(HDLCError::BufferTooSmall);
+        }
+
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        if tail.wrapping_sub(self.queue.head.load(Ordering::Acquire)) >= M {
+            return Err(HDLCError::QueueFull);
+        }
+
+        // The slot isn't visible to the consumer until the tail moves past it
+        let slot = unsafe { &mut *self.queue.slots[tail % M].get() };
+        slot.buf[..frame.len()].copy_from_slice(frame);
+        slot.len = frame.len();
+        self.queue
+            .tail
+            .store(tail.wrapping_add(1), Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Returns true if the next frame would be rejected with `HDLCError::QueueFull`.
+    pub fn is_full(&self) -> bool {
+        self.queue.len() >= M
+    }
+}
+
+/// Consuming end of a [`FrameQueue`].
+#[derive(Debug)]
+pub struct FrameConsumer<'q, const N: usize, const M: usize> {
+    /// The queue frames are popped from
+    queue: &'q FrameQueue<N, M>,
+}
+
+impl<const N: usize, const M: usize> FrameConsumer<'_, N, M> {
+    /// Returns the oldest queued frame without removing it.
+    pub fn peek(&self) -> Option<&[u8]> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        if self.queue.tail.load(Ordering::Acquire) == head {
+            return None;
+        }
+
+        // The producer doesn't touch the slot until the head moves past it
+        let slot = unsafe { &*self.queue.slots[head % M].get() };
+        Some(&slot.buf[..slot.len])
+    }
+
+    /// Removes the oldest queued frame, returning false if the queue was empty.
+    pub fn pop(&mut self) -> bool {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        if self.queue.tail.load(Ordering::Acquire) == head {
+            return false;
+        }
+
+        self.queue
+            .head
+            .store(head.wrapping_add(1), Ordering::Release);
+        true
+    }
+
+    /// Copies the oldest queued frame into `buf` and removes it.
+    ///
+    /// # Returns
+    /// * `Result<Option<usize>>` - The length of the frame, or None if the queue is empty.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::BufferTooSmall**: The frame doesn't fit in `buf`, it stays queued.
+    pub fn dequeue_into(&mut self, buf: &mut [u8]) -> Result<Option<usize>, HDLCError> {
+        let len = match self.peek() {
+            Some(frame) => {
+                buf.get_mut(..frame.len())
+                    .ok_or(HDLCError::BufferTooSmall)?
+                    .copy_from_slice(frame);
+                frame.len()
+            }
+            None => return Ok(None),
+        };
+
+        self.pop();
+        Ok(Some(len))
+    }
+}
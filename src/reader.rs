@@ -0,0 +1,251 @@
+//! Streaming decoder implementing `std::io::Read`
+
+use std::io::{self, Read};
+
+use memchr::memchr;
+
+use crate::{memchr2, HDLCError, SpecialChars};
+
+/// Where a [`DecodingReader`] stands in the received data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Looking for the opening FEND of the next frame
+    Hunt,
+
+    /// Right after the opening FEND, further FENDs are fill
+    Opened,
+
+    /// Inside the payload of a frame
+    Data,
+
+    /// Right after a FESC, waiting for the trade character
+    Escaped,
+
+    /// The closing FEND of the current frame was read
+    End,
+}
+
+/// A reader handing out the unescaped payload of one frame at a time, so parsers expecting a
+/// `Read` consume frame contents directly.
+///
+/// `read` returns the payload bytes of the current frame and `Ok(0)` once its closing `fend` was
+/// reached. [`DecodingReader::next_frame`] then moves on to the next frame. Bytes in front of the
+/// first frame, and between frames, are skipped.
+///
+/// A malformed frame ends in an `InvalidData` error, and a frame cut short by the end of the
+/// data in an `UnexpectedEof` error, both wrapping the [`HDLCError`]. Offsets count from the
+/// opening `fend` of the frame.
+///
+/// # Example
+/// ```rust
+/// use std::io::{Cursor, Read};
+///
+/// use hdlc::{DecodingReader, SpecialChars};
+///
+/// let data = Cursor::new(vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x02, 0x7E]);
+/// let mut reader = DecodingReader::new(data, SpecialChars::default()).unwrap();
+///
+/// let mut frame = Vec::new();
+/// reader.read_to_end(&mut frame).unwrap();
+/// assert_eq!(frame, vec![0x01, 0x7E]);
+///
+/// assert!(reader.next_frame().unwrap());
+/// let mut frame = Vec::new();
+/// reader.read_to_end(&mut frame).unwrap();
+/// assert_eq!(frame, vec![0x02]);
+///
+/// assert!(!reader.next_frame().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct DecodingReader<R> {
+    /// Source of the encoded frames
+    reader: R,
+
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// Where the reader stands in the received data
+    state: State,
+
+    /// Bytes read from `reader`
+    raw: Vec<u8>,
+
+    /// Start of the bytes in `raw` not processed yet
+    start: usize,
+
+    /// End of the bytes read into `raw`
+    end: usize,
+
+    /// Offset of the next byte from the opening FEND of the current frame
+    pos: usize,
+}
+
+impl<R: Read> DecodingReader<R> {
+    /// Creates a new DecodingReader instance reading 1024 bytes at a time.
+    ///
+    /// # Arguments
+    /// * `reader` - The source of the encoded frames.
+    /// * `s_char` - The special characters used for HDLC encoding.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub fn new(reader: R, s_char: SpecialChars) -> Result<Self, HDLCError> {
+        // Safety check to make sure the special character values are all unique
+        if s_char.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+
+        Ok(DecodingReader {
+            reader,
+            s_char,
+            state: State::Hunt,
+            raw: vec![0; 1024],
+            start: 0,
+            end: 0,
+            pos: 0,
+        })
+    }
+
+    /// Skips what is left of the current frame and moves to the start of the next one.
+    ///
+    /// # Returns
+    /// * `io::Result<bool>` - Whether another frame was found, false at the end of the data.
+    ///
+    /// # Error
+    ///
+    /// * Any error returned by the reader. Malformed frames are skipped instead.
+    pub fn next_frame(&mut self) -> io::Result<bool> {
+        let mut sink = [0; 64];
+        while matches!(self.state, State::Opened | State::Data | State::Escaped) {
+            match self.read(&mut sink) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(e) if e.get_ref().is_some_and(|e| e.is::<HDLCError>()) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.state = State::Hunt;
+        self.hunt()
+    }
+
+    /// Returns a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns the inner reader. Bytes already read from it but not yet decoded are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Makes sure unprocessed bytes are buffered, returning false at the end of the data
+    fn fill(&mut self) -> io::Result<bool> {
+        if self.start == self.end {
+            self.end = self.reader.read(&mut self.raw)?;
+            self.start = 0;
+        }
+        Ok(self.start < self.end)
+    }
+
+    /// Marks `len` buffered bytes as processed
+    fn consume(&mut self, len: usize) {
+        self.start += len;
+        self.pos += len;
+    }
+
+    /// Skips bytes up to and including the opening FEND, returning false at the end of the data
+    fn hunt(&mut self) -> io::Result<bool> {
+        while self.state == State::Hunt {
+            if !self.fill()? {
+                return Ok(false);
+            }
+            let raw = &self.raw[self.start..self.end];
+            match memchr(self.s_char.fend, raw) {
+                Some(index) => {
+                    self.start += index + 1;
+                    self.pos = 1;
+                    self.state = State::Opened;
+                }
+                None => self.start = self.end,
+            }
+        }
+        Ok(true)
+    }
+
+    /// Ends the current frame with `error`
+    fn fail(&mut self, kind: io::ErrorKind, error: HDLCError) -> io::Error {
+        self.state = State::End;
+        io::Error::new(kind, error)
+    }
+}
+
+impl<R: Read> Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() || (self.state == State::Hunt && !self.hunt()?) {
+            return Ok(0);
+        }
+
+        let mut len = 0;
+        while len < buf.len() && self.state != State::End {
+            // Hand out what was decoded before blocking on the reader again
+            if self.start == self.end && len > 0 {
+                break;
+            }
+            if !self.fill()? {
+                let needed = if self.state == State::Escaped { 2 } else { 1 };
+                let error = HDLCError::Incomplete {
+                    needed: Some(needed),
+                };
+                return Err(self.fail(io::ErrorKind::UnexpectedEof, error));
+            }
+
+            let raw = &self.raw[self.start..self.end];
+            if self.state == State::Escaped {
+                match self.s_char.translate.decode(&raw[0]) {
+                    Some(&byte) => {
+                        buf[len] = byte;
+                        len += 1;
+                        self.consume(1);
+                        self.state = State::Data;
+                    }
+                    // Hand out the bytes before the error first
+                    None if len > 0 => break,
+                    None => {
+                        let error = HDLCError::MissingTradeChar {
+                            offset: self.pos - 1,
+                        };
+                        return Err(self.fail(io::ErrorKind::InvalidData, error));
+                    }
+                }
+                continue;
+            }
+
+            // Copy the run of bytes that need no swapping in one go
+            let run = memchr2(self.s_char.fend, self.s_char.fesc, raw)
+                .unwrap_or(raw.len())
+                .min(buf.len() - len);
+            if run > 0 {
+                buf[len..len + run].copy_from_slice(&raw[..run]);
+                len += run;
+                self.consume(run);
+                self.state = State::Data;
+            } else if raw[0] == self.s_char.fesc {
+                self.consume(1);
+                self.state = State::Escaped;
+            } else {
+                self.consume(1);
+                // A FEND right after the opening one is fill, otherwise it closes the frame
+                if self.state == State::Data {
+                    self.state = State::End;
+                } else {
+                    self.pos = 1;
+                }
+            }
+        }
+
+        Ok(len)
+    }
+}
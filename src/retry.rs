@@ -0,0 +1,148 @@
+//! Retransmission policies of the reliable datagram layer
+
+/// Decides how long an [`Arq`](crate::Arq) endpoint waits for an acknowledgement before sending
+/// a frame again, and when it gives up.
+///
+/// Times are in the unit of the `now` arguments of the endpoint. Any
+/// `FnMut(u32) -> Option<u64>` closure is a policy, so links with unusual characteristics can
+/// be tuned without implementing the trait.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{Arq, ArqConfig, SpecialChars};
+///
+/// // Wait 500 for the first transmission, then 2000, and give up after the third
+/// let policy = |attempt: u32| [500, 2000, 2000].get(attempt as usize).copied();
+/// let mut arq = Arq::with_policy(SpecialChars::default(), ArqConfig::default(), policy).unwrap();
+///
+/// arq.send(&[0x01], 0).unwrap();
+/// assert_eq!(arq.next_timeout(), Some(500));
+/// ```
+pub trait RetryPolicy {
+    /// Returns how long to wait for an acknowledgement of a frame transmitted for the
+    /// `attempt`th time, counting from 0 for the first transmission. None gives up on the frame
+    /// instead of sending it again.
+    fn timeout(&mut self, attempt: u32) -> Option<u64>;
+}
+
+impl<F: FnMut(u32) -> Option<u64>> RetryPolicy for F {
+    fn timeout(&mut self, attempt: u32) -> Option<u64> {
+        self(attempt)
+    }
+}
+
+/// Waits the same time after every transmission, for links of steady latency like short
+/// cables. The default policy of [`Arq::new`](crate::Arq::new).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedBackoff {
+    /// Time waited after every transmission
+    pub timeout: u64,
+
+    /// Number of times a frame is sent again before giving up
+    pub retries: u32,
+}
+
+impl RetryPolicy for FixedBackoff {
+    fn timeout(&mut self, attempt: u32) -> Option<u64> {
+        (attempt <= self.retries).then_some(self.timeout)
+    }
+}
+
+/// Waits longer by the same step after every transmission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LinearBackoff {
+    /// Time waited after the first transmission
+    pub initial: u64,
+
+    /// Time added for every further transmission
+    pub step: u64,
+
+    /// Longest time waited
+    pub max: u64,
+
+    /// Number of times a frame is sent again before giving up
+    pub retries: u32,
+}
+
+impl RetryPolicy for LinearBackoff {
+    fn timeout(&mut self, attempt: u32) -> Option<u64> {
+        let timeout = self
+            .initial
+            .saturating_add(self.step.saturating_mul(u64::from(attempt)));
+        (attempt <= self.retries).then_some(timeout.min(self.max))
+    }
+}
+
+/// Doubles the time waited after every transmission, less a random share of up to `jitter`,
+/// for links of varying latency like satellite hops where endpoints retrying in lockstep
+/// would collide again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExponentialBackoff {
+    /// Time waited after the first transmission
+    initial: u64,
+
+    /// Longest time waited
+    max: u64,
+
+    /// Share of the time taken off at random, from 0.0 to 1.0
+    jitter: f64,
+
+    /// Number of times a frame is sent again before giving up
+    retries: u32,
+
+    /// State of the xorshift generator drawing the jitter, never 0
+    state: u64,
+}
+
+impl ExponentialBackoff {
+    /// Creates a new ExponentialBackoff instance without jitter.
+    ///
+    /// # Arguments
+    /// * `initial` - Time waited after the first transmission.
+    /// * `max` - Longest time waited.
+    /// * `retries` - Number of times a frame is sent again before giving up.
+    pub fn new(initial: u64, max: u64, retries: u32) -> Self {
+        ExponentialBackoff {
+            initial,
+            max,
+            jitter: 0.0,
+            retries,
+            state: 1,
+        }
+    }
+
+    /// Takes a random share of up to `jitter`, from 0.0 to 1.0, off every time waited. The
+    /// same seed draws the same shares.
+    pub fn jitter(mut self, jitter: f64, seed: u64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        // Xorshift gets stuck at 0
+        self.state = seed.max(1);
+        self
+    }
+
+    /// Returns a uniform float in [0, 1) from the xorshift generator
+    fn next_f64(&mut self) -> f64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn timeout(&mut self, attempt: u32) -> Option<u64> {
+        if attempt > self.retries {
+            return None;
+        }
+        let timeout = self
+            .initial
+            .saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX))
+            .min(self.max);
+        if self.jitter == 0.0 {
+            return Some(timeout);
+        }
+
+        let share = self.jitter * self.next_f64();
+        Some(timeout - (timeout as f64 * share) as u64)
+    }
+}
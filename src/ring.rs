@@ -0,0 +1,163 @@
+//! Decoding frames out of ring buffers presenting their data as two slices
+
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+
+use memchr::memchr_iter;
+
+use crate::{decode_with_ref, DecodeOptions, Frames, Framing, HDLCError, SpecialChars};
+
+/// Works like [`decode_frames_with`](crate::decode_frames_with) on the data of a ring buffer
+/// that wrapped around, `head` followed by `tail`, such as the two halves of
+/// `VecDeque::as_slices`.
+///
+/// Frames are decoded where they sit, only the frame straddling the end of `head` is copied.
+/// The number of bytes consumed counts from the start of `head` and may reach into `tail`.
+///
+/// # Inputs
+/// * **&[u8]**: The first part of the received data
+/// * **&[u8]**: The part of the received data following `head`
+/// * **SpecialChars**: The special characters you want to swap
+/// * **DecodeOptions**: How the frames are decoded
+///
+/// # Output
+///
+/// * **`Result<(Vec<Vec<u8>>, usize)>`**: Decoded payloads and the number of bytes consumed
+///
+/// # Error
+///
+/// * Any error [`decode_frames_with`](crate::decode_frames_with) returns. Offsets count from
+///   the start of `head`.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{decode_frames_split, DecodeOptions, SpecialChars};
+///
+/// let head = [0x7E, 0x01, 0x7E, 0x7E, 0x02];
+/// let tail = [0x7D, 0x5E, 0x7E, 0x7E, 0x03];
+///
+/// let (payloads, consumed) =
+///     decode_frames_split(&head, &tail, SpecialChars::default(), DecodeOptions::default())
+///         .unwrap();
+/// assert_eq!(payloads, vec![vec![0x01], vec![0x02, 0x7E]]);
+/// assert_eq!(consumed, 8);
+/// ```
+pub fn decode_frames_split(
+    head: &[u8],
+    tail: &[u8],
+    s_chars: SpecialChars,
+    options: DecodeOptions,
+) -> Result<(Vec<Vec<u8>>, usize), HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+
+    let mut payloads = Vec::new();
+    let mut iter = split_frames(head, &s_chars, options);
+    decode_run(&mut iter, 0, &s_chars, options, &mut payloads)?;
+    let consumed = head.len() - iter.remainder().len();
+
+    // The partial frame at the end of the head, along with the closing flag that may open it
+    let sharing = options.shared_flags && !options.empty_frames && !iter.terminated;
+    let carry = usize::from(
+        sharing
+            && iter.remainder().is_empty()
+            && head.last() == Some(&s_chars.fend)
+            && tail.first().is_some_and(|&b| b != s_chars.fend),
+    );
+    let mut straddle = head[consumed - carry..].to_vec();
+    let mut tail_iter = split_frames(tail, &s_chars, options);
+    if !straddle.is_empty() {
+        // Grow the straddling frame one flag at a time until it is complete
+        let mut copied = 0;
+        let complete = memchr_iter(s_chars.fend, tail).any(|index| {
+            straddle.extend_from_slice(&tail[copied..=index]);
+            copied = index + 1;
+            split_frames(&straddle, &s_chars, options).next().is_some()
+        });
+        if !complete {
+            // Skip what the straddling frame can't start with
+            let mut iter = split_frames(&straddle, &s_chars, options);
+            iter.next();
+            return Ok((payloads, consumed - carry + iter.pos));
+        }
+
+        let mut iter = split_frames(&straddle, &s_chars, options);
+        decode_run(
+            &mut iter,
+            consumed - carry,
+            &s_chars,
+            options,
+            &mut payloads,
+        )?;
+        // Everything past the straddling frame was copied from the tail, and is left to it
+        let left = iter.remainder().len();
+        tail_iter.pos = copied - left;
+        tail_iter.shared = sharing && left == 0;
+    }
+    decode_run(&mut tail_iter, head.len(), &s_chars, options, &mut payloads)?;
+    Ok((payloads, head.len() + tail_iter.pos))
+}
+
+/// Decodes the frames of a ring buffer with [`decode_frames_split`], and removes the bytes
+/// consumed from its front. The trailing partial frame stays in the ring.
+///
+/// # Error
+///
+/// * Any error [`decode_frames_split`] returns. The ring is left untouched.
+///
+/// # Example
+/// ```rust
+/// use std::collections::VecDeque;
+///
+/// use hdlc::{decode_frames_deque, DecodeOptions, SpecialChars};
+///
+/// let mut ring = VecDeque::from(vec![0x7E, 0x01, 0x7E, 0x7E, 0x02]);
+///
+/// let payloads =
+///     decode_frames_deque(&mut ring, SpecialChars::default(), DecodeOptions::default()).unwrap();
+/// assert_eq!(payloads, vec![vec![0x01]]);
+/// assert_eq!(ring, vec![0x7E, 0x02]);
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_frames_deque(
+    ring: &mut VecDeque<u8>,
+    s_chars: SpecialChars,
+    options: DecodeOptions,
+) -> Result<Vec<Vec<u8>>, HDLCError> {
+    let (head, tail) = ring.as_slices();
+    let (payloads, consumed) = decode_frames_split(head, tail, s_chars, options)?;
+    ring.drain(..consumed);
+
+    Ok(payloads)
+}
+
+/// Returns an iterator over the raw frames of `buf` as configured by `options`
+fn split_frames<'a>(buf: &'a [u8], s_chars: &SpecialChars, options: DecodeOptions) -> Frames<'a> {
+    let mut iter = Frames::new(buf, s_chars.fend);
+    iter.empty = options.empty_frames;
+    iter.share = options.shared_flags;
+    iter.terminated = options.framing == Framing::TerminatorOnly;
+    iter
+}
+
+/// Decodes the frames `iter` hands out into `payloads`, shifting error offsets by `base`
+fn decode_run(
+    iter: &mut Frames<'_>,
+    base: usize,
+    s_chars: &SpecialChars,
+    options: DecodeOptions,
+    payloads: &mut Vec<Vec<u8>>,
+) -> Result<(), HDLCError> {
+    while let Some(frame) = iter.next() {
+        let start = base + iter.pos - frame.len();
+        let payload =
+            decode_with_ref(frame, s_chars, options).map_err(|error| error.shifted(start))?;
+        if options.empty_frames || !payload.is_empty() {
+            payloads.push(payload);
+        }
+    }
+
+    Ok(())
+}
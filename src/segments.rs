@@ -0,0 +1,172 @@
+//! Scatter-gather encoding that describes a frame without copying the payload
+
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::{HDLCError, SpecialChars};
+
+/// An encoded frame described as a list of segments, built by [`encode_segments`].
+///
+/// Runs of the payload that need no escaping are borrowed as they are, and only the flags and
+/// the escape pairs live in the frame itself. This fits DMA engines taking a descriptor list and
+/// vectored writes.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{encode_segments, SpecialChars};
+///
+/// let payload = [0x01, 0x02, 0x7E, 0x03];
+/// let frame = encode_segments(&payload, SpecialChars::default()).unwrap();
+///
+/// let segments: Vec<&[u8]> = frame.iter().collect();
+/// assert_eq!(
+///     segments,
+///     vec![&[0x7E][..], &[0x01, 0x02], &[0x7D, 0x5E], &[0x03], &[0x7E]]
+/// );
+/// assert_eq!(frame.len(), 7);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EncodedSegments<'a> {
+    /// The payload being encoded
+    data: &'a [u8],
+
+    /// The opening and closing flag
+    fend: [u8; 1],
+
+    /// Escape pair for every byte, only meaningful for escaped bytes
+    escapes: [[u8; 2]; 256],
+
+    /// Set for every byte that has to be escaped
+    escaped: [bool; 256],
+}
+
+/// Describes the encoded frame of `data` as segments, without copying the payload.
+///
+/// # Inputs
+/// * **&[u8]**: The payload you want to encode
+/// * **SpecialChars**: The special characters you want to swap
+///
+/// # Output
+///
+/// * **`Result<EncodedSegments>`**: The segments making up the encoded frame
+///
+/// # Error
+///
+/// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+pub fn encode_segments(
+    data: &[u8],
+    s_chars: SpecialChars,
+) -> Result<EncodedSegments<'_>, HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+
+    let mut escapes = [[0; 2]; 256];
+    let mut escaped = [false; 256];
+    for (byte, translated) in s_chars.translate.iter() {
+        escapes[byte as usize] = [s_chars.fesc, translated];
+        escaped[byte as usize] = true;
+    }
+
+    Ok(EncodedSegments {
+        data,
+        fend: [s_chars.fend],
+        escapes,
+        escaped,
+    })
+}
+
+impl<'a> EncodedSegments<'a> {
+    /// Returns an iterator over the segments, in transmit order.
+    pub fn iter(&self) -> Segments<'_> {
+        Segments {
+            frame: self,
+            rest: self.data,
+            opened: false,
+            closed: false,
+        }
+    }
+
+    /// Returns the segments as `IoSlice`s, ready for `Write::write_vectored`.
+    #[cfg(feature = "std")]
+    pub fn io_slices(&self) -> Vec<IoSlice<'_>> {
+        self.iter().map(IoSlice::new).collect()
+    }
+
+    /// Returns the length of the encoded frame in bytes.
+    pub fn len(&self) -> usize {
+        let escapes = self
+            .data
+            .iter()
+            .filter(|&&b| self.escaped[b as usize])
+            .count();
+        self.data.len() + escapes + 2
+    }
+
+    /// Always false, an encoded frame holds at least its two flags.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl<'s, 'a> IntoIterator for &'s EncodedSegments<'a> {
+    type Item = &'s [u8];
+    type IntoIter = Segments<'s>;
+
+    fn into_iter(self) -> Segments<'s> {
+        self.iter()
+    }
+}
+
+/// Iterator over the segments of an [`EncodedSegments`] frame.
+#[derive(Debug, Clone)]
+pub struct Segments<'s> {
+    /// The frame being described
+    frame: &'s EncodedSegments<'s>,
+
+    /// The payload not yet described
+    rest: &'s [u8],
+
+    /// Set once the opening flag was yielded
+    opened: bool,
+
+    /// Set once the closing flag was yielded
+    closed: bool,
+}
+
+impl<'s> Iterator for Segments<'s> {
+    type Item = &'s [u8];
+
+    fn next(&mut self) -> Option<&'s [u8]> {
+        if !self.opened {
+            self.opened = true;
+            return Some(&self.frame.fend);
+        }
+
+        match self.rest.first() {
+            Some(&byte) if self.frame.escaped[byte as usize] => {
+                self.rest = &self.rest[1..];
+                Some(&self.frame.escapes[byte as usize])
+            }
+            Some(_) => {
+                let run = self
+                    .rest
+                    .iter()
+                    .position(|&b| self.frame.escaped[b as usize])
+                    .unwrap_or(self.rest.len());
+                let (segment, rest) = self.rest.split_at(run);
+                self.rest = rest;
+                Some(segment)
+            }
+            None if !self.closed => {
+                self.closed = true;
+                Some(&self.frame.fend)
+            }
+            None => None,
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Transmit queue with watermark based backpressure
+
+use std::io::{self, Write};
+use std::task::{Context, Poll, Waker};
+
+use crate::{encode_into_ref, HDLCError, SpecialChars};
+
+/// A transmit queue buffering encoded frames in front of a writer.
+///
+/// Queued frames are written out back to back, so a single write call carries as many frames as
+/// the writer accepts. Once the queued bytes reach the high watermark the sink stops being ready
+/// and stays so until the queue has drained to the low watermark, which keeps fast producers
+/// from overrunning a slow serial output.
+///
+/// The writer may be non-blocking: a `WouldBlock` error just leaves the data queued. Async
+/// producers use [`FrameSink::poll_ready`], and the event loop calls [`FrameSink::writable`]
+/// when the writer can take more data.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{FrameSink, SpecialChars};
+///
+/// let mut sink = FrameSink::new(Vec::new(), SpecialChars::default())
+///     .unwrap()
+///     .watermarks(4, 8);
+///
+/// sink.start_send(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06]).unwrap();
+/// assert!(!sink.is_ready());
+///
+/// assert!(sink.ready().unwrap());
+/// assert_eq!(sink.get_ref(), &vec![0x7E, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x7E]);
+/// ```
+#[derive(Debug)]
+pub struct FrameSink<W> {
+    /// Sink for the encoded frames
+    writer: W,
+
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// Encoded bytes not yet accepted by the writer
+    queue: Vec<u8>,
+
+    /// Queue length at which the sink becomes ready again
+    low_watermark: usize,
+
+    /// Queue length at which the sink stops being ready
+    high_watermark: usize,
+
+    /// Set between reaching the high watermark and draining to the low watermark
+    blocked: bool,
+
+    /// Task waiting for the sink to become ready
+    waker: Option<Waker>,
+}
+
+impl<W: Write> FrameSink<W> {
+    /// Creates a new FrameSink instance with a low watermark of 1024 and a high watermark of
+    /// 4096 bytes.
+    ///
+    /// # Arguments
+    /// * `writer` - The sink frames are written to.
+    /// * `s_char` - The special characters used for HDLC encoding.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub fn new(writer: W, s_char: SpecialChars) -> Result<Self, HDLCError> {
+        // Safety check to make sure the special character values are all unique
+        if s_char.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+
+        Ok(FrameSink {
+            writer,
+            s_char,
+            queue: Vec::new(),
+            low_watermark: 1024,
+            high_watermark: 4096,
+            blocked: false,
+            waker: None,
+        })
+    }
+
+    /// Sets the low and high watermarks in queued bytes. The high watermark is raised to the low
+    /// one if it is smaller.
+    pub fn watermarks(mut self, low: usize, high: usize) -> Self {
+        self.low_watermark = low;
+        self.high_watermark = high.max(low);
+        self
+    }
+
+    /// Returns true while the sink accepts new frames, without writing anything.
+    pub fn is_ready(&self) -> bool {
+        !self.blocked
+    }
+
+    /// Returns the number of encoded bytes waiting to be written.
+    pub fn buffered(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Encodes `payload` and appends it to the queue.
+    ///
+    /// Frames are accepted even when the sink isn't ready; producers that want backpressure check
+    /// [`FrameSink::ready`] or [`FrameSink::poll_ready`] first.
+    ///
+    /// # Error
+    ///
+    /// * Any error returned by [`encode_into`](crate::encode_into).
+    pub fn start_send(&mut self, payload: &[u8]) -> Result<(), HDLCError> {
+        encode_into_ref(payload, &self.s_char, &mut self.queue)?;
+        if self.queue.len() >= self.high_watermark {
+            self.blocked = true;
+        }
+
+        Ok(())
+    }
+
+    /// Writes as much of the queue as the writer accepts and returns whether the sink is ready.
+    ///
+    /// # Error
+    ///
+    /// * Any error returned by the writer other than `WouldBlock` and `Interrupted`.
+    pub fn ready(&mut self) -> io::Result<bool> {
+        self.write_queue()?;
+        Ok(self.is_ready())
+    }
+
+    /// Async flavour of [`FrameSink::ready`]. When the sink isn't ready the task is woken by the
+    /// next [`FrameSink::writable`] call that drains the queue to the low watermark.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.ready() {
+            Ok(true) => Poll::Ready(Ok(())),
+            Ok(false) => {
+                self.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            Err(e) => Poll::Ready(Err(e)),
+        }
+    }
+
+    /// Notifies the sink that the writer can take more data, writing the queue and waking the
+    /// task waiting in [`FrameSink::poll_ready`] once the sink is ready.
+    pub fn writable(&mut self) -> io::Result<()> {
+        self.write_queue()?;
+        if self.is_ready() {
+            if let Some(waker) = self.waker.take() {
+                waker.wake();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the whole queue and flushes the writer.
+    ///
+    /// # Error
+    ///
+    /// * **io::ErrorKind::WouldBlock**: A non-blocking writer couldn't take the whole queue, the
+    ///   rest stays queued.
+    /// * Any error returned by the writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_queue()?;
+        if !self.queue.is_empty() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        self.writer.flush()
+    }
+
+    /// Returns a reference to the writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns the writer, dropping any queued frames.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes queued bytes until the queue is empty or the writer would block.
+    fn write_queue(&mut self) -> io::Result<()> {
+        let mut written = 0;
+        let result = loop {
+            if written == self.queue.len() {
+                break Ok(());
+            }
+            match self.writer.write(&self.queue[written..]) {
+                Ok(0) => break Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => written += n,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => break Err(e),
+            }
+        };
+
+        self.queue.drain(..written);
+        if self.queue.len() <= self.low_watermark {
+            self.blocked = false;
+        }
+
+        result
+    }
+}
@@ -0,0 +1,116 @@
+//! Encoder driven by a lookup table built once from the special characters
+
+use crate::{HDLCError, Output, SpecialChars};
+
+/// Set in a table entry when the byte has to be escaped, the low byte holds the substitute
+const ESCAPE: u16 = 0x100;
+
+/// An encoder driven by a 256 entry table mapping every byte to whether it needs escaping and
+/// its substitute.
+///
+/// The special characters are validated once, when the encoder is built, so encoding is a
+/// single table lookup per byte whatever the character set, which pays off for custom
+/// translations and large [`EscapeMap`](crate::EscapeMap)s.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{EscapeMap, SpecialChars, TableEncoder};
+///
+/// let chars = SpecialChars::default().with_escape_map(EscapeMap::from_accm(0xFFFF_FFFF)).unwrap();
+/// let encoder = TableEncoder::new(chars).unwrap();
+///
+/// assert_eq!(encoder.encode(&[0x01, 0x41]), vec![0x7E, 0x7D, 0x21, 0x41, 0x7E]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TableEncoder {
+    /// Frame END, written before and after the payload
+    fend: u8,
+
+    /// Frame ESCape, written in front of every substitute
+    fesc: u8,
+
+    /// `ESCAPE | substitute` for every escaped byte, 0 for every other byte
+    table: [u16; 256],
+}
+
+impl TableEncoder {
+    /// Creates a new TableEncoder instance.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.
+    pub fn new(s_chars: SpecialChars) -> Result<Self, HDLCError> {
+        if s_chars.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+
+        let mut table = [0; 256];
+        for (byte, translated) in s_chars.translate.iter() {
+            table[byte as usize] = ESCAPE | u16::from(translated);
+        }
+
+        Ok(TableEncoder {
+            fend: s_chars.fend,
+            fesc: s_chars.fesc,
+            table,
+        })
+    }
+
+    /// Returns the length of the encoded message of `data`, flags included.
+    pub fn encoded_len(&self, data: &[u8]) -> usize {
+        let escapes: usize = data
+            .iter()
+            .map(|&b| usize::from(self.table[b as usize] >> 8))
+            .sum();
+        data.len() + escapes + 2
+    }
+
+    /// Produces escaped (encoded) message surrounded with `FEND`.
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(self.encoded_len(data));
+        output.push(self.fend);
+        for &value in data {
+            match self.table[value as usize] {
+                0 => output.push(value),
+                entry => output.extend_from_slice(&[self.fesc, entry as u8]),
+            }
+        }
+        output.push(self.fend);
+
+        output
+    }
+
+    /// Pushes the escaped (encoded) message surrounded with `FEND` to any [`Output`].
+    ///
+    /// # Output
+    ///
+    /// * **`Result<usize>`**: Number of bytes pushed
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::BufferTooSmall**: The output is full. Part of the message may already have
+    ///   been pushed.
+    pub fn encode_into(&self, data: &[u8], out: &mut impl Output) -> Result<usize, HDLCError> {
+        out.push(self.fend)?;
+        let mut pushed = 1;
+
+        // Push the runs of bytes that need no swapping in one go
+        let mut start = 0;
+        for (index, &value) in data.iter().enumerate() {
+            let entry = self.table[value as usize];
+            if entry != 0 {
+                out.extend_from_slice(&data[start..index])?;
+                out.extend_from_slice(&[self.fesc, entry as u8])?;
+                pushed += index - start + 2;
+                start = index + 1;
+            }
+        }
+
+        out.extend_from_slice(&data[start..])?;
+        out.push(self.fend)?;
+        pushed += data.len() - start + 1;
+
+        Ok(pushed)
+    }
+}
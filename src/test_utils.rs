@@ -0,0 +1,108 @@
+//! Roundtrip laws and a lossy channel for tests of downstream crates, enabled by the `test-utils`
+//! feature
+//!
+//! The generators turn arbitrary bytes into valid configurations and the assertions panic with
+//! a description of the broken law, so they plug into any property testing framework, like
+//! `proptest` with `any::<[u8; 4]>().prop_filter_map("duplicate", special_chars)`.
+//!
+//! [`LossyChannel`] corrupts a stream deterministically and [`MalformedFrame`] builds frames with
+//! chosen faults, to exercise the error paths of receivers.
+
+use crate::{
+    decode_with, encode, encode_frames, escape, unescape, DecodeOptions, Decoder, EncodeOptions,
+    EscapeMap, FcsMode, FrameReader, SpecialChars, TableEncoder,
+};
+
+pub use crate::lossy::{LossyChannel, LossyConfig, LossyStats};
+pub use crate::malformed::MalformedFrame;
+
+/// Builds special characters from arbitrary `[fend, fesc, tfend, tfesc]` bytes, or returns None
+/// if any of them collide.
+pub fn special_chars(bytes: [u8; 4]) -> Option<SpecialChars> {
+    let [fend, fesc, tfend, tfesc] = bytes;
+    SpecialChars::try_new(fend, fesc, tfend, tfesc).ok()
+}
+
+/// Builds special characters like [`special_chars`] that also escape the bytes of a PPP ACCM,
+/// or returns None if any of the translations collide.
+pub fn special_chars_with_accm(bytes: [u8; 4], accm: u32) -> Option<SpecialChars> {
+    special_chars(bytes)?
+        .with_escape_map(EscapeMap::from_accm(accm))
+        .ok()
+}
+
+/// Asserts that `payload` survives being framed with `s_chars` and `fcs` and decoded again, by
+/// every encoder and decoder.
+///
+/// # Panics
+///
+/// Panics naming the first law that doesn't hold.
+pub fn assert_roundtrip(payload: &[u8], s_chars: SpecialChars, fcs: FcsMode) {
+    let escaped = escape(payload, s_chars.clone()).expect("escape failed");
+    assert_eq!(
+        unescape(&escaped, s_chars.clone()).as_deref(),
+        Ok(payload),
+        "unescape(escape(payload)) != payload"
+    );
+
+    let mut data = payload.to_vec();
+    fcs.append(&mut data);
+    let frame = encode(&data, s_chars.clone()).expect("encode failed");
+    let table = TableEncoder::new(s_chars.clone()).expect("TableEncoder::new failed");
+    assert_eq!(table.encode(&data), frame, "TableEncoder::encode != encode");
+
+    let options = DecodeOptions {
+        fcs,
+        empty_frames: true,
+        ..DecodeOptions::default()
+    };
+    assert_eq!(
+        decode_with(&frame, s_chars.clone(), options).as_deref(),
+        Ok(payload),
+        "decode_with(encode(payload)) != payload"
+    );
+
+    let mut decoder =
+        Decoder::with_options(s_chars, options).expect("Decoder::with_options failed");
+    for byte in &frame {
+        decoder.push(&[*byte]);
+    }
+    assert_eq!(
+        decoder.next_frame(),
+        Some(Ok(payload.to_vec())),
+        "Decoder fed one byte at a time doesn't hand out the payload"
+    );
+}
+
+/// Asserts that a burst of `payloads` framed with `s_chars` and `options` comes out of a
+/// [`FrameReader`] reading `chunk_size` bytes at a time. Payloads left empty are skipped by the
+/// reader.
+///
+/// # Panics
+///
+/// Panics naming the first law that doesn't hold.
+pub fn assert_stream_roundtrip(
+    payloads: &[Vec<u8>],
+    s_chars: SpecialChars,
+    options: EncodeOptions,
+    chunk_size: usize,
+) {
+    let data = encode_frames(payloads, s_chars.clone(), options).expect("encode_frames failed");
+    let decode_options = DecodeOptions {
+        shared_flags: options.shared_flags,
+        framing: options.framing,
+        ..DecodeOptions::default()
+    };
+
+    let mut reader = std::io::Cursor::new(data);
+    let frames: Vec<Vec<u8>> = FrameReader::builder(s_chars)
+        .chunk_size(chunk_size)
+        .options(decode_options)
+        .build(&mut reader)
+        .collect();
+    let expected: Vec<Vec<u8>> = payloads.iter().filter(|p| !p.is_empty()).cloned().collect();
+    assert_eq!(
+        frames, expected,
+        "FrameReader doesn't hand out the payloads"
+    );
+}
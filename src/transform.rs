@@ -0,0 +1,152 @@
+//! Payload transforms applied around the framing, such as compression or encryption
+
+use crate::{decode, encode, HDLCError, SpecialChars};
+
+/// A reversible transformation of payloads, applied before escaping by
+/// [`encode_transformed`] and after unescaping by [`decode_transformed`].
+///
+/// Bandwidth-constrained links plug a compressor in here to compress transparently at the
+/// framing layer, and secured links an authenticated cipher sealing the payloads. The methods
+/// take `&mut self` so a transform may keep state across frames, like a shared dictionary or a
+/// nonce counter.
+///
+/// A pair of transforms applies the first one and then the second one, so `(compress, seal)`
+/// compresses before sealing and opens before decompressing. Transforms picked at runtime are
+/// passed as `Box<dyn PayloadTransform>`.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{decode_transformed, encode_transformed, HDLCError, PayloadTransform, SpecialChars};
+///
+/// /// Run-length encodes payloads as `(count, byte)` pairs
+/// struct RunLength;
+///
+/// impl PayloadTransform for RunLength {
+///     fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+///         let mut output = Vec::new();
+///         for run in payload.chunk_by(|a, b| a == b) {
+///             for part in run.chunks(255) {
+///                 output.extend_from_slice(&[part.len() as u8, part[0]]);
+///             }
+///         }
+///         Ok(output)
+///     }
+///
+///     fn decode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+///         if payload.len() % 2 != 0 {
+///             return Err(HDLCError::TransformFailed);
+///         }
+///         Ok(payload
+///             .chunks(2)
+///             .flat_map(|pair| core::iter::repeat(pair[1]).take(pair[0] as usize))
+///             .collect())
+///     }
+/// }
+///
+/// let chars = SpecialChars::default();
+/// let frame = encode_transformed(&[0x00; 64], chars.clone(), &mut RunLength).unwrap();
+/// assert_eq!(frame, vec![0x7E, 0x40, 0x00, 0x7E]);
+///
+/// let payload = decode_transformed(&frame, chars, &mut RunLength).unwrap();
+/// assert_eq!(payload, vec![0x00; 64]);
+/// ```
+pub trait PayloadTransform {
+    /// Transforms a payload before it is framed.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::TransformFailed**: The payload can't be transformed.
+    fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError>;
+
+    /// Reverts [`PayloadTransform::encode`] on a received payload.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::TransformFailed**: The payload wasn't produced by `encode`.
+    /// * **HDLCError::AuthenticationFailed**: The payload failed the authentication of a sealing
+    ///   transform.
+    fn decode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError>;
+}
+
+impl<T: PayloadTransform + ?Sized> PayloadTransform for &mut T {
+    fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        (**self).encode(payload)
+    }
+
+    fn decode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        (**self).decode(payload)
+    }
+}
+
+impl<T: PayloadTransform + ?Sized> PayloadTransform for Box<T> {
+    fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        (**self).encode(payload)
+    }
+
+    fn decode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        (**self).decode(payload)
+    }
+}
+
+impl<A: PayloadTransform, B: PayloadTransform> PayloadTransform for (A, B) {
+    fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        self.1.encode(&self.0.encode(payload)?)
+    }
+
+    fn decode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        self.0.decode(&self.1.decode(payload)?)
+    }
+}
+
+/// Transforms `data` with `transform` and produces the escaped (encoded) message surrounded
+/// with `FEND`.
+///
+/// # Inputs
+/// * **&[u8]**: The payload you want to encode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **&mut impl PayloadTransform**: The transform applied before escaping
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: Encoded output message
+///
+/// # Error
+///
+/// * **HDLCError::TransformFailed**: The transform failed.
+/// * Any error [`encode`] returns.
+pub fn encode_transformed(
+    data: &[u8],
+    s_chars: SpecialChars,
+    transform: &mut (impl PayloadTransform + ?Sized),
+) -> Result<Vec<u8>, HDLCError> {
+    // Safety check to make sure the special character values are all unique
+    if s_chars.has_duplicates() {
+        return Err(HDLCError::DuplicateSpecialChar);
+    }
+
+    encode(&transform.encode(data)?, s_chars)
+}
+
+/// Produces the unescaped (decoded) message of a frame and reverts `transform` on it.
+///
+/// # Inputs
+/// * **&[u8]**: The frame you want to decode
+/// * **SpecialChars**: The special characters you want to swap
+/// * **&mut impl PayloadTransform**: The transform reverted after unescaping
+///
+/// # Output
+///
+/// * **`Result<Vec<u8>>`**: Decoded output message
+///
+/// # Error
+///
+/// * **HDLCError::TransformFailed**: The transform failed.
+/// * **HDLCError::AuthenticationFailed**: A sealing transform rejected the payload.
+/// * Any error [`decode`] returns.
+pub fn decode_transformed(
+    input: &[u8],
+    s_chars: SpecialChars,
+    transform: &mut (impl PayloadTransform + ?Sized),
+) -> Result<Vec<u8>, HDLCError> {
+    transform.decode(&decode(input, s_chars)?)
+}
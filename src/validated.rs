@@ -0,0 +1,108 @@
+//! Special characters validated once, up front
+
+use crate::{
+    decode_unchecked, decoded_len_hint, encode_unchecked, encoded_len_of, HDLCError, Output,
+    SpecialChars,
+};
+
+/// Special characters checked for duplicates once, when created.
+///
+/// Encoding and decoding through it skips the per-call check, so encoding can't fail and
+/// decoding only fails on malformed frames, never with `HDLCError::DuplicateSpecialChar`.
+///
+/// # Example
+/// ```rust
+/// use hdlc::{SpecialChars, ValidatedSpecialChars};
+///
+/// let chars = ValidatedSpecialChars::new(SpecialChars::default()).unwrap();
+///
+/// let frame = chars.encode(&[0x01, 0x7E]);
+/// assert_eq!(frame, vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E]);
+/// assert_eq!(chars.decode(&frame), Ok(vec![0x01, 0x7E]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValidatedSpecialChars(SpecialChars);
+
+impl ValidatedSpecialChars {
+    /// Checks `s_chars` for duplicates and wraps them.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.
+    pub const fn new(s_chars: SpecialChars) -> Result<Self, HDLCError> {
+        if s_chars.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+        Ok(ValidatedSpecialChars(s_chars))
+    }
+
+    /// Returns the wrapped special characters.
+    pub const fn get(&self) -> &SpecialChars {
+        &self.0
+    }
+
+    /// Unwraps the special characters.
+    pub fn into_inner(self) -> SpecialChars {
+        self.0
+    }
+
+    /// Produces escaped (encoded) message surrounded with `FEND`.
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(encoded_len_of(data, &self.0));
+        // Pushing to a Vec never fails
+        let _ = encode_unchecked(data, &self.0, &mut output);
+        output
+    }
+
+    /// Pushes the escaped (encoded) message surrounded with `FEND` to any [`Output`].
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::BufferTooSmall**: The output is full. Part of the message may already have
+    ///   been pushed.
+    pub fn encode_into(&self, data: &[u8], out: &mut impl Output) -> Result<usize, HDLCError> {
+        encode_unchecked(data, &self.0, out)
+    }
+
+    /// Produces unescaped (decoded) message without `FEND` characters.
+    ///
+    /// # Error
+    ///
+    /// * Any error [`decode`](crate::decode) returns for a malformed frame.
+    pub fn decode(&self, input: &[u8]) -> Result<Vec<u8>, HDLCError> {
+        let mut output = Vec::with_capacity(decoded_len_hint(input.len()));
+        decode_unchecked(input, &self.0, &mut output)?;
+        Ok(output)
+    }
+
+    /// Pushes the unescaped (decoded) message without `FEND` characters to any [`Output`].
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::BufferTooSmall**: The output is full.
+    /// * Any error [`decode`](crate::decode) returns for a malformed frame.
+    pub fn decode_into(&self, input: &[u8], out: &mut impl Output) -> Result<usize, HDLCError> {
+        decode_unchecked(input, &self.0, out)
+    }
+}
+
+impl TryFrom<SpecialChars> for ValidatedSpecialChars {
+    type Error = HDLCError;
+
+    fn try_from(s_chars: SpecialChars) -> Result<Self, HDLCError> {
+        ValidatedSpecialChars::new(s_chars)
+    }
+}
+
+impl From<ValidatedSpecialChars> for SpecialChars {
+    fn from(s_chars: ValidatedSpecialChars) -> SpecialChars {
+        s_chars.0
+    }
+}
+
+impl AsRef<SpecialChars> for ValidatedSpecialChars {
+    fn as_ref(&self) -> &SpecialChars {
+        &self.0
+    }
+}
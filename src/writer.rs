@@ -0,0 +1,146 @@
+//! Streaming encoder implementing `std::io::Write`
+
+use std::io::{self, Write};
+
+use crate::pacing::Pacer;
+use crate::{escape_unchecked, HDLCError, Pacing, SpecialChars};
+
+/// A writer escaping everything written to it into an HDLC frame on the fly.
+///
+/// The opening `fend` is written along with the first bytes of a frame and
+/// [`EncodingWriter::finish`] writes the closing `fend`, so code written against `io::Write`,
+/// like serializers or compressors, streams straight into a frame. The next write opens the
+/// next frame.
+///
+/// `flush` flushes the inner writer without closing the frame.
+///
+/// # Example
+/// ```rust
+/// use std::io::Write;
+///
+/// use hdlc::{EncodingWriter, SpecialChars};
+///
+/// let mut writer = EncodingWriter::new(Vec::new(), SpecialChars::default()).unwrap();
+/// writer.write_all(&[0x01, 0x7E]).unwrap();
+/// writer.write_all(&[0x02]).unwrap();
+/// writer.finish().unwrap();
+///
+/// assert_eq!(writer.into_inner(), vec![0x7E, 0x01, 0x7D, 0x5E, 0x02, 0x7E]);
+/// ```
+#[derive(Debug)]
+pub struct EncodingWriter<W> {
+    /// Sink for the encoded frames
+    writer: W,
+
+    /// List of HDLC special chars
+    s_char: SpecialChars,
+
+    /// Set once the opening FEND of the current frame was written
+    open: bool,
+
+    /// Escaped bytes of the current write call
+    scratch: Vec<u8>,
+
+    /// Separation of consecutive frames
+    pacer: Pacer,
+}
+
+impl<W: Write> EncodingWriter<W> {
+    /// Creates a new EncodingWriter instance.
+    ///
+    /// # Arguments
+    /// * `writer` - The sink frames are written to.
+    /// * `s_char` - The special characters used for HDLC encoding.
+    ///
+    /// # Error
+    ///
+    /// * **HDLCError::DuplicateSpecialChar**: Checks special characters for duplicates, if any of
+    ///   the `SpecialChars` are duplicate, throw an error.  Displays "Duplicate special character".
+    pub fn new(writer: W, s_char: SpecialChars) -> Result<Self, HDLCError> {
+        // Safety check to make sure the special character values are all unique
+        if s_char.has_duplicates() {
+            return Err(HDLCError::DuplicateSpecialChar);
+        }
+
+        Ok(EncodingWriter {
+            writer,
+            s_char,
+            open: false,
+            scratch: Vec::new(),
+            pacer: Pacer::default(),
+        })
+    }
+
+    /// Separates consecutive frames by `pacing`, applied when the next frame is opened.
+    pub fn pacing(mut self, pacing: Pacing) -> Self {
+        self.pacer = Pacer::new(pacing);
+        self
+    }
+
+    /// Writes the closing `fend` of the current frame and flushes the inner writer. A frame
+    /// nothing was written to is sent empty, as its two flags.
+    ///
+    /// # Error
+    ///
+    /// * Any error returned by the writer. The frame stays open, so finishing can be retried.
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.open()?;
+        self.writer.write_all(&[self.s_char.fend])?;
+        self.open = false;
+        self.pacer.after_frame();
+        self.writer.flush()
+    }
+
+    /// Returns true while a frame is open, between the first write and [`EncodingWriter::finish`].
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the inner writer. Writing to it directly corrupts an open
+    /// frame.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns the inner writer, without closing an open frame.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes the opening FEND unless the current frame already has it
+    fn open(&mut self) -> io::Result<()> {
+        if !self.open {
+            self.pacer
+                .before_frame(&mut self.writer, self.s_char.fend)?;
+            self.writer.write_all(&[self.s_char.fend])?;
+            self.open = true;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for EncodingWriter<W> {
+    /// Escapes all of `buf` into the current frame, opening it if needed.
+    ///
+    /// An escape sequence can't be split across calls, so the escaped bytes are written in full
+    /// before returning.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.open()?;
+
+        self.scratch.clear();
+        // Escaping into a Vec can't fail
+        let _ = escape_unchecked(buf, &self.s_char, &mut self.scratch);
+        self.writer.write_all(&self.scratch)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,235 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{
+        encode, Arq, ArqConfig, ExponentialBackoff, FixedBackoff, HDLCError, LinearBackoff,
+        RetryPolicy, SpecialChars,
+    };
+
+    fn pair(config: ArqConfig) -> (Arq, Arq) {
+        let chars = SpecialChars::default();
+        (
+            Arq::new(chars.clone(), config).unwrap(),
+            Arq::new(chars, config).unwrap(),
+        )
+    }
+
+    /// Moves every pending frame from `from` to `to`, dropping those `lose` picks
+    fn shuttle(from: &mut Arq, to: &mut Arq, now: u64, lose: &mut impl FnMut() -> bool) {
+        while let Some(frame) = from.poll_transmit() {
+            if !lose() {
+                to.receive(&frame, now).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn stop_and_wait_delivers_in_order() {
+        let (mut alice, mut bob) = pair(ArqConfig::default());
+        for byte in 0..3 {
+            alice.send(&[byte], 0).unwrap();
+        }
+        assert_eq!(alice.in_flight(), 1);
+        assert_eq!(alice.backlog(), 2);
+
+        let mut never = || false;
+        for now in 0..3 {
+            shuttle(&mut alice, &mut bob, now, &mut never);
+            shuttle(&mut bob, &mut alice, now, &mut never);
+        }
+
+        let received: Vec<Vec<u8>> = std::iter::from_fn(|| bob.recv()).collect();
+        assert_eq!(received, vec![vec![0], vec![1], vec![2]]);
+        assert_eq!(alice.in_flight(), 0);
+        assert_eq!(alice.next_timeout(), None);
+    }
+
+    #[test]
+    fn lossy_link_delivers_every_payload_once() {
+        let config = ArqConfig {
+            timeout: 10,
+            retries: 20,
+            window: 4,
+        };
+        let (mut alice, mut bob) = pair(config);
+        let payloads: Vec<Vec<u8>> = (0..=255u8).map(|b| vec![b, 0x7E, b]).collect();
+        for payload in &payloads {
+            alice.send(payload, 0).unwrap();
+        }
+
+        // Drop about a quarter of the frames in either direction
+        let mut state = 0x2545_F491u32;
+        let mut lose = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state & 3 == 0
+        };
+        let mut received = Vec::new();
+        for now in 0..10_000 {
+            alice.poll(now).unwrap();
+            shuttle(&mut alice, &mut bob, now, &mut lose);
+            shuttle(&mut bob, &mut alice, now, &mut lose);
+            received.extend(std::iter::from_fn(|| bob.recv()));
+            if received.len() == payloads.len() && alice.in_flight() == 0 {
+                break;
+            }
+        }
+
+        assert_eq!(received, payloads);
+        assert_eq!(alice.backlog(), 0);
+        assert_eq!(alice.in_flight(), 0);
+    }
+
+    #[test]
+    fn duplicates_are_suppressed_and_acknowledged_again() {
+        let (mut alice, mut bob) = pair(ArqConfig::default());
+        alice.send(&[0x01], 0).unwrap();
+        let frame = alice.poll_transmit().unwrap();
+
+        bob.receive(&frame, 0).unwrap();
+        bob.receive(&frame, 1).unwrap();
+
+        assert_eq!(bob.recv(), Some(vec![0x01]));
+        assert_eq!(bob.recv(), None);
+        let ack = bob.poll_transmit().unwrap();
+        assert_eq!(bob.poll_transmit(), Some(ack.clone()));
+        assert_eq!(ack, vec![0x7E, 0x81, 0x7E]);
+    }
+
+    #[test]
+    fn retries_exhausted_after_timeouts() {
+        let config = ArqConfig {
+            timeout: 5,
+            retries: 2,
+            window: 1,
+        };
+        let (mut alice, _) = pair(config);
+        alice.send(&[0x01], 0).unwrap();
+        let frame = alice.poll_transmit().unwrap();
+
+        assert_eq!(alice.poll(4), Ok(()));
+        assert_eq!(alice.poll_transmit(), None);
+        assert_eq!(alice.next_timeout(), Some(5));
+        assert_eq!(alice.poll(5), Ok(()));
+        assert_eq!(alice.poll_transmit(), Some(frame.clone()));
+        assert_eq!(alice.poll(10), Ok(()));
+        assert_eq!(alice.poll_transmit(), Some(frame));
+        assert_eq!(alice.poll(15), Err(HDLCError::RetriesExhausted));
+
+        alice.reset();
+        assert_eq!(alice.in_flight(), 0);
+        assert_eq!(alice.poll(20), Ok(()));
+    }
+
+    #[test]
+    fn malformed_frames_are_reported() {
+        let chars = SpecialChars::default();
+        let (mut alice, _) = pair(ArqConfig::default());
+
+        assert_eq!(
+            alice.receive(&[0x7E, 0x7E], 0),
+            Err(HDLCError::Incomplete { needed: Some(1) })
+        );
+        assert_eq!(
+            alice.receive(&[0x7E, 0x7D, 0x01, 0x7E], 0),
+            Err(HDLCError::MissingTradeChar { offset: 1 })
+        );
+        // A stale acknowledgement is ignored
+        alice.send(&[0x01], 0).unwrap();
+        alice
+            .receive(&encode(&[0x80 | 0x40], chars.clone()).unwrap(), 0)
+            .unwrap();
+        assert_eq!(alice.in_flight(), 1);
+        assert!(Arq::new(
+            SpecialChars::new(0x7E, 0x7E, 0x5E, 0x5D),
+            ArqConfig::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn fixed_and_linear_backoff() {
+        let mut fixed = FixedBackoff {
+            timeout: 100,
+            retries: 2,
+        };
+        let timeouts: Vec<_> = (0..4).map(|attempt| fixed.timeout(attempt)).collect();
+        assert_eq!(timeouts, vec![Some(100), Some(100), Some(100), None]);
+
+        let mut linear = LinearBackoff {
+            initial: 100,
+            step: 50,
+            max: 180,
+            retries: 3,
+        };
+        let timeouts: Vec<_> = (0..5).map(|attempt| linear.timeout(attempt)).collect();
+        assert_eq!(
+            timeouts,
+            vec![Some(100), Some(150), Some(180), Some(180), None]
+        );
+    }
+
+    #[test]
+    fn exponential_backoff_with_jitter() {
+        let mut plain = ExponentialBackoff::new(100, 1000, 5);
+        let timeouts: Vec<_> = (0..7).map(|attempt| plain.timeout(attempt)).collect();
+        assert_eq!(
+            timeouts,
+            vec![
+                Some(100),
+                Some(200),
+                Some(400),
+                Some(800),
+                Some(1000),
+                Some(1000),
+                None
+            ]
+        );
+        assert_eq!(plain.timeout(200), None);
+
+        let mut jittered = ExponentialBackoff::new(1000, u64::MAX, 100).jitter(0.5, 7);
+        let mut replay = ExponentialBackoff::new(1000, u64::MAX, 100).jitter(0.5, 7);
+        for attempt in 0..100 {
+            let full = 1000u64.saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX));
+            let timeout = jittered.timeout(attempt).unwrap();
+            assert!(
+                timeout <= full && timeout >= full / 2,
+                "attempt {}",
+                attempt
+            );
+            assert_eq!(replay.timeout(attempt), Some(timeout));
+        }
+    }
+
+    #[test]
+    fn arq_follows_policy() {
+        let chars = SpecialChars::default();
+        let policy = ExponentialBackoff::new(100, 1000, 2);
+        let mut arq = Arq::with_policy(chars, ArqConfig::default(), policy).unwrap();
+
+        arq.send(&[0x01], 0).unwrap();
+        assert!(arq.poll_transmit().is_some());
+        assert_eq!(arq.next_timeout(), Some(100));
+
+        assert_eq!(arq.poll(99), Ok(()));
+        assert!(arq.poll_transmit().is_none());
+        assert_eq!(arq.poll(100), Ok(()));
+        assert!(arq.poll_transmit().is_some());
+        assert_eq!(arq.next_timeout(), Some(300));
+
+        assert_eq!(arq.poll(300), Ok(()));
+        assert_eq!(arq.next_timeout(), Some(700));
+        assert_eq!(arq.poll(700), Err(HDLCError::RetriesExhausted));
+    }
+
+    #[test]
+    fn arq_takes_closure_policy() {
+        let chars = SpecialChars::default();
+        let policy = |attempt: u32| (attempt == 0).then_some(10);
+        let mut arq = Arq::with_policy(chars, ArqConfig::default(), policy).unwrap();
+
+        arq.send(&[0x01], 5).unwrap();
+        assert_eq!(arq.next_timeout(), Some(15));
+        assert_eq!(arq.poll(15), Err(HDLCError::RetriesExhausted));
+    }
+}
@@ -0,0 +1,34 @@
+#[cfg(all(test, feature = "rayon"))]
+mod tests {
+    use hdlc::{decode_batch, encode, encode_batch, HDLCError, SpecialChars, FEND, FESC};
+
+    #[test]
+    fn batch_keeps_order() {
+        let chars = SpecialChars::default();
+        let payloads: Vec<Vec<u8>> = (0..1000u32).map(|i| i.to_le_bytes().to_vec()).collect();
+
+        let frames = encode_batch(&payloads, chars.clone()).unwrap();
+        let decoded = decode_batch(&frames, chars.clone());
+
+        assert_eq!(frames[126], encode(&payloads[126], chars).unwrap());
+        assert_eq!(decoded, payloads.into_iter().map(Ok).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn batch_reports_errors() {
+        let frames = [&[FEND, 0x01, FEND][..], &[FEND, FESC, 0x00, FEND]];
+        let dupes = SpecialChars::new(FEND, FEND, 0x00, 0x01);
+
+        assert_eq!(
+            decode_batch(&frames, SpecialChars::default()),
+            vec![
+                Ok(vec![0x01]),
+                Err(HDLCError::MissingTradeChar { offset: 1 })
+            ]
+        );
+        assert_eq!(
+            encode_batch(&[[0x01]], dupes),
+            Err(HDLCError::DuplicateSpecialChar)
+        );
+    }
+}
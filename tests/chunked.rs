@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{encode, ChunkedDecoder, DecodeEvent, HDLCError, SpecialChars};
+
+    /// Feeds `msg` in pieces of `size` bytes, collecting the completed frames and errors
+    fn feed_all(msg: &[u8], size: usize) -> Vec<Result<Vec<u8>, HDLCError>> {
+        let mut decoder = ChunkedDecoder::new(SpecialChars::default()).unwrap();
+        let mut frames = Vec::new();
+        let mut frame = Vec::new();
+        for piece in msg.chunks(size) {
+            decoder.feed(piece, |event| match event {
+                DecodeEvent::Data(chunk) => frame.extend_from_slice(chunk),
+                DecodeEvent::End => frames.push(Ok(std::mem::take(&mut frame))),
+                DecodeEvent::Error(e) => {
+                    frame.clear();
+                    frames.push(Err(e));
+                }
+            });
+        }
+        frames
+    }
+
+    #[test]
+    fn chunked_decoder_any_split() {
+        let payload: Vec<u8> = (0..=255).cycle().take(5000).collect();
+        let mut msg = encode(&payload, SpecialChars::default()).unwrap();
+        msg.extend(encode(&[0x01], SpecialChars::default()).unwrap());
+
+        for size in [1, 2, 7, 1024, msg.len()] {
+            assert_eq!(
+                feed_all(&msg, size),
+                vec![Ok(payload.clone()), Ok(vec![0x01])]
+            );
+        }
+    }
+
+    #[test]
+    fn chunked_decoder_skips_garbage_and_empty_frames() {
+        let msg = [0x01, 0x7D, 0x7E, 0x7E, 0x7E, 0x02, 0x7E];
+
+        assert_eq!(feed_all(&msg, 3), vec![Ok(vec![0x02])]);
+    }
+
+    #[test]
+    fn chunked_decoder_reports_bad_escape() {
+        let msg = [0x7E, 0x01, 0x7D, 0x02, 0x03, 0x7E, 0x7E, 0x04, 0x7E];
+
+        assert_eq!(
+            feed_all(&msg, 2),
+            vec![
+                Err(HDLCError::MissingTradeChar { offset: 2 }),
+                Ok(vec![0x04])
+            ]
+        );
+    }
+
+    #[test]
+    fn chunked_decoder_rejects_duplicate_chars() {
+        let chars = SpecialChars::new(0x7E, 0x7E, 0x5E, 0x5D);
+
+        assert_eq!(
+            ChunkedDecoder::new(chars).err(),
+            Some(HDLCError::DuplicateSpecialChar)
+        );
+    }
+}
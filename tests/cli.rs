@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "cli")]
+    use std::io::Write;
+    #[cfg(feature = "cli")]
+    use std::process::{Command, Output, Stdio};
+
+    #[cfg(feature = "cli")]
+    fn hdlc(args: &[&str], stdin: &[u8]) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_hdlc"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(stdin).unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn encodes_hex_lines() {
+        let output = hdlc(&["encode", "--hex"], b"01 7E 02\n0x0A0B\n");
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"7E 01 7D 5E 02 7E\n7E 0A 0B 7E\n");
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn decodes_binary_stream() {
+        let frames = [0x7E, 0x61, 0x7D, 0x5E, 0x7E, 0x7E, 0x62, 0x7E];
+
+        let output = hdlc(&["decode"], &frames);
+        assert!(output.status.success());
+        assert_eq!(output.stdout, [0x61, 0x7E, 0x62]);
+
+        let output = hdlc(&["decode", "--hex"], &frames);
+        assert_eq!(output.stdout, b"61 7E\n62\n");
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn verifies_fcs_round_trip() {
+        let frame = hdlc(&["encode", "--fcs", "crc32"], b"abc").stdout;
+
+        let output = hdlc(&["verify", "--fcs", "crc32"], &frame);
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"frame 0: ok, 3 byte payload\n");
+
+        let output = hdlc(&["verify", "--fcs", "crc16"], &frame);
+        assert_eq!(output.status.code(), Some(1));
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn dumps_with_custom_chars() {
+        let output = hdlc(
+            &["--chars", "71,70,51,50", "dump"],
+            &[0x71, 0x70, 0x51, 0x71],
+        );
+
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "frame 0, 4 bytes\n0000: [71] <70> <51> [71]\n"
+        );
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn rejects_bad_hex() {
+        let output = hdlc(&["encode", "--hex"], b"zz\n");
+
+        assert_eq!(output.status.code(), Some(2));
+        assert!(!output.stderr.is_empty());
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn rejects_duplicate_chars() {
+        let output = hdlc(&["--chars", "7E,7E,5E,5D", "dump"], &[0x7E, 0x01, 0x7E]);
+
+        assert_eq!(output.status.code(), Some(2));
+        assert!(!output.stderr.is_empty());
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn reports_read_errors() {
+        // Opening a directory works, reading it fails
+        let output = hdlc(&["dump", env!("CARGO_MANIFEST_DIR")], &[]);
+
+        assert_eq!(output.status.code(), Some(2));
+        assert!(!output.stderr.is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use hdlc::{Decoder, FrameReader, HDLCError, SpecialChars, SystemClock, TimedFrame};
+
+    #[test]
+    fn decoder_stamps_frames_in_order() {
+        let mut ticks = 0;
+        let mut clock = || {
+            ticks += 1;
+            ticks
+        };
+
+        let mut decoder = Decoder::new(SpecialChars::default()).unwrap();
+        decoder.push(&[0x7E, 0x01, 0x7E, 0x7E, 0x02, 0x7E]);
+
+        assert_eq!(
+            decoder.next_frame_timed(&mut clock),
+            Some(Ok(TimedFrame {
+                frame: vec![0x01],
+                received: 1
+            }))
+        );
+        assert_eq!(
+            decoder.next_frame_timed(&mut clock),
+            Some(Ok(TimedFrame {
+                frame: vec![0x02],
+                received: 2
+            }))
+        );
+        assert_eq!(decoder.next_frame_timed(&mut clock), None);
+    }
+
+    #[test]
+    fn decoder_doesnt_read_clock_for_malformed_frame() {
+        let mut reads = 0;
+        let mut clock = || reads += 1;
+
+        let mut decoder = Decoder::new(SpecialChars::default()).unwrap();
+        decoder.push(&[0x7E, 0x01, 0x7D, 0x02, 0x7E]);
+
+        assert_eq!(
+            decoder.next_frame_timed(&mut clock),
+            Some(Err(HDLCError::MissingTradeChar { offset: 2 }))
+        );
+        assert_eq!(reads, 0);
+    }
+
+    #[test]
+    fn reader_stamps_frames_with_system_clock() {
+        let mut data = Cursor::new(vec![0x7E, 0x01, 0x7E, 0x7E, 0x02, 0x7E]);
+        let mut reader = FrameReader::new(&mut data, SpecialChars::default());
+
+        let first = reader.read_frame_timed(&mut SystemClock).unwrap();
+        let second = reader.read_frame_timed(&mut SystemClock).unwrap();
+
+        assert_eq!(first.frame, vec![0x7E, 0x01, 0x7E]);
+        assert_eq!(second.frame, vec![0x7E, 0x02, 0x7E]);
+        assert!(second.received >= first.received);
+        assert_eq!(reader.read_frame_timed(&mut SystemClock), None);
+    }
+}
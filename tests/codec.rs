@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{
+        DecodeOptions, Decoder, EscapeMap, FcsMode, FrameCodec, Framing, HDLCError, HdlcCodec,
+        SpecialChars,
+    };
+
+    /// SLIP framing without escapes, enough to stand for another codec
+    #[derive(Default)]
+    struct Slip {
+        rest: Vec<u8>,
+    }
+
+    impl FrameCodec for Slip {
+        fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+            let mut frame = payload.to_vec();
+            frame.push(0xC0);
+            Ok(frame)
+        }
+
+        fn push(&mut self, data: &[u8]) {
+            self.rest.extend_from_slice(data);
+        }
+
+        fn next_frame(&mut self) -> Option<Result<Vec<u8>, HDLCError>> {
+            let end = self.rest.iter().position(|&b| b == 0xC0)?;
+            let frame = self.rest.drain(..=end).take(end).collect();
+            Some(Ok(frame))
+        }
+    }
+
+    #[test]
+    fn selects_codec_at_runtime() {
+        let mut ports: Vec<Box<dyn FrameCodec>> = ["hdlc", "slip"]
+            .iter()
+            .map(|&framing| -> Box<dyn FrameCodec> {
+                match framing {
+                    "hdlc" => Box::new(Decoder::new(SpecialChars::default()).unwrap()),
+                    _ => Box::new(Slip::default()),
+                }
+            })
+            .collect();
+
+        for port in &mut ports {
+            let frame = port.encode(&[0x01, 0x02]).unwrap();
+            port.push(&frame[..1]);
+            port.push(&frame[1..]);
+            assert_eq!(port.next_frame(), Some(Ok(vec![0x01, 0x02])));
+            assert_eq!(port.next_frame(), None);
+        }
+    }
+
+    #[test]
+    fn encodes_with_decoder_options() {
+        let options = DecodeOptions {
+            fcs: FcsMode::Crc16,
+            ..DecodeOptions::default()
+        };
+        let mut codec: Box<dyn FrameCodec> =
+            Box::new(Decoder::with_options(SpecialChars::default(), options).unwrap());
+
+        let frame = codec.encode(b"123456789").unwrap();
+        assert_eq!(&frame[10..], &[0x6E, 0x90, 0x7E]);
+
+        codec.push(&frame);
+        assert_eq!(codec.next_frame(), Some(Ok(b"123456789".to_vec())));
+    }
+
+    #[test]
+    fn rejects_duplicate_chars() {
+        let chars = SpecialChars::new(0x7E, 0x7E, 0x5E, 0x5D);
+
+        assert_eq!(
+            HdlcCodec::new(chars).err(),
+            Some(HDLCError::DuplicateSpecialChar)
+        );
+    }
+
+    #[test]
+    fn rejects_colliding_escape_map() {
+        let codec = HdlcCodec::new(SpecialChars::default())
+            .unwrap()
+            .escape_map(EscapeMap::new().with(0x5E));
+
+        assert_eq!(codec.err(), Some(HDLCError::DuplicateSpecialChar));
+    }
+
+    #[test]
+    fn shares_configuration_between_directions() {
+        let chars = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+        let mut codec = HdlcCodec::new(chars)
+            .unwrap()
+            .escape_map(EscapeMap::new().with(0x13))
+            .unwrap()
+            .fcs(FcsMode::Crc32)
+            .framing(Framing::TerminatorOnly);
+
+        let frame = codec.encode(&[0x13, 0x71, 0x01]).unwrap();
+        assert_eq!(&frame[..5], &[0x70, 0x33, 0x70, 0x51, 0x01]);
+        assert_eq!(frame.last(), Some(&0x71));
+        assert_eq!(frame.len(), 10);
+
+        assert_eq!(codec.decode(&frame), Ok(vec![0x13, 0x71, 0x01]));
+        codec.push(&frame);
+        codec.push(&frame);
+        assert_eq!(codec.next_frame(), Some(Ok(vec![0x13, 0x71, 0x01])));
+        assert_eq!(codec.next_frame(), Some(Ok(vec![0x13, 0x71, 0x01])));
+        assert_eq!(codec.next_frame(), None);
+    }
+
+    #[test]
+    fn applies_length_limits_both_ways() {
+        let codec = HdlcCodec::new(SpecialChars::default())
+            .unwrap()
+            .max_payload_len(2);
+        assert_eq!(
+            codec.encode(&[0x01, 0x02, 0x03]),
+            Err(HDLCError::PayloadTooLarge)
+        );
+        assert_eq!(
+            codec.decode(&[0x7E, 0x01, 0x02, 0x03, 0x7E]),
+            Err(HDLCError::PayloadTooLarge)
+        );
+
+        let mut codec = HdlcCodec::new(SpecialChars::default())
+            .unwrap()
+            .max_frame_len(5);
+        assert_eq!(
+            codec.encode(&[0x7E, 0x01, 0x02]),
+            Err(HDLCError::FrameTooLong)
+        );
+        assert!(codec.encode(&[0x01, 0x02, 0x03]).is_ok());
+
+        codec.push(&[0x7E, 0x7D, 0x5E, 0x01, 0x02, 0x7E, 0x7E, 0x03, 0x7E]);
+        assert_eq!(codec.next_frame(), Some(Ok(vec![0x03])));
+    }
+
+    #[test]
+    fn reports_fcs_mismatch() {
+        let codec = HdlcCodec::new(SpecialChars::default())
+            .unwrap()
+            .fcs(FcsMode::Crc16);
+        let mut frame = codec.encode(&[0x01]).unwrap();
+        frame[1] = 0x02;
+
+        assert_eq!(codec.decode(&frame), Err(HDLCError::FcsMismatch));
+    }
+
+    #[test]
+    fn is_frame_codec() {
+        let mut codec: Box<dyn FrameCodec> = Box::new(
+            HdlcCodec::new(SpecialChars::default())
+                .unwrap()
+                .fcs(FcsMode::Crc16),
+        );
+
+        let frame = codec.encode(&[0x01]).unwrap();
+        codec.push(&frame);
+        assert_eq!(codec.next_frame(), Some(Ok(vec![0x01])));
+    }
+}
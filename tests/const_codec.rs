@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{decode, encode, Codec, HDLCError, SliceOutput, SpecialChars, FEND, FESC};
+
+    type Standard = Codec<0x7E, 0x7D, 0x5E, 0x5D>;
+    type Custom = Codec<0x71, 0x70, 0x51, 0x50>;
+
+    #[test]
+    fn matches_runtime_codec() {
+        let data = [0x01, FEND, 0x02, FESC, FESC, FEND, 0x03];
+        let frame = Standard::new().encode(&data);
+
+        assert_eq!(frame, encode(&data, SpecialChars::default()).unwrap());
+        assert_eq!(Standard::new().decode(&frame), Ok(data.to_vec()));
+        assert_eq!(Standard::CHARS, SpecialChars::default());
+    }
+
+    #[test]
+    fn encodes_custom_chars() {
+        let chars = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+        let frame = Custom::new().encode(&[0x71, 0x7E, 0x70]);
+
+        assert_eq!(frame, vec![0x71, 0x70, 0x51, 0x7E, 0x70, 0x50, 0x71]);
+        assert_eq!(decode(&frame, chars), Ok(vec![0x71, 0x7E, 0x70]));
+    }
+
+    #[test]
+    fn reports_malformed_frames_like_decode() {
+        let codec = Standard::new();
+        let frames: [&[u8]; 5] = [
+            &[0x01, FEND],
+            &[FEND, 0x01, FEND, 0x02, FEND],
+            &[FEND, FESC, 0x01, FEND],
+            &[FEND, 0x01],
+            &[FEND, FESC],
+        ];
+
+        for frame in frames {
+            assert_eq!(
+                codec.decode(frame),
+                decode(frame, SpecialChars::default()),
+                "{:02X?}",
+                frame
+            );
+        }
+        assert_eq!(
+            codec.decode(&[FEND, FESC, 0x01, FEND]),
+            Err(HDLCError::MissingTradeChar { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn encodes_into_slice() {
+        let mut buf = [0; 4];
+        let mut out = SliceOutput::new(&mut buf);
+        assert_eq!(
+            Standard::new().encode_into(&[0x01, 0x7E], &mut out),
+            Err(HDLCError::BufferTooSmall)
+        );
+
+        let mut buf = [0; 5];
+        let mut out = SliceOutput::new(&mut buf);
+        assert_eq!(Standard::new().encode_into(&[0x01, 0x7E], &mut out), Ok(5));
+        assert_eq!(buf, [0x7E, 0x01, 0x7D, 0x5E, 0x7E]);
+    }
+}
@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read};
+
+    use hdlc::{
+        spawn_decoder, DecodeOptions, Decoder, DecoderState, FcsMode, FrameCodec, HDLCError,
+        SpecialChars, FEND, FESC, TFEND,
+    };
+
+    /// Non-blocking reader handing out one chunk per `read` call, `None` meaning `WouldBlock`
+    struct NonBlockingReader {
+        chunks: Vec<Option<Vec<u8>>>,
+    }
+
+    impl Read for NonBlockingReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.chunks.is_empty() {
+                return Ok(0);
+            }
+            match self.chunks.remove(0) {
+                Some(chunk) => {
+                    buf[..chunk.len()].copy_from_slice(&chunk);
+                    Ok(chunk.len())
+                }
+                None => Err(io::ErrorKind::WouldBlock.into()),
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_pushed_bytes() {
+        let mut decoder = Decoder::new(SpecialChars::default()).unwrap();
+
+        decoder.push(&[0x10, FEND, 0x01]);
+        assert_eq!(decoder.next_frame(), None);
+
+        decoder.push(&[FESC, TFEND, FEND, FEND, FESC, 0x00, FEND]);
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![0x01, FEND])));
+        assert_eq!(
+            decoder.next_frame(),
+            Some(Err(HDLCError::MissingTradeChar { offset: 1 }))
+        );
+        assert_eq!(decoder.next_frame(), None);
+    }
+
+    #[test]
+    fn rejects_duplicate_chars() {
+        let chars = SpecialChars::new(FEND, FEND, TFEND, 0x5D);
+
+        assert_eq!(
+            Decoder::new(chars.clone()).err(),
+            Some(HDLCError::DuplicateSpecialChar)
+        );
+        assert_eq!(
+            spawn_decoder(io::empty(), chars).err(),
+            Some(HDLCError::DuplicateSpecialChar)
+        );
+    }
+
+    #[test]
+    fn reads_until_would_block() {
+        let mut decoder = Decoder::new(SpecialChars::default()).unwrap();
+        let mut reader = NonBlockingReader {
+            chunks: vec![
+                Some(vec![FEND, 0x01, FEND, FEND]),
+                Some(vec![0x02]),
+                None,
+                Some(vec![0x03, FEND]),
+            ],
+        };
+
+        let frames = decoder.read_available(&mut reader).unwrap();
+        assert_eq!(frames, vec![Ok(vec![0x01])]);
+        assert!(!decoder.is_eof());
+
+        let frames = decoder.read_available(&mut reader).unwrap();
+        assert_eq!(frames, vec![Ok(vec![0x02, 0x03])]);
+        assert!(decoder.is_eof());
+    }
+
+    #[test]
+    fn decodes_on_worker_thread() {
+        let msg = vec![
+            FEND, 0x01, FEND, FEND, FESC, 0x00, FEND, FEND, 0x02, FEND, 0x03,
+        ];
+        let (frames, worker) =
+            spawn_decoder(io::Cursor::new(msg), SpecialChars::default()).unwrap();
+
+        let frames: Vec<Result<Vec<u8>, HDLCError>> = frames.iter().collect();
+
+        assert_eq!(
+            frames,
+            vec![
+                Ok(vec![0x01]),
+                Err(HDLCError::MissingTradeChar { offset: 1 }),
+                Ok(vec![0x02])
+            ]
+        );
+        assert!(worker.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn resumes_from_state() {
+        let chars = SpecialChars::default();
+        let mut decoder = Decoder::new(chars.clone()).unwrap();
+        decoder.push(&[0x10, FEND, 0x01, FESC]);
+        assert_eq!(decoder.next_frame(), None);
+
+        let state = decoder.state();
+        assert_eq!(
+            state,
+            DecoderState {
+                rest: vec![FEND, 0x01, FESC],
+                overflow: false,
+                eof: false,
+                partial_since: None,
+            }
+        );
+
+        let mut decoder = Decoder::from_state(chars, state).unwrap();
+        decoder.push(&[TFEND, FEND]);
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![0x01, FEND])));
+    }
+
+    #[test]
+    fn resumes_from_state_with_options() {
+        let chars = SpecialChars::default();
+        let options = DecodeOptions {
+            fcs: FcsMode::Crc16,
+            ..DecodeOptions::default()
+        };
+        let mut decoder = Decoder::with_options(chars.clone(), options)
+            .unwrap()
+            .max_partial_age(100);
+        let frame = decoder.encode(&[0x01, 0x02]).unwrap();
+        decoder.push_at(&frame[..3], 10);
+        assert_eq!(decoder.next_frame(), None);
+
+        let state = decoder.state();
+        assert_eq!(state.partial_since, Some(10));
+
+        let mut decoder = Decoder::from_state_with_options(chars.clone(), options, state.clone())
+            .unwrap()
+            .max_partial_age(100);
+        decoder.push(&frame[3..]);
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![0x01, 0x02])));
+
+        // The FCS is still checked after the restart
+        let mut corrupt = frame.clone();
+        corrupt[1] ^= 0x01;
+        decoder.push(&corrupt);
+        assert_eq!(decoder.next_frame(), Some(Err(HDLCError::FcsMismatch)));
+
+        // So is the age of the restored partial frame
+        let mut decoder = Decoder::from_state_with_options(chars, options, state)
+            .unwrap()
+            .max_partial_age(100);
+        assert_eq!(decoder.expire_partial(111), 3);
+    }
+
+    #[test]
+    fn expires_stale_partial_frame() {
+        let mut decoder = Decoder::new(SpecialChars::default())
+            .unwrap()
+            .max_partial_age(100);
+
+        // The closing FEND of the first frame is lost
+        decoder.push_at(&[FEND, 0x01, 0x02], 0);
+        assert_eq!(decoder.next_frame(), None);
+        assert_eq!(decoder.expire_partial(100), 0);
+        assert_eq!(decoder.expire_partial(101), 3);
+
+        decoder.push_at(&[FEND, 0x03, FEND], 150);
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![0x03])));
+    }
+
+    #[test]
+    fn keeps_partial_frame_without_max_age() {
+        let mut decoder = Decoder::new(SpecialChars::default()).unwrap();
+        decoder.push_at(&[FEND, 0x01], 0);
+
+        assert_eq!(decoder.expire_partial(u64::MAX), 0);
+        decoder.push(&[FEND]);
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![0x01])));
+    }
+
+    #[test]
+    fn times_pushed_partial_frame_from_first_check() {
+        let mut decoder = Decoder::new(SpecialChars::default())
+            .unwrap()
+            .max_partial_age(10);
+
+        decoder.push(&[FEND, 0x01, FEND, FEND, 0x02]);
+        // A complete frame is buffered
+        assert_eq!(decoder.expire_partial(50), 0);
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![0x01])));
+
+        assert_eq!(decoder.expire_partial(60), 0);
+        assert_eq!(decoder.expire_partial(70), 0);
+        assert_eq!(decoder.expire_partial(71), 2);
+        assert_eq!(decoder.state().rest, Vec::<u8>::new());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_state() {
+        let mut decoder = Decoder::new(SpecialChars::default()).unwrap();
+        decoder.push(&[FEND, 0x01]);
+
+        let json = serde_json::to_string(&decoder.state()).unwrap();
+        let state: DecoderState = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(state, decoder.state());
+    }
+}
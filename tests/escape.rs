@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{
+        decode, decode_with_policy, encode, encode_with_policy, EscapeMap, EscapePolicy, HDLCError,
+        SpecialChars, FEND, FESC,
+    };
+
+    /// Escapes the upper half of the byte range as well by flipping the top bit, but for the
+    /// bytes that would collide with the translations of `fend` and `fesc`
+    struct HighBit;
+
+    impl EscapePolicy for HighBit {
+        fn escape(&self, byte: u8) -> Option<u8> {
+            match byte {
+                FEND | FESC => Some(byte ^ 0x20),
+                0xDD | 0xDE | 0xFD | 0xFE => None,
+                0x80..=0xFF => Some(byte ^ 0x80),
+                _ => None,
+            }
+        }
+
+        fn unescape(&self, translated: u8) -> Option<u8> {
+            match translated {
+                0x5D | 0x5E => Some(translated ^ 0x20),
+                FEND | FESC => None,
+                _ => Some(translated ^ 0x80),
+            }
+        }
+    }
+
+    /// Leaves `fend` unescaped
+    struct Broken;
+
+    impl EscapePolicy for Broken {
+        fn escape(&self, byte: u8) -> Option<u8> {
+            (byte == FESC).then_some(0x5D)
+        }
+
+        fn unescape(&self, translated: u8) -> Option<u8> {
+            (translated == 0x5D).then_some(FESC)
+        }
+    }
+
+    /// Escapes every control character by flipping bit 6, which turns 0x3E into `fend`
+    struct Bit6;
+
+    impl EscapePolicy for Bit6 {
+        fn escape(&self, byte: u8) -> Option<u8> {
+            matches!(byte, 0x00..=0x1F | 0x3E | FEND | FESC).then_some(byte ^ 0x40)
+        }
+
+        fn unescape(&self, translated: u8) -> Option<u8> {
+            Some(translated ^ 0x40)
+        }
+    }
+
+    #[test]
+    fn special_chars_are_default_policy() {
+        let chars = SpecialChars::new(0x71, 0x70, 0x51, 0x50)
+            .with_escape_map(EscapeMap::new().with(0x11))
+            .unwrap();
+        let data = [0x71, 0x11, 0x70, 0x7E, 0x01];
+
+        let frame = encode_with_policy(&data, &chars).unwrap();
+        assert_eq!(frame, encode(&data, chars.clone()).unwrap());
+        assert_eq!(decode_with_policy(&frame, &chars), decode(&frame, chars));
+    }
+
+    #[test]
+    fn round_trips_custom_policy() {
+        let data = [0x01, 0x81, FEND, 0xFE, FESC, 0xFF];
+        let frame = encode_with_policy(&data, &HighBit).unwrap();
+
+        assert_eq!(
+            frame,
+            vec![FEND, 0x01, FESC, 0x01, FESC, 0x5E, 0xFE, FESC, 0x5D, FESC, 0x7F, FEND]
+        );
+        assert_eq!(decode_with_policy(&frame, &HighBit), Ok(data.to_vec()));
+    }
+
+    #[test]
+    fn accepts_boxed_policy() {
+        let policy: Box<dyn EscapePolicy> = Box::new(HighBit);
+        let frame = encode_with_policy(&[0x80], &policy).unwrap();
+
+        assert_eq!(frame, vec![FEND, FESC, 0x00, FEND]);
+    }
+
+    #[test]
+    fn reports_malformed_frames() {
+        assert_eq!(
+            decode_with_policy(&[0x01, FEND], &HighBit),
+            Err(HDLCError::MissingFirstFend)
+        );
+        assert_eq!(
+            decode_with_policy(&[FEND, 0x01, FEND, 0x02, FEND], &HighBit),
+            Err(HDLCError::FendCharInData { offset: 2 })
+        );
+        assert_eq!(
+            decode_with_policy(&[FEND, 0x01, FESC, FESC, FEND], &HighBit),
+            Err(HDLCError::MissingTradeChar { offset: 2 })
+        );
+        assert_eq!(
+            decode_with_policy(&[FEND, 0x01, FESC], &HighBit),
+            Err(HDLCError::Incomplete { needed: Some(2) })
+        );
+        assert_eq!(
+            decode_with_policy(&[FEND, 0x01], &HighBit),
+            Err(HDLCError::Incomplete { needed: Some(1) })
+        );
+    }
+
+    #[test]
+    fn rejects_policy_leaving_fend_unescaped() {
+        assert_eq!(
+            encode_with_policy(&[0x01], &Broken),
+            Err(HDLCError::DuplicateSpecialChar)
+        );
+    }
+
+    #[test]
+    fn rejects_translation_into_fend() {
+        assert_eq!(
+            encode_with_policy(&[0x01, FEND], &Bit6),
+            Ok(vec![FEND, FESC, 0x41, FESC, 0x3E, FEND])
+        );
+        assert_eq!(
+            encode_with_policy(&[0x01, 0x3E], &Bit6),
+            Err(HDLCError::DuplicateSpecialChar)
+        );
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{DecodeOptions, EventDecoder, FrameEvents, HDLCError, SpecialChars, FEND, FESC};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Frame(Vec<u8>),
+        Error(HDLCError, u64),
+        Resync(usize),
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<Event>,
+    }
+
+    impl FrameEvents for Recorder {
+        fn on_frame(&mut self, payload: &[u8]) {
+            self.events.push(Event::Frame(payload.to_vec()));
+        }
+
+        fn on_error(&mut self, error: HDLCError, offset: u64) {
+            self.events.push(Event::Error(error, offset));
+        }
+
+        fn on_resync(&mut self, discarded: usize) {
+            self.events.push(Event::Resync(discarded));
+        }
+    }
+
+    #[test]
+    fn reports_frames_across_feeds() {
+        let mut decoder = EventDecoder::new(SpecialChars::default(), Recorder::default());
+
+        decoder.feed(&[FEND, 0x01]);
+        assert!(decoder.handler().events.is_empty());
+
+        decoder.feed(&[0x02, FEND, FEND, 0x03, FEND]);
+        assert_eq!(
+            decoder.into_handler().events,
+            vec![Event::Frame(vec![0x01, 0x02]), Event::Frame(vec![0x03])]
+        );
+    }
+
+    #[test]
+    fn reports_errors_with_stream_offset() {
+        let mut recorder = Recorder::default();
+        let mut decoder = EventDecoder::new(SpecialChars::default(), &mut recorder);
+
+        decoder.feed(&[FEND, 0x01, FEND]);
+        decoder.feed(&[FEND, 0x02, FESC, 0x00, FEND]);
+        decoder.feed(&[FEND, 0x03, FEND]);
+
+        assert_eq!(
+            recorder.events,
+            vec![
+                Event::Frame(vec![0x01]),
+                Event::Error(HDLCError::MissingTradeChar { offset: 2 }, 3),
+                Event::Frame(vec![0x03]),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_resyncs() {
+        let options = DecodeOptions {
+            max_len: Some(4),
+            ..DecodeOptions::default()
+        };
+        let mut decoder =
+            EventDecoder::with_options(SpecialChars::default(), options, Recorder::default());
+
+        decoder.feed(&[0x10, 0x11, FEND, 0x01, FEND, FEND, FEND]);
+        decoder.feed(&[FEND, 0x01, 0x02, 0x03, 0x04, 0x05, FEND, FEND, 0x06, FEND]);
+
+        assert_eq!(
+            decoder.handler().events,
+            vec![
+                Event::Resync(2),
+                Event::Frame(vec![0x01]),
+                Event::Resync(7),
+                Event::Frame(vec![0x06]),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_errors_and_resyncs_by_default() {
+        struct Frames(usize);
+
+        impl FrameEvents for Frames {
+            fn on_frame(&mut self, _payload: &[u8]) {
+                self.0 += 1;
+            }
+        }
+
+        let mut decoder = EventDecoder::new(SpecialChars::default(), Frames(0));
+        decoder.feed(&[0x10, FEND, FESC, 0x00, FEND, FEND, 0x01, FEND]);
+
+        assert_eq!(decoder.handler().0, 1);
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ffi")]
+    use core::ptr;
+
+    #[cfg(feature = "ffi")]
+    use hdlc::ffi::*;
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn encodes_and_decodes() {
+        let mut frame = [0u8; 16];
+        let mut len = 0;
+        let code = unsafe {
+            hdlc_encode(
+                [0x01, 0x7E].as_ptr(),
+                2,
+                ptr::null(),
+                frame.as_mut_ptr(),
+                16,
+                &mut len,
+            )
+        };
+        assert_eq!(code, HDLC_OK);
+        assert_eq!(&frame[..len], &[0x7E, 0x01, 0x7D, 0x5E, 0x7E]);
+
+        let mut payload = [0u8; 16];
+        let mut payload_len = 0;
+        let code = unsafe {
+            hdlc_decode(
+                frame.as_ptr(),
+                len,
+                ptr::null(),
+                payload.as_mut_ptr(),
+                16,
+                &mut payload_len,
+            )
+        };
+        assert_eq!(code, HDLC_OK);
+        assert_eq!(&payload[..payload_len], &[0x01, 0x7E]);
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn reports_errors() {
+        let mut out = [0u8; 2];
+        let mut len = 0;
+
+        let code = unsafe {
+            hdlc_encode(
+                [0x7E].as_ptr(),
+                1,
+                ptr::null(),
+                out.as_mut_ptr(),
+                2,
+                &mut len,
+            )
+        };
+        assert_eq!(code, HDLC_ERR_BUFFER_TOO_SMALL);
+        assert_eq!(len, 4);
+
+        let frame = [0x7E, 0x7D, 0x01, 0x7E];
+        let code = unsafe {
+            hdlc_decode(
+                frame.as_ptr(),
+                4,
+                ptr::null(),
+                out.as_mut_ptr(),
+                2,
+                &mut len,
+            )
+        };
+        assert_eq!(code, HDLC_ERR_MISSING_TRADE_CHAR);
+
+        let chars = HdlcSpecialChars {
+            fend: 0x7E,
+            fesc: 0x7E,
+            tfend: 0x5E,
+            tfesc: 0x5D,
+        };
+        let code = unsafe { hdlc_encode(ptr::null(), 0, &chars, out.as_mut_ptr(), 2, &mut len) };
+        assert_eq!(code, HDLC_ERR_DUPLICATE_SPECIAL_CHAR);
+        assert!(unsafe { hdlc_decoder_new(&chars) }.is_null());
+
+        let code =
+            unsafe { hdlc_encode(ptr::null(), 1, ptr::null(), out.as_mut_ptr(), 2, &mut len) };
+        assert_eq!(code, HDLC_ERR_NULL);
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn decoder_streams_frames() {
+        unsafe {
+            let decoder = hdlc_decoder_new(ptr::null());
+            assert!(!decoder.is_null());
+
+            let mut out = [0u8; 8];
+            let mut len = 0;
+            let data = [0x7E, 0x01, 0x02, 0x03, 0x7E, 0x7E, 0x04];
+            assert_eq!(hdlc_decoder_push(decoder, data.as_ptr(), 4), HDLC_OK);
+            assert_eq!(
+                hdlc_decoder_next(decoder, out.as_mut_ptr(), 8, &mut len),
+                HDLC_NO_FRAME
+            );
+
+            assert_eq!(hdlc_decoder_push(decoder, data[4..].as_ptr(), 3), HDLC_OK);
+            assert_eq!(
+                hdlc_decoder_next(decoder, out.as_mut_ptr(), 2, &mut len),
+                HDLC_ERR_BUFFER_TOO_SMALL
+            );
+            assert_eq!(len, 3);
+            assert_eq!(
+                hdlc_decoder_next(decoder, out.as_mut_ptr(), 8, &mut len),
+                HDLC_OK
+            );
+            assert_eq!(&out[..len], &[0x01, 0x02, 0x03]);
+            assert_eq!(
+                hdlc_decoder_next(decoder, out.as_mut_ptr(), 8, &mut len),
+                HDLC_NO_FRAME
+            );
+
+            assert_eq!(hdlc_decoder_push(decoder, [0x7E].as_ptr(), 1), HDLC_OK);
+            assert_eq!(
+                hdlc_decoder_next(decoder, out.as_mut_ptr(), 8, &mut len),
+                HDLC_OK
+            );
+            assert_eq!(&out[..len], &[0x04]);
+
+            hdlc_decoder_free(decoder);
+            hdlc_decoder_free(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{FixedDecoder, FixedEncoder, HDLCError, SpecialChars, FEND, FESC, TFEND, TFESC};
+
+    /// Feeds every byte to the decoder, collecting the completed frames and errors
+    fn push_all<const N: usize>(
+        decoder: &mut FixedDecoder<N>,
+        msg: &[u8],
+    ) -> Vec<Result<Vec<u8>, HDLCError>> {
+        msg.iter()
+            .filter_map(|&byte| match decoder.push(byte) {
+                Ok(frame) => frame.map(|f| Ok(f.to_vec())),
+                Err(e) => Some(Err(e)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fixed_encoder_packs() {
+        let mut encoder: FixedEncoder<8> = FixedEncoder::new(SpecialChars::default());
+
+        assert_eq!(
+            encoder.encode(&[0x01, FESC, 0x02]),
+            Ok(&[FEND, 0x01, FESC, TFESC, 0x02, FEND][..])
+        );
+        assert_eq!(
+            encoder.encode(&[FEND, FEND, FEND, FEND]),
+            Err(HDLCError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn fixed_decoder_depacks() {
+        let mut decoder: FixedDecoder<8> = FixedDecoder::new(SpecialChars::default()).unwrap();
+        let msg = [
+            0x10, FEND, 0x01, FESC, TFEND, FEND, 0x33, FEND, FEND, 0x02, FEND,
+        ];
+
+        let frames = push_all(&mut decoder, &msg);
+
+        assert_eq!(frames, vec![Ok(vec![0x01, FEND]), Ok(vec![0x02])]);
+    }
+
+    #[test]
+    fn fixed_decoder_recovers_from_errors() {
+        let mut decoder: FixedDecoder<2> = FixedDecoder::new(SpecialChars::default()).unwrap();
+        let msg = [
+            FEND, 0x01, 0x02, 0x03, FEND, FEND, FESC, 0x00, FEND, FEND, 0x04, FEND,
+        ];
+
+        let frames = push_all(&mut decoder, &msg);
+
+        assert_eq!(
+            frames,
+            vec![
+                Err(HDLCError::BufferTooSmall),
+                Err(HDLCError::MissingTradeChar { offset: 1 }),
+                Ok(vec![0x04])
+            ]
+        );
+    }
+
+    #[test]
+    fn fixed_decoder_rejects_duplicate_chars() {
+        let chars = SpecialChars::new(FEND, FEND, TFEND, TFESC);
+
+        assert_eq!(
+            FixedDecoder::<8>::new(chars).err(),
+            Some(HDLCError::DuplicateSpecialChar)
+        );
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{decode_with, encode, DecodeOptions, FcsMode, FrameFormat, HDLCError, SpecialChars};
+
+    fn options() -> DecodeOptions {
+        DecodeOptions {
+            fcs: FcsMode::Crc16,
+            frame_format: true,
+            ..DecodeOptions::default()
+        }
+    }
+
+    #[test]
+    fn converts_field_bytes() {
+        let format = FrameFormat::from_bytes([0xA8, 0x1E]);
+        assert_eq!(
+            format,
+            FrameFormat {
+                format_type: FrameFormat::TYPE_3,
+                segmented: true,
+                length: 0x1E,
+            }
+        );
+        assert_eq!(format.to_bytes(), [0xA8, 0x1E]);
+        assert_eq!(FrameFormat::new(false, 0x7FF).to_bytes(), [0xA7, 0xFF]);
+    }
+
+    #[test]
+    fn decodes_wrapped_frame() {
+        let chars = SpecialChars::default();
+        let contents = FrameFormat::wrap(&[0x03, 0x21, 0x7E], true, FcsMode::Crc16).unwrap();
+        assert_eq!(&contents[..2], &[0xA8, 0x07]);
+
+        let frame = encode(&contents, chars.clone()).unwrap();
+        assert_eq!(
+            decode_with(&frame, chars, options()),
+            Ok(vec![0xA8, 0x07, 0x03, 0x21, 0x7E])
+        );
+    }
+
+    #[test]
+    fn reports_truncated_frame_before_fcs() {
+        let chars = SpecialChars::default();
+        let mut contents = FrameFormat::wrap(&[0x03, 0x21, 0x93], false, FcsMode::Crc16).unwrap();
+        contents.remove(3);
+
+        let frame = encode(&contents, chars.clone()).unwrap();
+        assert_eq!(
+            decode_with(&frame, chars, options()),
+            Err(HDLCError::FrameLengthMismatch {
+                declared: 7,
+                actual: 6,
+            })
+        );
+    }
+
+    #[test]
+    fn reports_frame_too_short_for_field() {
+        assert_eq!(
+            FrameFormat::check(&[0xA0]),
+            Err(HDLCError::FrameLengthMismatch {
+                declared: 2,
+                actual: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_payload() {
+        assert_eq!(
+            FrameFormat::wrap(&[0; 0x7FC], false, FcsMode::Crc16),
+            Err(HDLCError::PayloadTooLarge)
+        );
+        assert!(FrameFormat::wrap(&[0; 0x7FB], false, FcsMode::Crc16).is_ok());
+    }
+
+    fn hcs_options() -> DecodeOptions {
+        DecodeOptions {
+            hcs: true,
+            ..options()
+        }
+    }
+
+    #[test]
+    fn decodes_frame_with_hcs() {
+        let chars = SpecialChars::default();
+        // Two byte destination address, one byte source address, I frame control field
+        let contents = FrameFormat::wrap_with_hcs(
+            &[0x00, 0x03, 0x21, 0x10],
+            &[0xE6, 0xE6],
+            false,
+            FcsMode::Crc16,
+        )
+        .unwrap();
+        assert_eq!(contents.len(), 12);
+        assert_eq!(FrameFormat::check_hcs(&contents, FcsMode::Crc16), Ok(8));
+
+        let frame = encode(&contents, chars.clone()).unwrap();
+        let payload = decode_with(&frame, chars, hcs_options()).unwrap();
+        assert_eq!(&payload[8..], &[0xE6, 0xE6]);
+    }
+
+    #[test]
+    fn accepts_frame_without_info_field() {
+        let contents =
+            FrameFormat::wrap_with_hcs(&[0x03, 0x21, 0x93], &[], false, FcsMode::Crc16).unwrap();
+
+        assert_eq!(&contents[..6], &[0xA0, 0x07, 0x03, 0x21, 0x93, 0x0F]);
+        assert_eq!(FrameFormat::check_hcs(&contents, FcsMode::Crc16), Ok(5));
+    }
+
+    #[test]
+    fn reports_corrupted_header_before_fcs() {
+        let chars = SpecialChars::default();
+        let mut contents =
+            FrameFormat::wrap_with_hcs(&[0x03, 0x21, 0x10], &[0xE6], false, FcsMode::Crc16)
+                .unwrap();
+        contents[4] = 0x32;
+        // Keep the FCS valid, so only the HCS catches the corruption
+        contents.truncate(contents.len() - 2);
+        FcsMode::Crc16.append(&mut contents);
+
+        let frame = encode(&contents, chars.clone()).unwrap();
+        assert_eq!(
+            decode_with(&frame, chars, hcs_options()),
+            Err(HDLCError::HcsMismatch)
+        );
+    }
+
+    #[test]
+    fn reports_unterminated_address() {
+        assert_eq!(
+            FrameFormat::check_hcs(
+                &[0xA0, 0x09, 0x02, 0x02, 0x02, 0x02, 0x02, 0x00, 0x00],
+                FcsMode::Crc16
+            ),
+            Err(HDLCError::HcsMismatch)
+        );
+        assert_eq!(
+            FrameFormat::check_hcs(&[0xA0, 0x05, 0x03, 0x00, 0x00], FcsMode::Crc16),
+            Err(HDLCError::HcsMismatch)
+        );
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{encode, DecodeOptions, DecodedFrame, FcsMode, HDLCError, SpecialChars, FEND, FESC};
+
+    #[test]
+    fn try_from_records_metadata() {
+        let frame = DecodedFrame::try_from(&[FEND, 0x01, 0x02, FEND][..]).unwrap();
+        assert_eq!(frame.payload(), &[0x01, 0x02]);
+        assert_eq!(frame.len(), 2);
+        assert!(!frame.is_empty());
+        assert!(!frame.has_escapes());
+        assert_eq!(frame.fcs(), FcsMode::None);
+        assert_eq!(&frame[..1], &[0x01]);
+        assert_eq!(Vec::from(frame), vec![0x01, 0x02]);
+
+        let frame = DecodedFrame::try_from(&[FEND, FEND][..]).unwrap();
+        assert!(frame.is_empty());
+
+        assert_eq!(
+            DecodedFrame::try_from(&[0x01, FEND][..]),
+            Err(HDLCError::MissingFirstFend)
+        );
+    }
+
+    #[test]
+    fn decode_with_fcs() {
+        let chars = SpecialChars::default();
+        let options = DecodeOptions {
+            fcs: FcsMode::Crc16,
+            ..DecodeOptions::default()
+        };
+        let mut payload = vec![0x01, FESC];
+        FcsMode::Crc16.append(&mut payload);
+        let encoded = encode(&payload, chars.clone()).unwrap();
+
+        let frame = DecodedFrame::decode(&encoded, chars.clone(), options).unwrap();
+        assert_eq!(frame.payload(), &[0x01, FESC]);
+        assert!(frame.has_escapes());
+        assert_eq!(frame.fcs(), FcsMode::Crc16);
+        assert_eq!(frame.into_payload(), vec![0x01, FESC]);
+
+        let mut corrupted = encoded.clone();
+        corrupted[1] = 0x02;
+        assert_eq!(
+            DecodedFrame::decode(&corrupted, chars, options),
+            Err(HDLCError::FcsMismatch)
+        );
+    }
+}
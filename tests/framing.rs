@@ -1,12 +1,37 @@
 #[cfg(test)]
+#[allow(clippy::while_let_loop)]
 mod tests {
-    use std::io::Cursor;
+    use std::collections::HashSet;
+    use std::io::{self, Cursor, Read, Write};
+    use std::mem::MaybeUninit;
 
     use hdlc::{
-        decode, decode_slice, encode, FrameReader, HDLCError, SpecialChars, FEND, FESC, TFEND,
-        TFESC,
+        decode, decode_first, decode_frames, decode_frames_with, decode_into, decode_lenient,
+        decode_prefix, decode_slice, decode_to_slice, decode_to_vec, decode_to_writer,
+        decoded_len_hint, encode, encode_frames, encode_in_place, encode_into, encode_segments,
+        encode_to_slice, encode_to_uninit, encode_to_vec, encode_to_writer, encode_with_report,
+        encoded_len, escape, frames, get_decoded_frames, get_frames, max_encoded_len, normalize,
+        transcode, transcode_stream, try_decode, try_encode, try_transcode, unescape,
+        DecodeOptions, Discarded, EncodeOptions, EncodeReport, EscapeMap, FrameReader, HDLCError,
+        Output, ReaderEvent, SliceOutput, SpecialChars, FEND, FESC, TFEND, TFESC,
     };
 
+    /// Reader handing out one chunk per `read` call
+    struct ChunkReader {
+        chunks: Vec<Vec<u8>>,
+    }
+
+    impl Read for ChunkReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.chunks.is_empty() {
+                return Ok(0);
+            }
+            let chunk = self.chunks.remove(0);
+            buf[..chunk.len()].copy_from_slice(&chunk);
+            Ok(chunk.len())
+        }
+    }
+
     #[test]
     fn packetizes() {
         let msg: Vec<u8> = vec![0x01, 0x50, 0x00, 0x00, 0x00, 0x05, 0x80, 0x09];
@@ -117,7 +142,7 @@ mod tests {
         let result = decode(&msg, chars);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), HDLCError::FendCharInData)
+        assert_eq!(result.unwrap_err(), HDLCError::FendCharInData { offset: 8 })
     }
 
     #[test]
@@ -128,7 +153,10 @@ mod tests {
         let result = decode(&msg, chars);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), HDLCError::MissingTradeChar)
+        assert_eq!(
+            result.unwrap_err(),
+            HDLCError::MissingTradeChar { offset: 2 }
+        )
     }
 
     #[test]
@@ -139,7 +167,10 @@ mod tests {
         let result = decode(&msg, chars);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), HDLCError::MissingFinalFend)
+        assert_eq!(
+            result.unwrap_err(),
+            HDLCError::Incomplete { needed: Some(1) }
+        )
     }
 
     #[test]
@@ -158,7 +189,7 @@ mod tests {
     fn depack_slice_it_swaps() {
         let chars = SpecialChars::default();
         let mut msg = [
-            FEND, 0x01, FESC, FESC, 0x00, 0x00, FESC, TFEND, 0x05, 0x80, 0x09, FEND,
+            FEND, 0x01, FESC, TFESC, 0x00, 0x00, FESC, TFEND, 0x05, 0x80, 0x09, FEND,
         ];
         let cmp = [1, 125, 0, 0, 126, 5, 128, 9];
 
@@ -172,7 +203,7 @@ mod tests {
     fn depack_slice_custom_s_chars() {
         let chars = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
         let mut msg = [
-            0x71, 0x01, 0x7E, 0x71, 0x51, 0x00, 0x05, 0x80, 0x70, 0x50, 0x09, 0x71,
+            0x71, 0x01, 0x7E, 0x70, 0x51, 0x00, 0x05, 0x80, 0x70, 0x50, 0x09, 0x71,
         ];
         let cmp = [1, 126, 0x71, 0, 5, 128, 0x70, 9];
 
@@ -203,7 +234,7 @@ mod tests {
         let result = decode_slice(&mut msg, chars);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), HDLCError::FendCharInData)
+        assert_eq!(result.unwrap_err(), HDLCError::FendCharInData { offset: 8 })
     }
 
     #[test]
@@ -214,7 +245,10 @@ mod tests {
         let result = decode_slice(&mut msg, chars);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), HDLCError::MissingTradeChar)
+        assert_eq!(
+            result.unwrap_err(),
+            HDLCError::MissingTradeChar { offset: 2 }
+        )
     }
 
     #[test]
@@ -225,7 +259,10 @@ mod tests {
         let result = decode_slice(&mut msg, chars);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), HDLCError::MissingFinalFend)
+        assert_eq!(
+            result.unwrap_err(),
+            HDLCError::Incomplete { needed: Some(1) }
+        )
     }
 
     #[test]
@@ -439,4 +476,1117 @@ mod tests {
         }
         assert_eq!(frames.len(), 0);
     }
+
+    #[test]
+    fn frames_splits_slice() {
+        let chars = SpecialChars::default();
+        let msg = [
+            0x01, FEND, 0x01, 0x00, FEND, FEND, 0x02, FESC, TFEND, FEND, FEND, 0x03,
+        ];
+
+        let mut iter = frames(&msg, chars);
+
+        assert_eq!(iter.next(), Some(&[FEND, 0x01, 0x00, FEND][..]));
+        assert_eq!(iter.next(), Some(&[FEND, 0x02, FESC, TFEND, FEND][..]));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.remainder(), &[FEND, 0x03]);
+    }
+
+    #[test]
+    fn frames_no_frame_start() {
+        let chars = SpecialChars::default();
+        let msg = [0x05, 0x80, 0x01];
+
+        let mut iter = frames(&msg, chars);
+
+        assert_eq!(iter.next(), None);
+        assert!(iter.remainder().is_empty());
+    }
+
+    #[test]
+    fn decode_frames_keeps_partial_frame() {
+        let chars = SpecialChars::default();
+        let msg = [
+            FEND, 0x01, FESC, TFESC, FEND, FEND, 0x02, 0x03, FEND, FEND, 0x04, FESC,
+        ];
+
+        let result = decode_frames(&msg, chars);
+
+        assert!(result.is_ok());
+        let (payloads, consumed) = result.unwrap();
+        assert_eq!(payloads, vec![vec![0x01, FESC], vec![0x02, 0x03]]);
+        assert_eq!(consumed, 9);
+    }
+
+    #[test]
+    fn decode_frames_rejects_stray_fesc_char() {
+        let chars = SpecialChars::default();
+        let msg = [FEND, 0x01, FEND, FEND, 0x02, FESC, 0x03, FEND];
+
+        let result = decode_frames(&msg, chars);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            HDLCError::MissingTradeChar { offset: 5 }
+        )
+    }
+
+    #[test]
+    fn get_buffered_frames_after_eof() {
+        let chars = SpecialChars::default();
+        let mut reader = ChunkReader {
+            chunks: vec![vec![
+                FEND, 0x01, FEND, 0x30, FEND, 0x02, FEND, FEND, 0x03, FEND, 0x10, 0x22,
+            ]],
+        };
+        let hdlc_reader = FrameReader::new(&mut reader, chars);
+
+        let frames: Vec<Vec<u8>> = hdlc_reader.collect();
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0], vec![126, 1, 126]);
+        assert_eq!(frames[1], vec![126, 2, 126]);
+        assert_eq!(frames[2], vec![126, 3, 126]);
+    }
+
+    #[test]
+    fn get_frame_across_reads() {
+        let chars = SpecialChars::default();
+        let mut reader = ChunkReader {
+            chunks: vec![
+                vec![0x10, FEND, 0x01],
+                vec![0x02, 0x03],
+                vec![0x04, FEND, 0x22],
+            ],
+        };
+        let hdlc_reader = FrameReader::new(&mut reader, chars);
+
+        let frames: Vec<Vec<u8>> = hdlc_reader.collect();
+
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0], vec![126, 1, 2, 3, 4, 126]);
+    }
+
+    #[test]
+    fn get_frame_larger_than_read_buffer() {
+        let chars = SpecialChars::default();
+        let mut msg = vec![0x55; 3000];
+        msg[0] = FEND;
+        msg[2999] = FEND;
+        let mut reader = Cursor::new(msg.clone());
+        let mut hdlc_reader = FrameReader::new(&mut reader, chars);
+
+        assert_eq!(hdlc_reader.read_frame(), Some(msg));
+        assert_eq!(hdlc_reader.read_frame(), None);
+    }
+
+    #[test]
+    fn read_frames_into_buffer() {
+        let chars = SpecialChars::default();
+        let msg = [0x10, FEND, 0x01, 0x02, FEND, FEND, 0x03, FEND];
+        let mut reader = Cursor::new(msg);
+        let mut hdlc_reader = FrameReader::new(&mut reader, chars);
+        let mut buf = [0u8; 8];
+
+        assert_eq!(hdlc_reader.read_frame_into(&mut buf), Ok(Some(4)));
+        assert_eq!(buf[..4], [FEND, 0x01, 0x02, FEND]);
+        assert_eq!(hdlc_reader.read_frame_into(&mut buf), Ok(Some(3)));
+        assert_eq!(buf[..3], [FEND, 0x03, FEND]);
+        assert_eq!(hdlc_reader.read_frame_into(&mut buf), Ok(None));
+    }
+
+    #[test]
+    fn read_frame_into_rejects_small_buffer() {
+        let chars = SpecialChars::default();
+        let msg = [FEND, 0x01, 0x02, 0x03, FEND];
+        let mut reader = Cursor::new(msg);
+        let mut hdlc_reader = FrameReader::new(&mut reader, chars);
+        let mut buf = [0u8; 4];
+
+        let result = hdlc_reader.read_frame_into(&mut buf);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), HDLCError::BufferTooSmall);
+        assert_eq!(hdlc_reader.read_frame(), Some(msg.to_vec()));
+    }
+
+    #[test]
+    fn builder_decodes_frames() {
+        let chars = SpecialChars::default();
+        let msg = [
+            FEND, 0x01, FESC, TFEND, FEND, FEND, FESC, 0x02, FEND, FEND, 0x03, FEND,
+        ];
+        let mut reader = Cursor::new(msg);
+        let hdlc_reader = FrameReader::builder(chars)
+            .chunk_size(2)
+            .decode(true)
+            .build(&mut reader);
+
+        let frames: Vec<Vec<u8>> = hdlc_reader.collect();
+
+        assert_eq!(frames, vec![vec![0x01, FEND], vec![0x03]]);
+    }
+
+    #[test]
+    fn builder_drops_oversized_frames() {
+        let chars = SpecialChars::default();
+        let msg = [
+            FEND, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, FEND, FEND, 0x07, FEND,
+        ];
+        let mut reader = Cursor::new(msg);
+        let hdlc_reader = FrameReader::builder(chars)
+            .chunk_size(3)
+            .max_frame_len(4)
+            .build(&mut reader);
+
+        let frames: Vec<Vec<u8>> = hdlc_reader.collect();
+
+        assert_eq!(frames, vec![vec![FEND, 0x07, FEND]]);
+    }
+
+    #[test]
+    fn encodes_to_writer() {
+        let msg: Vec<u8> = vec![0x01, 0x7E, 0x00, 0x7D, 0x00, 0x05, 0x80, 0x09];
+        let cmp: Vec<u8> = vec![126, 1, 125, 94, 0, 125, 93, 0, 5, 128, 9, 126];
+        let chars = SpecialChars::default();
+        let mut output = Vec::new();
+
+        let result = encode_to_writer(&msg, chars, &mut output);
+
+        assert_eq!(result.unwrap(), cmp.len());
+        assert_eq!(output, cmp)
+    }
+
+    #[test]
+    fn encode_to_writer_rejects_dupe_s_chars() {
+        let chars = SpecialChars::new(0x7E, 0x7D, 0x5D, 0x5D);
+        let mut output = Vec::new();
+
+        let result = encode_to_writer(&[0x01], chars, &mut output);
+
+        assert!(result.is_err());
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn encode_to_writer_passes_writer_errors_through() {
+        let mut full = [0u8; 3];
+        let mut output = &mut full[..];
+
+        let result = encode_to_writer(&[0x01, FEND], SpecialChars::default(), &mut output);
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::WriteZero);
+        assert_eq!(full, [FEND, 0x01, FESC]);
+    }
+
+    #[test]
+    fn decodes_to_writer() {
+        let chars = SpecialChars::default();
+        let msg: Vec<u8> = vec![
+            FEND, 0x01, FESC, TFESC, 0x00, 0x00, FESC, TFEND, 0x05, 0x80, 0x09, FEND,
+        ];
+        let cmp: Vec<u8> = vec![1, 125, 0, 0, 126, 5, 128, 9];
+        let mut output = Vec::new();
+
+        let result = decode_to_writer(&msg, chars, &mut output);
+
+        assert_eq!(result.unwrap(), cmp.len());
+        assert_eq!(output, cmp)
+    }
+
+    #[test]
+    fn decode_to_writer_rejects_stray_fesc_char() {
+        let chars = SpecialChars::default();
+        let msg: Vec<u8> = vec![FEND, 0x01, FESC, 0x00, FEND];
+        let mut output = Vec::new();
+
+        let result = decode_to_writer(&msg, chars, &mut output);
+
+        let err = result.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<HDLCError>(),
+            Some(&HDLCError::MissingTradeChar { offset: 2 })
+        );
+    }
+
+    #[test]
+    fn transcodes_between_s_chars() {
+        let custom = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+        let msg: Vec<u8> = vec![0x71, 0x01, FEND, 0x70, 0x51, FESC, 0x70, 0x50, 0x71];
+        let cmp: Vec<u8> = vec![FEND, 0x01, FESC, TFEND, 0x71, FESC, TFESC, 0x70, FEND];
+
+        let result = transcode(&msg, custom.clone(), SpecialChars::default());
+
+        assert_eq!(result, Ok(cmp.clone()));
+        assert_eq!(transcode(&cmp, SpecialChars::default(), custom), Ok(msg));
+    }
+
+    #[test]
+    fn transcode_rejects_stray_fesc_char() {
+        let msg: Vec<u8> = vec![FEND, 0x01, FESC, 0x00, FEND];
+
+        let result = transcode(&msg, SpecialChars::default(), SpecialChars::default());
+
+        assert_eq!(result, Err(HDLCError::MissingTradeChar { offset: 2 }));
+    }
+
+    #[test]
+    fn transcodes_stream() {
+        let custom = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+        let msg = [
+            0x22, FEND, 0x01, FEND, FEND, FESC, 0x00, FEND, FEND, FESC, TFEND, FEND,
+        ];
+        let mut reader = Cursor::new(msg);
+        let mut output = Vec::new();
+
+        let result = transcode_stream(&mut reader, &mut output, SpecialChars::default(), custom);
+
+        assert_eq!(result.unwrap(), (2, 1));
+        assert_eq!(output, vec![0x71, 0x01, 0x71, 0x71, FEND, 0x71]);
+    }
+
+    #[test]
+    fn transcode_stream_reports_io_errors() {
+        /// Reader failing after its first chunk
+        struct Broken(Option<Vec<u8>>);
+
+        impl Read for Broken {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let chunk = self.0.take().ok_or(io::ErrorKind::BrokenPipe)?;
+                buf[..chunk.len()].copy_from_slice(&chunk);
+                Ok(chunk.len())
+            }
+        }
+
+        let custom = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+        let mut broken = Broken(Some(vec![FEND, 0x01, FEND, FEND, 0x02]));
+        let mut output = Vec::new();
+
+        let result = transcode_stream(&mut broken, &mut output, SpecialChars::default(), custom);
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(output, vec![0x71, 0x01, 0x71]);
+    }
+
+    #[test]
+    fn encodes_to_slice() {
+        let msg: Vec<u8> = vec![0x01, 0x7E, 0x00, 0x7D, 0x00, 0x05, 0x80, 0x09];
+        let cmp: Vec<u8> = vec![126, 1, 125, 94, 0, 125, 93, 0, 5, 128, 9, 126];
+        let chars = SpecialChars::default();
+        let mut output = [0u8; 12];
+
+        let result = encode_to_slice(&msg, chars.clone(), &mut output);
+
+        assert_eq!(result, Ok(12));
+        assert_eq!(output.to_vec(), cmp);
+        assert_eq!(
+            encode_to_slice(&msg, chars, &mut output[..11]),
+            Err(HDLCError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn decodes_to_slice() {
+        let chars = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+        let msg: Vec<u8> = vec![
+            0x71, 0x01, 0x7E, 0x70, 0x51, 0x00, 0x05, 0x80, 0x70, 0x50, 0x09, 0x71,
+        ];
+        let cmp: Vec<u8> = vec![1, 126, 0x71, 0, 5, 128, 0x70, 9];
+        let mut output = [0u8; 8];
+
+        let result = decode_to_slice(&msg, chars.clone(), &mut output);
+
+        assert_eq!(result, Ok(8));
+        assert_eq!(output.to_vec(), cmp);
+        assert_eq!(
+            decode_to_slice(&msg, chars, &mut output[..7]),
+            Err(HDLCError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn pack_const_s_chars() {
+        const CHARS: SpecialChars = SpecialChars::new_checked(0x71, 0x70, 0x51, 0x50);
+        let msg: Vec<u8> = vec![0x01, 0x7E, 0x70, 0x7D, 0x00, 0x05, 0x80, 0x09];
+        let cmp: Vec<u8> = vec![0x71, 1, 126, 112, 80, 125, 0, 5, 128, 9, 0x71];
+
+        let result = encode(&msg, CHARS);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), cmp)
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate special character")]
+    fn new_checked_rejects_dupe_s_chars() {
+        SpecialChars::new_checked(0x7E, 0x7D, 0x5D, 0x5D);
+    }
+
+    #[test]
+    fn pack_segments_matches_encode() {
+        let msg = [FESC, 0x01, 0x02, FEND, FEND, 0x03];
+        let chars = SpecialChars::default();
+
+        let frame = encode_segments(&msg, chars.clone()).unwrap();
+        let joined: Vec<u8> = frame.iter().flatten().copied().collect();
+
+        assert_eq!(joined, encode(&msg, chars).unwrap());
+        assert_eq!(frame.len(), joined.len());
+        assert_eq!(frame.iter().nth(2), Some(&[0x01, 0x02][..]));
+    }
+
+    #[test]
+    fn pack_segments_vectored_write() {
+        let msg = [0x01, FEND, 0x02];
+        let mut out = Vec::new();
+
+        let frame = encode_segments(&msg, SpecialChars::default()).unwrap();
+        let written = out.write_vectored(&frame.io_slices()).unwrap();
+
+        assert_eq!(written, frame.len());
+
+        assert_eq!(out, vec![FEND, 0x01, FESC, TFEND, 0x02, FEND]);
+    }
+
+    #[test]
+    fn pack_segments_rejects_dupe_s_chars() {
+        let chars = SpecialChars::new(FEND, FEND, TFEND, TFESC);
+
+        assert_eq!(
+            encode_segments(&[0x01], chars).err(),
+            Some(HDLCError::DuplicateSpecialChar)
+        );
+    }
+
+    /// Output holding at most `cap` bytes
+    struct CappedOutput {
+        bytes: Vec<u8>,
+        cap: usize,
+    }
+
+    impl Output for CappedOutput {
+        fn push(&mut self, byte: u8) -> Result<(), HDLCError> {
+            if self.bytes.len() == self.cap {
+                return Err(HDLCError::BufferTooSmall);
+            }
+            self.bytes.push(byte);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn pack_into_custom_output() {
+        let msg = [0x01, FEND, 0x02];
+        let mut out = CappedOutput {
+            bytes: Vec::new(),
+            cap: 6,
+        };
+
+        let result = encode_into(&msg, SpecialChars::default(), &mut out);
+
+        assert_eq!(result, Ok(6));
+        assert_eq!(out.bytes, vec![FEND, 0x01, FESC, TFEND, 0x02, FEND]);
+        assert_eq!(
+            encode_into(&msg, SpecialChars::default(), &mut out),
+            Err(HDLCError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn depack_into_slice_output() {
+        let msg = [FEND, 0x01, FESC, TFESC, 0x02, FEND];
+        let mut buf = [0u8; 4];
+        let mut out = SliceOutput::new(&mut buf);
+
+        let result = decode_into(&msg, SpecialChars::default(), &mut out);
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(out.as_slice(), &[0x01, FESC, 0x02]);
+        assert_eq!(
+            decode_into(&msg, SpecialChars::default(), &mut out),
+            Err(HDLCError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn try_variants_match_allocating_ones() {
+        let msg = [0x01, FEND, FESC, 0x02];
+        let chars = SpecialChars::default();
+        let custom = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+
+        let encoded = try_encode(&msg, chars.clone()).unwrap();
+
+        assert_eq!(encoded, encode(&msg, chars.clone()).unwrap());
+        assert_eq!(encoded.capacity(), encoded.len());
+        assert_eq!(try_decode(&encoded, chars.clone()), Ok(msg.to_vec()));
+        assert_eq!(
+            try_transcode(&encoded, chars.clone(), custom.clone()),
+            transcode(&encoded, chars, custom)
+        );
+    }
+
+    #[test]
+    fn encode_with_report_counts_escapes() {
+        let msg = [0x01, FEND, FESC, 0x02, FEND];
+        let chars = SpecialChars::default();
+
+        let (encoded, report) = encode_with_report(&msg, chars.clone()).unwrap();
+
+        assert_eq!(encoded, encode(&msg, chars.clone()).unwrap());
+        assert_eq!(
+            report,
+            EncodeReport {
+                escapes: 3,
+                out_len: 10
+            }
+        );
+        assert_eq!(
+            encode_with_report(&[], chars).unwrap().1,
+            EncodeReport {
+                escapes: 0,
+                out_len: 2
+            }
+        );
+        assert_eq!(
+            encode_with_report(&msg, SpecialChars::new(FEND, FEND, TFEND, TFESC)),
+            Err(HDLCError::DuplicateSpecialChar)
+        );
+    }
+
+    #[test]
+    fn depack_truncated_escapes_without_panicking() {
+        let chars = SpecialChars::default();
+        let mut leading_fesc = [FESC, TFEND, FEND, 0x01, FEND];
+
+        assert_eq!(
+            decode(&[FEND, 0x01, FESC], chars.clone()),
+            Err(HDLCError::Incomplete { needed: Some(2) })
+        );
+        assert_eq!(decode_slice(&mut leading_fesc, chars), Ok(&[0x01][..]));
+    }
+
+    #[test]
+    fn pack_to_uninit() {
+        let msg = [0x01, FESC, 0x02];
+        let mut output = [MaybeUninit::<u8>::uninit(); 6];
+        let mut small = [MaybeUninit::<u8>::uninit(); 5];
+
+        assert_eq!(
+            encode_to_uninit(&msg, SpecialChars::default(), &mut output),
+            Ok(&[FEND, 0x01, FESC, TFESC, 0x02, FEND][..])
+        );
+        assert_eq!(
+            encode_to_uninit(&msg, SpecialChars::default(), &mut small),
+            Err(HDLCError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn escape_map_round_trips() {
+        let map = EscapeMap::from_accm(0x000A_0000);
+        let chars = SpecialChars::default().with_escape_map(map).unwrap();
+        let msg = [0x11, 0x13, FEND, 0x20];
+
+        let encoded = encode(&msg, chars.clone()).unwrap();
+
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![0x11, 0x13]);
+        assert_eq!(
+            encoded,
+            vec![FEND, FESC, 0x31, FESC, 0x33, FESC, TFEND, 0x20, FEND]
+        );
+        assert_eq!(decode(&encoded, chars.clone()), Ok(msg.to_vec()));
+        assert_eq!(
+            decode(&[FEND, 0x11, 0x13, FEND], chars),
+            Ok(vec![0x11, 0x13])
+        );
+    }
+
+    #[test]
+    fn escape_map_rejects_colliding_translations() {
+        // 0x5E would be translated to 0x7E, the FEND
+        let chars = SpecialChars::default().with_escape_map([0x5E].into_iter().collect());
+        assert_eq!(chars, Err(HDLCError::DuplicateSpecialChar));
+
+        // 0x7D would be translated to 0x5D, the TFESC of custom FEND 0x71
+        let custom = SpecialChars::new(0x71, 0x70, 0x51, 0x5D);
+        let chars = custom.with_escape_map(EscapeMap::new().with(0x7D));
+        assert_eq!(chars, Err(HDLCError::DuplicateSpecialChar));
+    }
+
+    #[test]
+    fn sparse_escapes_round_trip() {
+        let mut msg = vec![0x42u8; 300];
+        msg[0] = FEND;
+        msg[150] = FESC;
+        msg[299] = 0x11;
+
+        for chars in [
+            SpecialChars::default(),
+            SpecialChars::default()
+                .with_escape_map(EscapeMap::new().with(0x11))
+                .unwrap(),
+        ] {
+            let encoded = encode(&msg, chars.clone()).unwrap();
+            let mut written = Vec::new();
+            encode_to_writer(&msg, chars.clone(), &mut written).unwrap();
+            let mut decoded = Vec::new();
+            decode_to_writer(&encoded, chars.clone(), &mut decoded).unwrap();
+
+            assert_eq!(written, encoded);
+            assert_eq!(decode(&encoded, chars), Ok(msg.clone()));
+            assert_eq!(decoded, msg);
+        }
+    }
+
+    #[test]
+    fn special_chars_at_every_offset() {
+        let chars = SpecialChars::default();
+
+        for index in 0..40 {
+            for special in [FEND, FESC] {
+                let mut msg = vec![0x42u8; 40];
+                msg[index] = special;
+
+                let encoded = encode(&msg, chars.clone()).unwrap();
+
+                assert_eq!(encoded.len(), 43);
+                assert_eq!(encoded[index + 1], FESC);
+                assert_eq!(decode(&encoded, chars.clone()), Ok(msg));
+            }
+        }
+    }
+
+    #[test]
+    fn pack_and_depack_reuse_vec() {
+        let chars = SpecialChars::default();
+        let mut encoded = Vec::new();
+        let mut decoded = Vec::new();
+
+        assert_eq!(
+            encode_to_vec(&[0x01; 64], chars.clone(), &mut encoded),
+            Ok(66)
+        );
+        let capacity = encoded.capacity();
+        assert_eq!(encode_to_vec(&[FEND], chars.clone(), &mut encoded), Ok(4));
+
+        assert_eq!(encoded, vec![FEND, FESC, TFEND, FEND]);
+        assert_eq!(encoded.capacity(), capacity);
+        assert_eq!(decode_to_vec(&encoded, chars.clone(), &mut decoded), Ok(1));
+        assert_eq!(decoded, vec![FEND]);
+        assert_eq!(
+            decode_to_vec(&[FEND, 0x02, FEND], chars, &mut decoded),
+            Ok(1)
+        );
+        assert_eq!(decoded, vec![0x02]);
+    }
+
+    #[test]
+    fn pack_allocates_exact_size() {
+        let msg = [0x01, FEND, 0x11, FESC, 0x02];
+        let xon = SpecialChars::default()
+            .with_escape_map(EscapeMap::new().with(0x11))
+            .unwrap();
+
+        for chars in [SpecialChars::default(), xon] {
+            let encoded = encode(&msg, chars.clone()).unwrap();
+
+            assert_eq!(encoded_len(&msg, chars), encoded.len());
+            assert_eq!(encoded.capacity(), encoded.len());
+        }
+    }
+
+    #[test]
+    fn length_helpers_bound_frames() {
+        let chars = SpecialChars::default();
+        let worst = [FEND; 32];
+        let encoded = encode(&worst, chars.clone()).unwrap();
+
+        assert_eq!(max_encoded_len(worst.len()), encoded.len());
+        assert_eq!(max_encoded_len(0), 2);
+        assert_eq!(max_encoded_len(usize::MAX), usize::MAX);
+        assert!(decode(&encoded, chars).unwrap().len() <= decoded_len_hint(encoded.len()));
+        assert_eq!(decoded_len_hint(0), 0);
+    }
+
+    #[test]
+    fn depack_slice_in_place() {
+        let mut msg = [
+            0x00, FEND, FESC, TFEND, 0x01, FESC, TFESC, FESC, TFEND, 0x02, FEND,
+        ];
+        let start = msg.as_ptr();
+
+        let result = decode_slice(&mut msg, SpecialChars::default()).unwrap();
+
+        assert_eq!(result, [FEND, 0x01, FESC, FEND, 0x02]);
+        assert_eq!(result.as_ptr(), start);
+    }
+
+    #[test]
+    fn pack_in_place() {
+        let chars = SpecialChars::default();
+        let payload = [FEND, 0x01, FESC, FESC, 0x02, FEND];
+        let mut buf = vec![0xAA, 0xAA];
+        buf.extend_from_slice(&payload);
+        buf.push(0xBB);
+
+        let frame = encode_in_place(&mut buf, 2..8, chars.clone()).unwrap();
+
+        assert_eq!(
+            &buf[frame.clone()],
+            &encode(&payload, chars.clone()).unwrap()[..]
+        );
+        assert_eq!(&buf[..2], &[0xAA, 0xAA]);
+        assert_eq!(&buf[frame.end..], &[0xBB]);
+        assert_eq!(
+            encode_in_place(&mut buf, 4..20, chars),
+            Err(HDLCError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn try_new_validates_once() {
+        let chars = SpecialChars::try_new(0x71, 0x70, 0x51, 0x50).unwrap();
+
+        assert_eq!(chars.translations().len(), 2);
+        assert_eq!(encode(&[0x71], chars), Ok(vec![0x71, 0x70, 0x51, 0x71]));
+        for (fend, fesc, tfend, tfesc) in [
+            (FEND, FEND, TFEND, TFESC),
+            (FEND, FESC, TFEND, TFEND),
+            (FEND, FESC, FESC, TFESC),
+            (FEND, FESC, TFEND, FEND),
+        ] {
+            assert_eq!(
+                SpecialChars::try_new(fend, fesc, tfend, tfesc).err(),
+                Some(HDLCError::DuplicateSpecialChar)
+            );
+        }
+    }
+
+    #[test]
+    fn get_frames_trickling_in() {
+        let chars = SpecialChars::default();
+        let mut reader = ChunkReader {
+            chunks: vec![
+                vec![FEND],
+                vec![FEND, 0x01],
+                vec![0x02],
+                vec![0x03, FEND, 0x04],
+                vec![FEND, FEND, 0x05],
+                vec![FEND],
+            ],
+        };
+        let hdlc_reader = FrameReader::new(&mut reader, chars);
+
+        let frames: Vec<Vec<u8>> = hdlc_reader.collect();
+
+        assert_eq!(
+            frames,
+            vec![vec![FEND, 0x01, 0x02, 0x03, FEND], vec![FEND, 0x05, FEND]]
+        );
+    }
+
+    #[test]
+    fn lenient_decode_resyncs() {
+        let chars = SpecialChars::default();
+        let mut input = vec![0x55];
+        input.extend(encode(&[0x01, 0x7E], chars.clone()).unwrap());
+        input.extend([0x7E, 0x02, 0x7D, 0x7D, 0x7E]);
+        input.extend(encode(&[0x03], chars.clone()).unwrap());
+        input.extend([0x7E, 0x04]);
+
+        assert_eq!(
+            decode_lenient(&input, chars).unwrap(),
+            vec![
+                Ok(vec![0x01, 0x7E]),
+                Err(Discarded {
+                    range: 6..11,
+                    error: HDLCError::MissingTradeChar { offset: 8 }
+                }),
+                Ok(vec![0x03]),
+                Err(Discarded {
+                    range: 14..16,
+                    error: HDLCError::MissingFinalFend
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn lenient_decode_duplicate_chars() {
+        let chars = SpecialChars::new(0x7E, 0x7E, 0x5E, 0x5D);
+
+        assert_eq!(
+            decode_lenient(&[0x7E, 0x7E], chars),
+            Err(HDLCError::DuplicateSpecialChar)
+        );
+    }
+
+    #[test]
+    fn get_frames_splits_buffer() {
+        let chars = SpecialChars::default();
+        let buf = [
+            0x55, FEND, 0x01, FESC, TFEND, FEND, FEND, FEND, 0x02, FEND, FEND, 0x03,
+        ];
+
+        let (frames, rest) = get_frames(&buf, chars.clone());
+        assert_eq!(
+            frames,
+            vec![&[FEND, 0x01, FESC, TFEND, FEND][..], &[FEND, 0x02, FEND]]
+        );
+        assert_eq!(rest, &[FEND, 0x03]);
+
+        let (payloads, rest) = get_decoded_frames(&buf, chars.clone()).unwrap();
+        assert_eq!(payloads, vec![vec![0x01, FEND], vec![0x02]]);
+        assert_eq!(rest, &[FEND, 0x03]);
+
+        assert_eq!(get_frames(&[0x01, 0x02], chars), (vec![], &[][..]));
+    }
+
+    #[test]
+    fn get_decoded_frames_malformed() {
+        let chars = SpecialChars::default();
+
+        assert_eq!(
+            get_decoded_frames(&[FEND, FESC, 0x01, FEND], chars),
+            Err(HDLCError::MissingTradeChar { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn errors_carry_offsets() {
+        let chars = SpecialChars::default();
+        let mut msg = vec![FEND];
+        msg.extend([0x01; 4000]);
+        msg.extend([FESC, 0x02, FEND]);
+
+        let error = decode(&msg, chars.clone()).unwrap_err();
+        assert_eq!(error, HDLCError::MissingTradeChar { offset: 4001 });
+        assert_eq!(error.offset(), Some(4001));
+        assert_eq!(
+            error.to_string(),
+            "Caught a random swap char in the data at byte 4001."
+        );
+        assert_eq!(decode(&msg[1..], chars).unwrap_err().offset(), None);
+    }
+
+    #[test]
+    fn incomplete_frames() {
+        let chars = SpecialChars::default();
+        let mut no_fend = [0x01, 0x02];
+        let mut cut_escape = [FEND, 0x01, FESC];
+
+        assert_eq!(
+            decode_slice(&mut no_fend, chars.clone()),
+            Err(HDLCError::Incomplete { needed: None })
+        );
+        assert_eq!(
+            decode_slice(&mut cut_escape, chars.clone()),
+            Err(HDLCError::Incomplete { needed: Some(2) })
+        );
+        assert_eq!(
+            transcode(&[FEND, 0x01], chars.clone(), chars.clone()),
+            Err(HDLCError::Incomplete { needed: Some(1) })
+        );
+        assert_eq!(
+            decode(&[FEND, FESC, 0x01, FEND], chars),
+            Err(HDLCError::MissingTradeChar { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn escapes_without_flags() {
+        let chars = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+        let msg = [0x01, 0x71, 0x02, 0x70, FEND];
+
+        let escaped = escape(&msg, chars.clone()).unwrap();
+        assert_eq!(escaped, vec![0x01, 0x70, 0x51, 0x02, 0x70, 0x50, FEND]);
+        assert_eq!(unescape(&escaped, chars.clone()), Ok(msg.to_vec()));
+        assert_eq!(escape(&[], chars.clone()), Ok(vec![]));
+        assert_eq!(
+            escape(&msg, SpecialChars::new(FEND, FEND, TFEND, TFESC)),
+            Err(HDLCError::DuplicateSpecialChar)
+        );
+    }
+
+    #[test]
+    fn unescape_rejects_malformed() {
+        let chars = SpecialChars::default();
+
+        assert_eq!(
+            unescape(&[0x01, FEND, 0x02], chars.clone()),
+            Err(HDLCError::FendCharInData { offset: 1 })
+        );
+        assert_eq!(
+            unescape(&[0x01, FESC, 0x02], chars.clone()),
+            Err(HDLCError::MissingTradeChar { offset: 1 })
+        );
+        assert_eq!(
+            unescape(&[0x01, FESC], chars),
+            Err(HDLCError::Incomplete { needed: Some(1) })
+        );
+    }
+
+    #[test]
+    fn encode_frames_shares_flags() {
+        let chars = SpecialChars::default();
+        let payloads = [vec![0x01, FEND], vec![], vec![0x02]];
+        let shared = EncodeOptions {
+            shared_flags: true,
+            ..EncodeOptions::default()
+        };
+
+        let burst = encode_frames(&payloads, chars.clone(), shared).unwrap();
+        assert_eq!(burst, vec![FEND, 0x01, FESC, TFEND, FEND, FEND, 0x02, FEND]);
+
+        let burst = encode_frames(&payloads, chars.clone(), EncodeOptions::default()).unwrap();
+        let (frames, rest) = get_decoded_frames(&burst, chars).unwrap();
+        assert_eq!(frames, vec![vec![0x01, FEND], vec![0x02]]);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn shared_flags_round_trip() {
+        let chars = SpecialChars::default();
+        let payloads = [vec![0x01], vec![0x02, FESC], vec![0x03]];
+        let shared = EncodeOptions {
+            shared_flags: true,
+            ..EncodeOptions::default()
+        };
+        let burst = encode_frames(&payloads, chars.clone(), shared).unwrap();
+        let options = DecodeOptions {
+            shared_flags: true,
+            ..DecodeOptions::default()
+        };
+
+        let (frames, consumed) = decode_frames_with(&burst, chars.clone(), options).unwrap();
+        assert_eq!(frames, payloads);
+        assert_eq!(consumed, burst.len());
+
+        for chunk_size in [1, 2, 1024] {
+            let mut reader = Cursor::new(burst.clone());
+            let frames: Vec<Vec<u8>> = FrameReader::builder(chars.clone())
+                .chunk_size(chunk_size)
+                .options(options)
+                .build(&mut reader)
+                .collect();
+            assert_eq!(frames, payloads);
+        }
+    }
+
+    #[test]
+    fn decode_prefix_reports_consumed() {
+        let chars = SpecialChars::default();
+        let input = [FEND, 0x01, FESC, TFESC, FEND, FEND, 0x02, FEND, 0x03];
+
+        let (frame, consumed) = decode_prefix(&input, chars.clone()).unwrap();
+        assert_eq!(frame, vec![0x01, FESC]);
+        assert_eq!(consumed, 5);
+
+        let (frame, len) = decode_prefix(&input[consumed..], chars.clone()).unwrap();
+        assert_eq!(frame, vec![0x02]);
+        assert_eq!(&input[consumed + len..], &[0x03]);
+
+        assert_eq!(
+            decode_prefix(&input[consumed + len..], chars.clone()),
+            Err(HDLCError::MissingFirstFend)
+        );
+        assert_eq!(
+            decode_prefix(&[FEND, 0x01], chars.clone()),
+            Err(HDLCError::Incomplete { needed: Some(1) })
+        );
+        assert_eq!(
+            decode_prefix(&[FEND, FESC, 0x01, FEND, 0x02], chars),
+            Err(HDLCError::MissingTradeChar { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn decode_first_returns_tail() {
+        let chars = SpecialChars::default();
+        let input = [FEND, 0x01, FEND, 0x55, FEND, 0x02, FEND];
+
+        assert_eq!(
+            decode(&input, chars.clone()),
+            Err(HDLCError::FendCharInData { offset: 2 })
+        );
+
+        let (frame, rest) = decode_first(&input, chars.clone()).unwrap();
+        assert_eq!(frame, vec![0x01]);
+        assert_eq!(rest, &[0x55, FEND, 0x02, FEND]);
+
+        let (frame, rest) = decode_first(&input[4..], chars).unwrap();
+        assert_eq!(frame, vec![0x02]);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn normalize_frames() {
+        let chars = SpecialChars::default()
+            .with_escape_map(EscapeMap::from_accm(0xFFFF_FFFF))
+            .unwrap();
+        let payload = [0x00, 0x01, FEND, 0x41, FESC, 0x1F];
+        let over_escaped = encode(&payload, chars.clone()).unwrap();
+        let canonical = encode(&payload, SpecialChars::default()).unwrap();
+        assert_ne!(over_escaped, canonical);
+
+        assert_eq!(
+            normalize(&over_escaped, chars.clone()),
+            Ok(canonical.clone())
+        );
+        assert_eq!(normalize(&canonical, chars.clone()), Ok(canonical.clone()));
+
+        let mut doubled = vec![FEND];
+        doubled.extend(&canonical);
+        doubled.push(FEND);
+        assert_eq!(normalize(&doubled, chars.clone()), Ok(canonical));
+
+        assert_eq!(
+            normalize(&[FEND, FESC, 0x01, FEND], chars),
+            Err(HDLCError::MissingTradeChar { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn special_chars_compare_and_hash() {
+        let mut configs = HashSet::new();
+        configs.insert(SpecialChars::default());
+        configs.insert(SpecialChars::new(FEND, FESC, TFEND, TFESC));
+        configs.insert(SpecialChars::new(0x71, 0x70, 0x51, 0x50));
+        assert_eq!(configs.len(), 2);
+
+        let mapped = SpecialChars::default()
+            .with_escape_map(EscapeMap::new().with(0x11))
+            .unwrap();
+        assert_ne!(mapped, SpecialChars::default());
+        assert!(!configs.contains(&mapped));
+    }
+
+    #[test]
+    fn reader_reports_events() {
+        let data = vec![
+            0x55, 0x56, FEND, FEND, 0x01, FEND, FEND, 0x01, 0x02, 0x03, 0x04, FEND, FEND, FESC,
+            0x00, FEND, FEND, 0x02, FEND,
+        ];
+
+        for chunk_size in [1, 3, 1024] {
+            let mut events = Vec::new();
+            let mut reader = Cursor::new(data.clone());
+            let frames: Vec<Vec<u8>> = FrameReader::builder(SpecialChars::default())
+                .chunk_size(chunk_size)
+                .max_frame_len(4)
+                .decode(true)
+                .build(&mut reader)
+                .on_event(|event| match (events.last_mut(), event) {
+                    // Skipped bytes are reported per read, add them up
+                    (Some(ReaderEvent::Skipped { len }), ReaderEvent::Skipped { len: more }) => {
+                        *len += more
+                    }
+                    (_, event) => events.push(event),
+                })
+                .collect();
+
+            assert_eq!(frames, vec![vec![0x01], vec![0x02]]);
+            assert_eq!(
+                events,
+                vec![
+                    ReaderEvent::Skipped { len: 2 },
+                    ReaderEvent::Resynced,
+                    ReaderEvent::Skipped { len: 6 },
+                    ReaderEvent::Dropped {
+                        len: 4,
+                        error: HDLCError::MissingTradeChar { offset: 1 }
+                    },
+                    ReaderEvent::Resynced,
+                ],
+                "chunk size {}",
+                chunk_size
+            );
+        }
+    }
+
+    #[test]
+    fn try_read_frame_reports_io_errors() {
+        /// Reader failing once between two chunks
+        struct Flaky(Vec<io::Result<Vec<u8>>>);
+
+        impl Read for Flaky {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0.is_empty() {
+                    return Ok(0);
+                }
+                let chunk = self.0.remove(0)?;
+                buf[..chunk.len()].copy_from_slice(&chunk);
+                Ok(chunk.len())
+            }
+        }
+
+        let mut flaky = Flaky(vec![
+            Ok(vec![FEND, 0x01, FEND, FEND, 0x02]),
+            Err(io::ErrorKind::TimedOut.into()),
+            Ok(vec![FESC, TFEND, FEND]),
+        ]);
+        let mut reader = FrameReader::builder(SpecialChars::default())
+            .decode(true)
+            .build(&mut flaky);
+
+        assert_eq!(reader.try_read_frame(), Ok(Some(vec![0x01])));
+        let error = reader.try_read_frame().unwrap_err();
+        assert_eq!(error, HDLCError::Io(io::ErrorKind::TimedOut.into()));
+        assert_ne!(error, HDLCError::Io(io::ErrorKind::Other.into()));
+        assert_eq!(reader.try_read_frame(), Ok(Some(vec![0x02, FEND])));
+        assert_eq!(reader.try_read_frame(), Ok(None));
+    }
+
+    #[test]
+    fn read_frame_into_reports_io_errors() {
+        /// Reader failing after its first chunk
+        struct Broken(Option<Vec<u8>>);
+
+        impl Read for Broken {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let chunk = self.0.take().ok_or(io::ErrorKind::BrokenPipe)?;
+                buf[..chunk.len()].copy_from_slice(&chunk);
+                Ok(chunk.len())
+            }
+        }
+
+        let mut broken = Broken(Some(vec![FEND, 0x01, FEND, FEND, 0x02]));
+        let mut reader = FrameReader::new(&mut broken, SpecialChars::default());
+        let mut buf = [0u8; 8];
+
+        assert_eq!(reader.read_frame_into(&mut buf), Ok(Some(3)));
+        assert_eq!(
+            reader.read_frame_into(&mut buf),
+            Err(HDLCError::Io(io::ErrorKind::BrokenPipe.into()))
+        );
+    }
+
+    #[test]
+    fn try_read_frame_retries_interrupted_reads() {
+        /// Reader interrupted before every chunk
+        struct Interrupted(Vec<Vec<u8>>, bool);
+
+        impl Read for Interrupted {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.1 = !self.1;
+                if self.1 {
+                    return Err(io::ErrorKind::Interrupted.into());
+                }
+                if self.0.is_empty() {
+                    return Ok(0);
+                }
+                let chunk = self.0.remove(0);
+                buf[..chunk.len()].copy_from_slice(&chunk);
+                Ok(chunk.len())
+            }
+        }
+
+        let mut interrupted = Interrupted(vec![vec![FEND, 0x01], vec![FEND]], false);
+        let mut reader = FrameReader::new(&mut interrupted, SpecialChars::default());
+
+        assert_eq!(reader.try_read_frame(), Ok(Some(vec![FEND, 0x01, FEND])));
+        assert_eq!(reader.try_read_frame(), Ok(None));
+    }
 }
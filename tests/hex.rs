@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{
+        decode_hex, encode, encode_hex, HDLCError, HexFrame, SpecialChars, FEND, FESC, TFEND, TFESC,
+    };
+
+    #[test]
+    fn renders_lines_flags_and_escapes() {
+        let chars = SpecialChars::default();
+        let mut payload: Vec<u8> = (0x00..0x10).collect();
+        payload.extend([FESC, 0xAB]);
+        let frame = encode(&payload, chars.clone()).unwrap();
+
+        assert_eq!(
+            HexFrame::new(&frame, chars.clone()).to_string(),
+            "0000: [7E] 00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E\n\
+             0010: 0F <7D> <5D> AB [7E]"
+        );
+        assert_eq!(HexFrame::new(&[], chars).to_string(), "");
+    }
+
+    #[test]
+    fn escape_marks_the_trade_char() {
+        let chars = SpecialChars::default();
+        let frame = [FEND, FESC, TFEND, TFESC, FESC, FEND];
+
+        assert_eq!(
+            format!("{:?}", HexFrame::new(&frame, chars)),
+            "0000: [7E] <7D> <5E> 5D <7D> <7E>"
+        );
+    }
+
+    #[test]
+    fn hex_strings_tolerate_separators() {
+        let chars = SpecialChars::default();
+
+        assert_eq!(
+            encode_hex("0x01, 0X7D-aa_bb\n\tCC", chars.clone()),
+            Ok("7E 01 7D 5D AA BB CC 7E".to_string())
+        );
+        assert_eq!(
+            decode_hex("7e:01:7d:5d:aa:7e", chars.clone()),
+            Ok("01 7D AA".to_string())
+        );
+        assert_eq!(encode_hex("", chars), Ok("7E 7E".to_string()));
+    }
+
+    #[test]
+    fn hex_strings_round_trip() {
+        let chars = SpecialChars::default();
+        let payload = "00 7E 7D 5E FF";
+
+        let frame = encode_hex(payload, chars.clone()).unwrap();
+        assert_eq!(frame, "7E 00 7D 5E 7D 5D 5E FF 7E");
+        assert_eq!(decode_hex(&frame, chars), Ok(payload.to_string()));
+    }
+
+    #[test]
+    fn hex_strings_report_bad_digits() {
+        let chars = SpecialChars::default();
+
+        assert_eq!(
+            encode_hex("01 0g", chars.clone()),
+            Err(HDLCError::InvalidHex { offset: 3 })
+        );
+        assert_eq!(
+            encode_hex("01 2", chars.clone()),
+            Err(HDLCError::InvalidHex { offset: 3 })
+        );
+        assert_eq!(
+            decode_hex("7E 01 7D 02 7E", chars),
+            Err(HDLCError::MissingTradeChar { offset: 2 })
+        );
+    }
+}
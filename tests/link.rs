@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, ErrorKind};
+
+    use hdlc::{DecodeOptions, FcsMode, HDLCError, Link, SpecialChars, FEND, FESC, TFEND, TFESC};
+
+    #[test]
+    fn sends_encoded_frames() {
+        let chars = SpecialChars::default();
+        let mut link = Link::new(Cursor::new(vec![]), Vec::new(), chars);
+
+        assert!(link.send(&[0x01, FEND]).is_ok());
+        assert!(link.send(&[FESC]).is_ok());
+
+        let (_, written) = link.into_inner();
+        assert_eq!(
+            written,
+            vec![FEND, 0x01, FESC, TFEND, FEND, FEND, FESC, TFESC, FEND]
+        );
+    }
+
+    #[test]
+    fn receives_decoded_frames() {
+        let chars = SpecialChars::default();
+        let msg = vec![0x22, FEND, 0x01, FESC, TFESC, FEND, FEND, 0x02, FEND];
+        let mut link = Link::new(Cursor::new(msg), Vec::new(), chars);
+
+        assert_eq!(link.recv().unwrap(), vec![0x01, FESC]);
+        assert_eq!(link.recv().unwrap(), vec![0x02]);
+        assert_eq!(link.recv().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn recv_reports_malformed_frame() {
+        let chars = SpecialChars::default();
+        let msg = vec![FEND, 0x01, FESC, 0x00, FEND, FEND, 0x02, FEND];
+        let mut link = Link::new(Cursor::new(msg), Vec::new(), chars);
+
+        let err = link.recv().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<HDLCError>(),
+            Some(&HDLCError::MissingTradeChar { offset: 2 })
+        );
+        assert_eq!(link.recv().unwrap(), vec![0x02]);
+    }
+
+    #[test]
+    fn counts_crc_failures_and_resyncs() {
+        let options = DecodeOptions {
+            fcs: FcsMode::Crc16,
+            ..DecodeOptions::default()
+        };
+        let mut sender =
+            Link::new(Cursor::new(vec![]), Vec::new(), SpecialChars::default()).options(options);
+        sender.send(&[0x01, 0x02]).unwrap();
+        sender.send(&[0x03]).unwrap();
+        let (_, mut msg) = sender.into_inner();
+        // Corrupt the payload of the first frame and append an unescapable one
+        msg[1] ^= 0x10;
+        msg.extend_from_slice(&[FEND, 0x04, FESC, 0x00, FEND]);
+
+        let mut link =
+            Link::new(Cursor::new(msg), Vec::new(), SpecialChars::default()).options(options);
+        let err = link.recv().unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<HDLCError>(),
+            Some(&HDLCError::FcsMismatch)
+        );
+        assert_eq!(link.recv().unwrap(), vec![0x03]);
+        assert_eq!(link.recv().unwrap_err().kind(), ErrorKind::InvalidData);
+
+        let snapshot = link.metrics().snapshot();
+        assert_eq!(snapshot.frames_received, 1);
+        assert_eq!(snapshot.crc_failures, 1);
+        assert_eq!(snapshot.resyncs, 1);
+    }
+}
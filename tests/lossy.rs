@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "test-utils")]
+    use std::io::{Cursor, Read, Write};
+
+    #[cfg(feature = "test-utils")]
+    use hdlc::test_utils::{LossyChannel, LossyConfig, LossyStats};
+    #[cfg(feature = "test-utils")]
+    use hdlc::{encode_frames, EncodeOptions, FcsMode, FrameReader, SpecialChars, FEND};
+
+    #[cfg(feature = "test-utils")]
+    fn stream(frames: usize) -> Vec<u8> {
+        let payloads: Vec<Vec<u8>> = (0..frames)
+            .map(|i| {
+                let mut payload = vec![i as u8; 8];
+                FcsMode::Crc16.append(&mut payload);
+                payload
+            })
+            .collect();
+        encode_frames(&payloads, SpecialChars::default(), EncodeOptions::default()).unwrap()
+    }
+
+    #[cfg(feature = "test-utils")]
+    fn read_all(data: Vec<u8>, config: LossyConfig) -> (Vec<u8>, LossyStats) {
+        let mut channel = LossyChannel::new(Cursor::new(data), SpecialChars::default(), config);
+        let mut output = Vec::new();
+        channel.read_to_end(&mut output).unwrap();
+        (output, channel.stats())
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn clean_channel_passes_bytes_through() {
+        let data = stream(10);
+
+        let (output, stats) = read_all(data.clone(), LossyConfig::default());
+
+        assert_eq!(output, data);
+        assert_eq!(stats, LossyStats::default());
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn same_seed_corrupts_the_same_way() {
+        let config = LossyConfig {
+            seed: 42,
+            bit_flip: 0.01,
+            drop: 0.01,
+            duplicate: 0.01,
+            truncate: 0.01,
+        };
+
+        let first = read_all(stream(100), config);
+        let second = read_all(stream(100), config);
+        let other = read_all(stream(100), LossyConfig { seed: 43, ..config });
+
+        assert_eq!(first, second);
+        assert_ne!(first.0, other.0);
+        assert!(first.1.flipped > 0);
+        assert!(first.1.dropped > 0);
+        assert!(first.1.duplicated > 0);
+        assert!(first.1.truncated > 0);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn every_byte_corruption() {
+        let data = vec![FEND, 0x01, 0x02, FEND];
+
+        let (doubled, stats) = read_all(
+            data.clone(),
+            LossyConfig {
+                duplicate: 1.0,
+                ..LossyConfig::default()
+            },
+        );
+        assert_eq!(
+            doubled,
+            vec![FEND, FEND, 0x01, 0x01, 0x02, 0x02, FEND, FEND]
+        );
+        assert_eq!(stats.duplicated, 4);
+
+        // Everything dropped reads as the end of the data
+        let (dropped, _) = read_all(
+            data.clone(),
+            LossyConfig {
+                drop: 1.0,
+                ..LossyConfig::default()
+            },
+        );
+        assert!(dropped.is_empty());
+
+        // Truncation keeps the flags
+        let (truncated, stats) = read_all(
+            data.clone(),
+            LossyConfig {
+                truncate: 1.0,
+                ..LossyConfig::default()
+            },
+        );
+        assert_eq!(truncated, vec![FEND, FEND]);
+        assert_eq!(stats.truncated, 1);
+
+        let (flipped, _) = read_all(
+            data.clone(),
+            LossyConfig {
+                bit_flip: 1.0,
+                ..LossyConfig::default()
+            },
+        );
+        for (a, b) in flipped.iter().zip(&data) {
+            assert_eq!((a ^ b).count_ones(), 1);
+        }
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn corrupted_writes_are_caught_by_the_fcs() {
+        let config = LossyConfig {
+            seed: 3,
+            bit_flip: 0.02,
+            ..LossyConfig::default()
+        };
+        let mut channel = LossyChannel::new(Vec::new(), SpecialChars::default(), config);
+        channel.write_all(&stream(50)).unwrap();
+        channel.flush().unwrap();
+        let flipped = channel.stats().flipped;
+        let written = channel.into_inner();
+
+        // Every frame passing the FCS is one of the frames sent
+        let sent: Vec<Vec<u8>> =
+            FrameReader::new(&mut Cursor::new(stream(50)), SpecialChars::default()).collect();
+        let mut reader = Cursor::new(written);
+        let good: Vec<Vec<u8>> = FrameReader::new(&mut reader, SpecialChars::default())
+            .filter(|frame| {
+                hdlc::decode(frame, SpecialChars::default())
+                    .is_ok_and(|payload| FcsMode::Crc16.check(&payload).is_ok())
+            })
+            .collect();
+        assert!(flipped > 0);
+        assert!(good.len() < 50);
+        assert!(good.iter().all(|frame| sent.contains(frame)));
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "test-utils")]
+    use hdlc::test_utils::MalformedFrame;
+    #[cfg(feature = "test-utils")]
+    use hdlc::{
+        decode, decode_with, encode, DecodeOptions, EscapeMap, FcsMode, HDLCError, SpecialChars,
+    };
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn no_faults_builds_the_encoded_frame() {
+        let chars = SpecialChars::default();
+        let payload = [0x01, 0x7E, 0x7D, 0x02];
+
+        let frame = MalformedFrame::new(&payload, chars.clone()).build();
+        assert_eq!(frame, encode(&payload, chars.clone()).unwrap());
+
+        let mut data = payload.to_vec();
+        FcsMode::Crc32.append(&mut data);
+        let frame = MalformedFrame::new(&payload, chars.clone())
+            .fcs(FcsMode::Crc32)
+            .build();
+        assert_eq!(frame, encode(&data, chars).unwrap());
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn faults_hit_their_error_paths() {
+        let chars = SpecialChars::default();
+        let payload = [0x01, 0x7E, 0x02];
+        let build = || MalformedFrame::new(&payload, chars.clone());
+
+        assert_eq!(
+            decode(&build().stray_fesc(0).build(), chars.clone()),
+            Err(HDLCError::MissingTradeChar { offset: 1 })
+        );
+        assert_eq!(
+            decode(&build().stray_fesc(100).build(), chars.clone()),
+            Err(HDLCError::MissingTradeChar { offset: 5 })
+        );
+        assert_eq!(
+            decode(&build().without_opening_flag().build(), chars.clone()),
+            Err(HDLCError::MissingFirstFend)
+        );
+        assert_eq!(
+            decode(&build().without_closing_flag().build(), chars.clone()),
+            Err(HDLCError::Incomplete { needed: Some(1) })
+        );
+
+        let options = DecodeOptions {
+            fcs: FcsMode::Crc16,
+            max_len: Some(32),
+            ..DecodeOptions::default()
+        };
+        let frame = build().fcs(FcsMode::Crc16).bad_fcs().build();
+        assert_eq!(
+            decode_with(&frame, chars.clone(), options),
+            Err(HDLCError::FcsMismatch)
+        );
+        let frame = build().fcs(FcsMode::Crc16).over_length(32).build();
+        assert_eq!(frame.len(), 34);
+        assert_eq!(
+            decode_with(&frame, chars.clone(), options),
+            Err(HDLCError::FrameTooLong)
+        );
+        // The padding alone is valid
+        let frame = build().fcs(FcsMode::Crc16).over_length(33).build();
+        let long = DecodeOptions {
+            max_len: Some(64),
+            ..options
+        };
+        assert_eq!(decode_with(&frame, chars, long).unwrap().len(), 30);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn faults_respect_custom_escapes() {
+        let chars = SpecialChars::default()
+            .with_escape_map(EscapeMap::from_accm(u32::MAX))
+            .unwrap();
+        let frame = MalformedFrame::new(&[0x00, 0x01], chars.clone())
+            .stray_fesc(1)
+            .over_length(10)
+            .build();
+
+        assert_eq!(
+            decode(&frame, chars),
+            Err(HDLCError::MissingTradeChar { offset: 3 })
+        );
+        assert!(frame.len() > 10);
+    }
+}
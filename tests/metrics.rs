@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use hdlc::{
+        Arq, ArqConfig, Link, LinkMetrics, MetricsSnapshot, SpecialChars, MAX_HISTOGRAM_BOUNDS,
+    };
+
+    #[test]
+    fn snapshot_and_reset() {
+        let metrics = LinkMetrics::new();
+        assert_eq!(metrics.snapshot(), MetricsSnapshot::default());
+        assert_eq!(metrics.snapshot().overhead_ratio(), 0.0);
+
+        metrics.record_sent(10, 14);
+        metrics.record_sent(6, 8);
+        metrics.record_resync();
+        metrics.record_retransmission();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.frames_sent, 2);
+        assert_eq!(snapshot.payload_bytes_sent, 16);
+        assert_eq!(snapshot.wire_bytes_sent, 22);
+        assert_eq!(snapshot.resyncs, 1);
+        assert_eq!(snapshot.retransmissions, 1);
+        assert_eq!(snapshot.overhead_ratio(), 22.0 / 16.0);
+
+        metrics.reset();
+        assert_eq!(metrics.snapshot(), MetricsSnapshot::default());
+    }
+
+    #[test]
+    fn counts_across_threads() {
+        let metrics = Arc::new(LinkMetrics::new());
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let metrics = Arc::clone(&metrics);
+                thread::spawn(move || (0..1000).for_each(|_| metrics.record_received(1, 3)))
+            })
+            .collect();
+        workers.into_iter().for_each(|w| w.join().unwrap());
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.frames_received, 4000);
+        assert_eq!(snapshot.wire_bytes_received, 12000);
+    }
+
+    #[test]
+    fn link_counts_frames() {
+        let incoming = Cursor::new(vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x7D, 0x01, 0x7E]);
+        let metrics = Arc::new(LinkMetrics::new());
+        let mut link = Link::new(incoming, Vec::new(), SpecialChars::default())
+            .with_metrics(Arc::clone(&metrics));
+
+        link.send(&[0x02, 0x7D]).unwrap();
+        assert_eq!(link.recv().unwrap(), vec![0x01, 0x7E]);
+        assert!(link.recv().is_err());
+
+        assert!(Arc::ptr_eq(link.metrics(), &metrics));
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.frames_sent, 1);
+        assert_eq!(snapshot.payload_bytes_sent, 2);
+        assert_eq!(snapshot.wire_bytes_sent, 5);
+        assert_eq!(snapshot.frames_received, 1);
+        assert_eq!(snapshot.payload_bytes_received, 2);
+        assert_eq!(snapshot.wire_bytes_received, 5);
+        assert_eq!(snapshot.resyncs, 1);
+    }
+
+    #[test]
+    fn arq_counts_retransmissions() {
+        let metrics = Arc::new(LinkMetrics::new());
+        let mut arq = Arq::new(SpecialChars::default(), ArqConfig::default())
+            .unwrap()
+            .with_metrics(Arc::clone(&metrics));
+
+        arq.send(&[0x01], 0).unwrap();
+        arq.poll(1000).unwrap();
+        arq.poll(2000).unwrap();
+
+        assert_eq!(metrics.snapshot().retransmissions, 2);
+    }
+
+    #[test]
+    fn histogram_buckets_sizes() {
+        let metrics = LinkMetrics::new().with_size_histogram(&[64, 8, 64, 32]);
+        for len in [2, 8, 9, 32, 33, 64, 65, 1000] {
+            metrics.record_sent(len, len);
+        }
+        metrics.record_received(1, 3);
+
+        let histogram = metrics.histogram().unwrap();
+        assert_eq!(histogram.bounds, vec![8, 32, 64]);
+        assert_eq!(histogram.sent, vec![2, 2, 2, 2]);
+        assert_eq!(histogram.received, vec![1, 0, 0, 0]);
+
+        metrics.reset();
+        assert_eq!(metrics.histogram().unwrap().sent, vec![0; 4]);
+        assert_eq!(LinkMetrics::new().histogram(), None);
+    }
+
+    #[test]
+    fn histogram_is_bounded() {
+        let bounds: Vec<usize> = (1..=1000).rev().collect();
+        let metrics = LinkMetrics::new().with_size_histogram(&bounds);
+        metrics.record_received(5000, 5000);
+
+        let histogram = metrics.histogram().unwrap();
+        assert_eq!(histogram.bounds.len(), MAX_HISTOGRAM_BOUNDS);
+        assert_eq!(histogram.bounds[0], 1);
+        assert_eq!(histogram.received.last(), Some(&1));
+    }
+
+    #[test]
+    fn throughput_rolls_over_window() {
+        let metrics = LinkMetrics::new().with_throughput(Duration::from_millis(200), Some(1000));
+        assert_eq!(LinkMetrics::new().throughput(), None);
+
+        metrics.record_received(10, 20);
+        metrics.record_sent(40, 50);
+        let throughput = metrics.throughput().unwrap();
+        // At most 20 bytes over at least one 20 ms slot
+        assert!(throughput.bytes_in_per_sec > 0.0);
+        assert!(throughput.bytes_in_per_sec <= 1000.0);
+        assert_eq!(
+            throughput.bytes_out_per_sec,
+            2.5 * throughput.bytes_in_per_sec
+        );
+        assert!(throughput.utilization_out.unwrap() > 0.0);
+
+        thread::sleep(Duration::from_millis(250));
+        let throughput = metrics.throughput().unwrap();
+        assert_eq!(throughput.bytes_in_per_sec, 0.0);
+        assert_eq!(throughput.bytes_out_per_sec, 0.0);
+        assert_eq!(throughput.utilization_in, Some(0.0));
+
+        metrics.record_sent(1, 100);
+        let throughput = metrics.throughput().unwrap();
+        assert_eq!(throughput.bytes_out_per_sec, 500.0);
+        assert_eq!(throughput.utilization_out, Some(0.5));
+        assert_eq!(metrics.snapshot().wire_bytes_sent, 150);
+    }
+
+    #[test]
+    fn throughput_without_line_rate() {
+        let metrics = LinkMetrics::new().with_throughput(Duration::from_secs(1), None);
+        metrics.record_sent(1, 3);
+
+        let throughput = metrics.throughput().unwrap();
+        assert!(throughput.bytes_out_per_sec > 0.0);
+        assert_eq!(throughput.utilization_out, None);
+
+        metrics.reset();
+        assert_eq!(metrics.throughput().unwrap().bytes_out_per_sec, 0.0);
+    }
+}
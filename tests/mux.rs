@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use hdlc::{encode, HDLCError, Mux, SpecialChars, MUX_PORTS};
+
+    #[test]
+    fn frames_are_routed_by_port() {
+        let chars = SpecialChars::default();
+        let mut local = Mux::new(chars.clone()).unwrap();
+        let mut remote = Mux::new(chars).unwrap();
+        let control = local.channel(0).unwrap();
+        let bulk = local.channel(15).unwrap();
+        let remote_control = remote.channel(0).unwrap();
+        let remote_bulk = remote.channel(15).unwrap();
+
+        bulk.send(&[0x7E, 0x01]).unwrap();
+        control.send(&[0x02]).unwrap();
+        bulk.send(&[0x03]).unwrap();
+        while let Some(frame) = local.poll_transmit() {
+            remote.receive(&frame).unwrap();
+        }
+
+        assert_eq!(remote_control.recv(), Some(vec![0x02]));
+        assert_eq!(remote_control.recv(), None);
+        assert_eq!(remote_bulk.recv(), Some(vec![0x7E, 0x01]));
+        assert_eq!(remote_bulk.recv(), Some(vec![0x03]));
+        assert_eq!(remote_bulk.port(), 15);
+    }
+
+    #[test]
+    fn frames_without_channel_are_dropped() {
+        let chars = SpecialChars::default();
+        let mut mux = Mux::new(chars.clone()).unwrap();
+        let channel = mux.channel(2).unwrap();
+
+        assert_eq!(
+            mux.receive(&encode(&[0x30, 0x01], chars.clone()).unwrap()),
+            Ok(None)
+        );
+        // Not a data frame
+        assert_eq!(
+            mux.receive(&encode(&[0x21, 0x01], chars.clone()).unwrap()),
+            Ok(None)
+        );
+        // No header
+        assert_eq!(mux.receive(&[0x7E, 0x7E]), Ok(None));
+        assert_eq!(
+            mux.receive(&encode(&[0x20, 0x01], chars).unwrap()),
+            Ok(Some(2))
+        );
+        assert_eq!(channel.recv(), Some(vec![0x01]));
+        assert_eq!(channel.recv(), None);
+    }
+
+    #[test]
+    fn malformed_frames_and_ports_are_reported() {
+        let mut mux = Mux::new(SpecialChars::default()).unwrap();
+
+        assert!(mux.channel(MUX_PORTS).is_none());
+        assert_eq!(
+            mux.receive(&[0x7E, 0x10, 0x7D, 0x7E]),
+            Err(HDLCError::MissingTradeChar { offset: 2 })
+        );
+        assert!(Mux::new(SpecialChars::new(0x7E, 0x7E, 0x5E, 0x5D)).is_err());
+    }
+
+    #[test]
+    fn channels_send_from_other_threads() {
+        let mut mux = Mux::new(SpecialChars::default()).unwrap();
+        let handles: Vec<_> = (0..4)
+            .map(|port| {
+                let channel = mux.channel(port).unwrap();
+                thread::spawn(move || channel.send(&[port; 3]).unwrap())
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut frames: Vec<Vec<u8>> = std::iter::from_fn(|| mux.poll_transmit()).collect();
+        frames.sort();
+        assert_eq!(
+            frames,
+            (0..4u8)
+                .map(|port| vec![0x7E, port << 4, port, port, port, 0x7E])
+                .collect::<Vec<_>>()
+        );
+    }
+}
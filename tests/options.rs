@@ -0,0 +1,321 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use hdlc::{
+        decode_frames_with, decode_with, encode, encode_frames, encode_with, fcs16, fcs32,
+        DecodeOptions, Decoder, EncodeOptions, FcsMode, FrameReader, Framing, HDLCError,
+        SpecialChars,
+    };
+
+    /// Encodes `payload` with its FCS appended
+    fn frame_with_fcs(payload: &[u8], fcs: FcsMode) -> Vec<u8> {
+        let mut payload = payload.to_vec();
+        fcs.append(&mut payload);
+        encode(&payload, SpecialChars::default()).unwrap()
+    }
+
+    #[test]
+    fn fcs_check_values() {
+        assert_eq!(fcs16(b"123456789"), 0x906E);
+        assert_eq!(fcs32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            FcsMode::Crc32.check(&[0x01, 0x02]),
+            Err(HDLCError::FcsMismatch)
+        );
+    }
+
+    #[test]
+    fn decode_with_fcs() {
+        for fcs in [FcsMode::None, FcsMode::Crc16, FcsMode::Crc32] {
+            let options = DecodeOptions {
+                fcs,
+                ..DecodeOptions::strict()
+            };
+            let mut frame = frame_with_fcs(&[0x01, 0x7E, 0x02], fcs);
+
+            assert_eq!(
+                decode_with(&frame, SpecialChars::default(), options),
+                Ok(vec![0x01, 0x7E, 0x02])
+            );
+
+            if fcs != FcsMode::None {
+                let len = frame.len();
+                frame[len - 2] ^= 0x01;
+                assert_eq!(
+                    decode_with(&frame, SpecialChars::default(), options),
+                    Err(HDLCError::FcsMismatch)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn decode_with_strict_and_tolerant() {
+        let chars = SpecialChars::default();
+        let input = [0x55, 0x7E, 0x01, 0x7E, 0x55];
+
+        assert_eq!(
+            decode_with(&input, chars.clone(), DecodeOptions::strict()),
+            Err(HDLCError::MissingFirstFend)
+        );
+        assert_eq!(
+            decode_with(&input[1..], chars.clone(), DecodeOptions::strict()),
+            Err(HDLCError::FendCharInData { offset: 2 })
+        );
+        assert_eq!(
+            decode_with(&input, chars.clone(), DecodeOptions::tolerant()),
+            Ok(vec![0x01])
+        );
+        assert_eq!(
+            decode_with(
+                &[0x55, 0x7E, 0x7D, 0x7E],
+                chars.clone(),
+                DecodeOptions::tolerant()
+            ),
+            Err(HDLCError::MissingTradeChar { offset: 2 })
+        );
+
+        let options = DecodeOptions {
+            max_len: Some(2),
+            ..DecodeOptions::tolerant()
+        };
+        assert_eq!(
+            decode_with(&input, chars, options),
+            Err(HDLCError::FrameTooLong)
+        );
+    }
+
+    #[test]
+    fn reader_and_decoder_use_options() {
+        let options = DecodeOptions {
+            fcs: FcsMode::Crc16,
+            ..DecodeOptions::default()
+        };
+        let mut data = frame_with_fcs(&[0x01], FcsMode::Crc16);
+        data.extend(encode(&[0x02, 0x03, 0x04], SpecialChars::default()).unwrap());
+        data.extend(frame_with_fcs(&[0x05], FcsMode::Crc16));
+
+        let mut reader = Cursor::new(data.clone());
+        let frames: Vec<Vec<u8>> = FrameReader::builder(SpecialChars::default())
+            .options(options)
+            .build(&mut reader)
+            .collect();
+        assert_eq!(frames, vec![vec![0x01], vec![0x05]]);
+
+        let mut decoder = Decoder::with_options(SpecialChars::default(), options).unwrap();
+        decoder.push(&data);
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![0x01])));
+        assert_eq!(decoder.next_frame(), Some(Err(HDLCError::FcsMismatch)));
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![0x05])));
+    }
+
+    #[test]
+    fn decode_with_fend_fill() {
+        let chars = SpecialChars::default();
+        let input = [0x7E, 0x7E, 0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x7E];
+        let options = DecodeOptions {
+            fend_fill: true,
+            ..DecodeOptions::strict()
+        };
+
+        assert_eq!(
+            decode_with(&input, chars.clone(), DecodeOptions::strict()),
+            Err(HDLCError::FendCharInData { offset: 1 })
+        );
+        assert_eq!(
+            decode_with(&input, chars.clone(), options),
+            Ok(vec![0x01, 0x7E])
+        );
+        assert_eq!(
+            decode_with(&[0x7E, 0x01, 0x7E, 0x02], chars, options),
+            Err(HDLCError::FendCharInData { offset: 2 })
+        );
+    }
+
+    #[test]
+    fn reader_skips_fend_fill() {
+        let data = [
+            0x7E, 0x7E, 0x7E, 0x01, 0x7E, 0x7E, 0x7E, 0x7E, 0x02, 0x7E, 0x7E,
+        ];
+
+        for chunk_size in [1, 2, 3, 1024] {
+            let mut reader = Cursor::new(data.to_vec());
+            let frames: Vec<Vec<u8>> = FrameReader::builder(SpecialChars::default())
+                .chunk_size(chunk_size)
+                .decode(true)
+                .build(&mut reader)
+                .collect();
+            assert_eq!(frames, vec![vec![0x01], vec![0x02]]);
+        }
+    }
+
+    #[test]
+    fn empty_frame_policy() {
+        let data = [0x7E, 0x7E, 0x7E, 0x01, 0x7E, 0x7E, 0x7E, 0x7E, 0x02, 0x7E];
+        let keep = DecodeOptions {
+            empty_frames: true,
+            ..DecodeOptions::default()
+        };
+
+        let (payloads, _) =
+            decode_frames_with(&data, SpecialChars::default(), DecodeOptions::default()).unwrap();
+        assert_eq!(payloads, vec![vec![0x01], vec![0x02]]);
+        let (payloads, _) = decode_frames_with(&data, SpecialChars::default(), keep).unwrap();
+        assert_eq!(payloads, vec![vec![], vec![0x01], vec![], vec![0x02]]);
+
+        for chunk_size in [1, 3, 1024] {
+            let mut reader = Cursor::new(data.to_vec());
+            let frames: Vec<Vec<u8>> = FrameReader::builder(SpecialChars::default())
+                .chunk_size(chunk_size)
+                .options(keep)
+                .build(&mut reader)
+                .collect();
+            assert_eq!(frames, vec![vec![], vec![0x01], vec![], vec![0x02]]);
+        }
+
+        let mut decoder = Decoder::with_options(SpecialChars::default(), keep).unwrap();
+        decoder.push(&data[..2]);
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![])));
+    }
+
+    #[test]
+    fn terminator_only_framing() {
+        let chars = SpecialChars::default();
+        let encode_options = EncodeOptions {
+            framing: Framing::TerminatorOnly,
+            ..EncodeOptions::default()
+        };
+        let options = DecodeOptions {
+            framing: Framing::TerminatorOnly,
+            ..DecodeOptions::default()
+        };
+
+        let data = encode_frames(
+            &[&[0x01, 0x7E][..], &[], &[0x02]],
+            chars.clone(),
+            encode_options,
+        )
+        .unwrap();
+        assert_eq!(data, vec![0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x02, 0x7E]);
+
+        assert_eq!(
+            decode_with(&data[..4], chars.clone(), options),
+            Ok(vec![0x01, 0x7E])
+        );
+        assert_eq!(
+            decode_with(&data, chars.clone(), options),
+            Err(HDLCError::FendCharInData { offset: 3 })
+        );
+        assert_eq!(
+            decode_with(&[0x01, 0x02], chars.clone(), options),
+            Err(HDLCError::Incomplete { needed: Some(1) })
+        );
+        assert_eq!(
+            decode_with(&[0x01, 0x7D, 0x7E], chars.clone(), options),
+            Err(HDLCError::MissingTradeChar { offset: 1 })
+        );
+
+        let (payloads, consumed) = decode_frames_with(&data, chars.clone(), options).unwrap();
+        assert_eq!(payloads, vec![vec![0x01, 0x7E], vec![0x02]]);
+        assert_eq!(consumed, data.len());
+
+        for chunk_size in [1, 3, 1024] {
+            let mut reader = Cursor::new(data.clone());
+            let frames: Vec<Vec<u8>> = FrameReader::builder(chars.clone())
+                .chunk_size(chunk_size)
+                .options(options)
+                .build(&mut reader)
+                .collect();
+            assert_eq!(frames, vec![vec![0x01, 0x7E], vec![0x02]]);
+        }
+
+        let mut decoder = Decoder::with_options(chars, options).unwrap();
+        decoder.push(&data[..5]);
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![0x01, 0x7E])));
+        assert_eq!(decoder.next_frame(), None);
+        decoder.push(&data[5..]);
+        assert_eq!(decoder.next_frame(), Some(Ok(vec![0x02])));
+    }
+
+    #[test]
+    fn doubled_flags() {
+        let chars = SpecialChars::default();
+        let doubled = EncodeOptions {
+            framing: Framing::DoubledFlags,
+            ..EncodeOptions::default()
+        };
+        let options = DecodeOptions {
+            framing: Framing::DoubledFlags,
+            ..DecodeOptions::default()
+        };
+
+        let frame = encode_with(&[0x01, 0x7E], chars.clone(), doubled).unwrap();
+        assert_eq!(frame, vec![0x7E, 0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E]);
+        assert_eq!(
+            decode_with(&frame, chars.clone(), options),
+            Ok(vec![0x01, 0x7E])
+        );
+
+        let shared = EncodeOptions {
+            shared_flags: true,
+            ..doubled
+        };
+        let data = encode_frames(&[[0x01], [0x02]], chars.clone(), shared).unwrap();
+        assert_eq!(data, vec![0x7E, 0x7E, 0x01, 0x7E, 0x7E, 0x02, 0x7E, 0x7E]);
+
+        let (payloads, consumed) = decode_frames_with(&data, chars, options).unwrap();
+        assert_eq!(payloads, vec![vec![0x01], vec![0x02]]);
+        // The last flag may still open a frame
+        assert_eq!(&data[consumed..], &[0x7E]);
+    }
+
+    #[test]
+    fn max_payload_len() {
+        let chars = SpecialChars::default();
+        let options = EncodeOptions {
+            max_payload_len: Some(2),
+            ..EncodeOptions::default()
+        };
+
+        // The limit applies to the payload, not to its escaped form
+        assert_eq!(
+            encode_with(&[0x7E, 0x7E], chars.clone(), options),
+            Ok(vec![0x7E, 0x7D, 0x5E, 0x7D, 0x5E, 0x7E])
+        );
+        assert_eq!(
+            encode_with(&[0x01, 0x02, 0x03], chars.clone(), options),
+            Err(HDLCError::PayloadTooLarge)
+        );
+        assert_eq!(
+            encode_frames(&[&[0x01][..], &[0x01, 0x02, 0x03]], chars, options),
+            Err(HDLCError::PayloadTooLarge)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_options() {
+        let chars = SpecialChars::default()
+            .with_escape_map(hdlc::EscapeMap::new().with(0x11))
+            .unwrap();
+        let decode_options = DecodeOptions {
+            fcs: FcsMode::Crc32,
+            max_len: Some(128),
+            ..DecodeOptions::tolerant()
+        };
+        let encode_options = EncodeOptions {
+            framing: Framing::TerminatorOnly,
+            max_payload_len: Some(64),
+            ..EncodeOptions::default()
+        };
+
+        let json = serde_json::to_string(&(&chars, decode_options, encode_options)).unwrap();
+        let (read_chars, read_decode, read_encode): (SpecialChars, DecodeOptions, EncodeOptions) =
+            serde_json::from_str(&json).unwrap();
+
+        assert_eq!(read_chars, chars);
+        assert_eq!(read_decode, decode_options);
+        assert_eq!(read_encode, encode_options);
+    }
+}
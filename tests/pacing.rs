@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+    use std::time::{Duration, Instant};
+
+    use hdlc::{EncodingWriter, FrameReader, Link, Pacing, SpecialChars};
+
+    #[test]
+    fn link_sends_idle_flags_between_frames() {
+        let mut link = Link::new(Cursor::new(Vec::new()), Vec::new(), SpecialChars::default())
+            .pacing(Pacing::IdleFlags(3));
+        link.send(&[0x01]).unwrap();
+        link.send(&[0x02]).unwrap();
+
+        let (_, sent) = link.into_inner();
+        assert_eq!(
+            sent,
+            vec![0x7E, 0x01, 0x7E, 0x7E, 0x7E, 0x7E, 0x7E, 0x02, 0x7E]
+        );
+
+        let frames: Vec<_> = FrameReader::builder(SpecialChars::default())
+            .decode(true)
+            .build(&mut sent.as_slice())
+            .collect();
+        assert_eq!(frames, vec![vec![0x01], vec![0x02]]);
+    }
+
+    #[test]
+    fn sends_long_runs_of_idle_flags() {
+        let mut link = Link::new(Cursor::new(Vec::new()), Vec::new(), SpecialChars::default())
+            .pacing(Pacing::IdleFlags(150));
+        link.send(&[0x01]).unwrap();
+        link.send(&[0x02]).unwrap();
+
+        let (_, sent) = link.into_inner();
+        assert_eq!(sent.len(), 156);
+        assert!(sent[2..154].iter().all(|&b| b == 0x7E));
+        assert_eq!(&sent[154..], &[0x02, 0x7E]);
+    }
+
+    #[test]
+    fn link_waits_for_gap() {
+        let gap = Duration::from_millis(30);
+        let mut link = Link::new(Cursor::new(Vec::new()), Vec::new(), SpecialChars::default())
+            .pacing(Pacing::Gap(gap));
+
+        let start = Instant::now();
+        link.send(&[0x01]).unwrap();
+        link.send(&[0x02]).unwrap();
+        link.send(&[0x03]).unwrap();
+        assert!(start.elapsed() >= gap * 2);
+
+        let (_, sent) = link.into_inner();
+        assert_eq!(sent.len(), 9);
+    }
+
+    #[test]
+    fn writer_paces_frames() {
+        let gap = Duration::from_millis(20);
+        let mut writer = EncodingWriter::new(Vec::new(), SpecialChars::default())
+            .unwrap()
+            .pacing(Pacing::Gap(gap));
+
+        let start = Instant::now();
+        writer.write_all(&[0x01]).unwrap();
+        writer.finish().unwrap();
+        writer.write_all(&[0x02]).unwrap();
+        assert!(start.elapsed() >= gap);
+        writer.write_all(&[0x03]).unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(
+            writer.into_inner(),
+            vec![0x7E, 0x01, 0x7E, 0x7E, 0x02, 0x03, 0x7E]
+        );
+    }
+
+    #[test]
+    fn no_pacing_by_default() {
+        let mut writer = EncodingWriter::new(Vec::new(), SpecialChars::default())
+            .unwrap()
+            .pacing(Pacing::default());
+        writer.write_all(&[0x01]).unwrap();
+        writer.finish().unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(writer.into_inner(), vec![0x7E, 0x01, 0x7E, 0x7E, 0x7E]);
+    }
+}
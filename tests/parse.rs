@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{parse_frame, HDLCError, ParseError, SpecialChars};
+
+    #[test]
+    fn parse_frame_needs_more_bytes() {
+        let chars = SpecialChars::default();
+
+        assert_eq!(parse_frame(&[], chars.clone()), Err(ParseError::Needed(2)));
+        assert_eq!(
+            parse_frame(&[0x7E], chars.clone()),
+            Err(ParseError::Needed(1))
+        );
+        assert_eq!(
+            parse_frame(&[0x7E, 0x01, 0x02], chars.clone()),
+            Err(ParseError::Needed(1))
+        );
+        assert_eq!(
+            parse_frame(&[0x7E, 0x01, 0x7D], chars),
+            Err(ParseError::Needed(2))
+        );
+    }
+
+    #[test]
+    fn parse_frame_returns_remaining_input() {
+        let chars = SpecialChars::default();
+        let input = [0x7E, 0x01, 0x7E, 0x7E, 0x02, 0x7E, 0x7E];
+
+        let (rest, first) = parse_frame(&input, chars.clone()).unwrap();
+        assert_eq!(first, vec![0x01]);
+        assert_eq!(rest, &[0x7E, 0x02, 0x7E, 0x7E]);
+
+        let (rest, second) = parse_frame(rest, chars.clone()).unwrap();
+        assert_eq!(second, vec![0x02]);
+        assert_eq!(parse_frame(rest, chars), Err(ParseError::Needed(1)));
+    }
+
+    #[test]
+    fn parse_frame_fails_on_malformed_frame() {
+        let chars = SpecialChars::default();
+
+        assert_eq!(
+            parse_frame(&[0x01, 0x7E], chars.clone()),
+            Err(ParseError::Invalid(HDLCError::MissingFirstFend))
+        );
+        assert_eq!(
+            parse_frame(&[0x7E, 0x7D, 0x01, 0x7E], chars),
+            Err(ParseError::Invalid(HDLCError::MissingTradeChar {
+                offset: 1
+            }))
+        );
+        assert_eq!(
+            parse_frame(&[0x7E, 0x7E], SpecialChars::new(0x7E, 0x7E, 0x5E, 0x5D)),
+            Err(ParseError::Invalid(HDLCError::DuplicateSpecialChar))
+        );
+    }
+
+    #[test]
+    fn parse_error_displays() {
+        assert_eq!(
+            ParseError::Needed(2).to_string(),
+            "Incomplete frame, at least 2 more bytes are needed."
+        );
+        assert_eq!(
+            ParseError::Invalid(HDLCError::MissingFirstFend).to_string(),
+            "Missing first FEND character."
+        );
+    }
+}
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "pcap")]
+    use std::io::{self, Cursor};
+    #[cfg(feature = "pcap")]
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[cfg(feature = "pcap")]
+    use hdlc::{
+        Link, PcapReader, PcapWriter, SpecialChars, LINKTYPE_C_HDLC, LINKTYPE_PPP_HDLC,
+        LINKTYPE_USER0,
+    };
+
+    #[cfg(feature = "pcap")]
+    fn u32_at(file: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(file[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[cfg(feature = "pcap")]
+    #[test]
+    fn writes_section_and_interface() {
+        let file = PcapWriter::new(Vec::new(), LINKTYPE_C_HDLC)
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(file.len(), 28 + 20);
+        assert_eq!(u32_at(&file, 0), 0x0A0D_0D0A);
+        assert_eq!(u32_at(&file, 4), 28);
+        assert_eq!(u32_at(&file, 8), 0x1A2B_3C4D);
+        assert_eq!(u32_at(&file, 24), 28);
+
+        assert_eq!(u32_at(&file, 28), 1);
+        assert_eq!(u32_at(&file, 32), 20);
+        assert_eq!(&file[36..38], &LINKTYPE_C_HDLC.to_le_bytes());
+        assert_eq!(u32_at(&file, 44), 20);
+    }
+
+    #[cfg(feature = "pcap")]
+    #[test]
+    fn writes_padded_packet_with_timestamp() {
+        let mut capture = PcapWriter::new(Vec::new(), LINKTYPE_PPP_HDLC).unwrap();
+        let timestamp = UNIX_EPOCH + Duration::from_micros(0x1_0000_0002);
+        capture
+            .write_frame(&[0xFF, 0x03, 0xC0, 0x21, 0x01], timestamp)
+            .unwrap();
+        let file = capture.into_inner();
+
+        let packet = &file[48..];
+        assert_eq!(packet.len(), 40);
+        assert_eq!(u32_at(packet, 0), 6);
+        assert_eq!(u32_at(packet, 4), 40);
+        assert_eq!(u32_at(packet, 8), 0);
+        assert_eq!(u32_at(packet, 12), 1);
+        assert_eq!(u32_at(packet, 16), 2);
+        assert_eq!(u32_at(packet, 20), 5);
+        assert_eq!(u32_at(packet, 24), 5);
+        assert_eq!(&packet[28..36], &[0xFF, 0x03, 0xC0, 0x21, 0x01, 0, 0, 0]);
+        assert_eq!(u32_at(packet, 36), 40);
+    }
+
+    #[cfg(feature = "pcap")]
+    #[test]
+    fn writes_packets_in_order() {
+        let mut capture = PcapWriter::new(Vec::new(), LINKTYPE_USER0).unwrap();
+        capture.write_frame_now(&[0x7E, 0x01, 0x7E]).unwrap();
+        capture.write_frame_now(&[]).unwrap();
+        capture.flush().unwrap();
+        let file = capture.get_ref();
+
+        assert_eq!(u32_at(file, 48 + 4), 36);
+        assert_eq!(u32_at(file, 48 + 20), 3);
+        assert_eq!(u32_at(file, 84), 6);
+        assert_eq!(u32_at(file, 84 + 4), 32);
+        assert_eq!(u32_at(file, 84 + 20), 0);
+        assert_eq!(file.len(), 84 + 32);
+    }
+
+    #[cfg(feature = "pcap")]
+    #[test]
+    fn reads_back_written_capture() {
+        let mut capture = PcapWriter::new(Vec::new(), LINKTYPE_USER0).unwrap();
+        let first = UNIX_EPOCH + Duration::from_micros(1_500_000);
+        let second = UNIX_EPOCH + Duration::from_micros(0x1_0000_0002);
+        capture.write_frame(&[0x7E, 0x01, 0x7E], first).unwrap();
+        capture.write_frame(&[], second).unwrap();
+        let file = capture.into_inner();
+
+        let frames: Vec<_> = PcapReader::new(file.as_slice())
+            .unwrap()
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].frame, vec![0x7E, 0x01, 0x7E]);
+        assert_eq!(frames[0].received, first);
+        assert_eq!(frames[1].frame, Vec::<u8>::new());
+        assert_eq!(frames[1].received, second);
+    }
+
+    #[cfg(feature = "pcap")]
+    #[test]
+    fn reads_big_endian_pcap_with_nanoseconds() {
+        let mut file = Vec::new();
+        file.extend_from_slice(&0xA1B2_3C4Du32.to_be_bytes());
+        file.extend_from_slice(&[0, 2, 0, 4]);
+        file.extend_from_slice(&[0; 8]);
+        file.extend_from_slice(&65535u32.to_be_bytes());
+        file.extend_from_slice(&u32::from(LINKTYPE_C_HDLC).to_be_bytes());
+        file.extend_from_slice(&3u32.to_be_bytes());
+        file.extend_from_slice(&250u32.to_be_bytes());
+        file.extend_from_slice(&2u32.to_be_bytes());
+        file.extend_from_slice(&2u32.to_be_bytes());
+        file.extend_from_slice(&[0x0F, 0x00]);
+
+        let mut reader = PcapReader::new(file.as_slice()).unwrap();
+        assert_eq!(reader.link_type(), Some(LINKTYPE_C_HDLC));
+        let frame = reader.next().unwrap().unwrap();
+        assert_eq!(frame.frame, vec![0x0F, 0x00]);
+        assert_eq!(frame.received, UNIX_EPOCH + Duration::new(3, 250));
+        assert!(reader.next().is_none());
+    }
+
+    #[cfg(feature = "pcap")]
+    #[test]
+    fn reads_pcapng_resolution_and_skips_other_blocks() {
+        let mut file = PcapWriter::new(Vec::new(), LINKTYPE_PPP_HDLC)
+            .unwrap()
+            .into_inner();
+        // A second interface with millisecond timestamps
+        let idb: [u32; 8] = [1, 32, 104, 0, 0x0001_0009, 3, 0, 32];
+        idb.iter()
+            .for_each(|w| file.extend_from_slice(&w.to_le_bytes()));
+        // A name resolution block
+        let nrb: [u32; 4] = [4, 16, 0, 16];
+        nrb.iter()
+            .for_each(|w| file.extend_from_slice(&w.to_le_bytes()));
+        // A packet of the second interface at 2.5 seconds
+        let epb: [u32; 9] = [6, 36, 1, 0, 2500, 1, 1, 0x42, 36];
+        epb.iter()
+            .for_each(|w| file.extend_from_slice(&w.to_le_bytes()));
+
+        let mut reader = PcapReader::new(file.as_slice()).unwrap();
+        assert_eq!(reader.link_type(), None);
+        let frame = reader.next().unwrap().unwrap();
+        assert_eq!(frame.frame, vec![0x42]);
+        assert_eq!(frame.received, UNIX_EPOCH + Duration::from_millis(2500));
+        assert_eq!(reader.link_type(), Some(LINKTYPE_C_HDLC));
+        assert!(reader.next().is_none());
+    }
+
+    #[cfg(feature = "pcap")]
+    #[test]
+    fn rejects_unknown_format() {
+        let err = PcapReader::new(&[0x01, 0x02, 0x03, 0x04][..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "pcap")]
+    #[test]
+    fn reports_truncated_packet_once() {
+        let mut capture = PcapWriter::new(Vec::new(), LINKTYPE_USER0).unwrap();
+        capture.write_frame_now(&[0x01, 0x02, 0x03]).unwrap();
+        let mut file = capture.into_inner();
+        file.truncate(file.len() - 6);
+
+        let mut reader = PcapReader::new(file.as_slice()).unwrap();
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+
+    #[cfg(feature = "pcap")]
+    #[test]
+    fn replays_into_link() {
+        let mut capture = PcapWriter::new(Vec::new(), LINKTYPE_USER0).unwrap();
+        let start = UNIX_EPOCH + Duration::from_secs(100);
+        capture.write_frame(&[0x01, 0x7E], start).unwrap();
+        capture
+            .write_frame(&[0x02], start + Duration::from_millis(20))
+            .unwrap();
+        let file = capture.into_inner();
+
+        let mut link = Link::new(Cursor::new(Vec::new()), Vec::new(), SpecialChars::default());
+        let begin = std::time::Instant::now();
+        let count = PcapReader::new(file.as_slice())
+            .unwrap()
+            .replay(true, |frame| link.send(frame))
+            .unwrap();
+        assert!(begin.elapsed() >= Duration::from_millis(20));
+        assert_eq!(count, 2);
+
+        let (_, sent) = link.into_inner();
+        assert_eq!(sent, vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x02, 0x7E]);
+    }
+}
@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{BufferPool, HDLCError, SpecialChars, FEND, FESC, TFEND};
+
+    #[test]
+    fn pool_reuses_buffers() {
+        let pool = BufferPool::new(1);
+        let chars = SpecialChars::default();
+
+        let frame = pool.encode(&[0x01; 100], chars.clone()).unwrap();
+        let ptr = frame.as_ptr();
+        drop(frame);
+
+        let frame = pool.decode(&[FEND, FESC, TFEND, FEND], chars).unwrap();
+        assert_eq!(*frame, vec![FEND]);
+        assert_eq!(frame.as_ptr(), ptr);
+        assert_eq!(pool.idle(), 0);
+    }
+
+    #[test]
+    fn pool_keeps_at_most_max_idle() {
+        let pool = BufferPool::new(1);
+        let mut first = pool.get();
+        let mut second = pool.get();
+        first.push(0x01);
+        second.push(0x02);
+
+        drop(first);
+        drop(second);
+        assert_eq!(pool.idle(), 1);
+        assert!(pool.get().is_empty());
+
+        assert_eq!(
+            pool.decode(&[0x01], SpecialChars::default()).err(),
+            Some(HDLCError::MissingFirstFend)
+        );
+        assert_eq!(pool.idle(), 1);
+        assert_eq!(pool.get().into_inner(), Vec::<u8>::new());
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+
+    use hdlc::{decode_frames, HDLCError, PrioritySink, SpecialChars};
+
+    /// Non-blocking writer taking at most `limit` bytes until it is opened up again
+    struct SlowWriter {
+        data: Vec<u8>,
+        limit: usize,
+    }
+
+    impl Write for SlowWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.limit == 0 {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            let len = buf.len().min(self.limit);
+            self.data.extend_from_slice(&buf[..len]);
+            self.limit -= len;
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn sent(data: &[u8]) -> Vec<Vec<u8>> {
+        decode_frames(data, SpecialChars::default()).unwrap().0
+    }
+
+    #[test]
+    fn higher_classes_go_first_in_order() {
+        let mut sink = PrioritySink::new(Vec::new(), SpecialChars::default()).unwrap();
+        sink.enqueue(&[0x01], 0).unwrap();
+        sink.enqueue(&[0x02], 2).unwrap();
+        sink.enqueue(&[0x03], 1).unwrap();
+        sink.enqueue(&[0x04], 2).unwrap();
+        assert_eq!(sink.queued(), 4);
+        assert_eq!(sink.buffered(), 12);
+
+        sink.flush().unwrap();
+
+        assert_eq!(
+            sent(sink.get_ref()),
+            vec![vec![0x02], vec![0x04], vec![0x03], vec![0x01]]
+        );
+        assert_eq!(sink.queued(), 0);
+        assert_eq!(sink.buffered(), 0);
+    }
+
+    #[test]
+    fn frame_on_the_wire_is_finished_first() {
+        let writer = SlowWriter {
+            data: Vec::new(),
+            limit: 3,
+        };
+        let mut sink = PrioritySink::new(writer, SpecialChars::default()).unwrap();
+        sink.enqueue(&[0x01, 0x02, 0x03, 0x04], 0).unwrap();
+
+        sink.writable().unwrap();
+        assert_eq!(sink.get_ref().data, vec![0x7E, 0x01, 0x02]);
+
+        // An urgent frame waits for the rest of the bulk frame, and then overtakes the queue
+        sink.enqueue(&[0x05], 0).unwrap();
+        sink.enqueue(&[0x7E], 9).unwrap();
+        assert_eq!(sink.queued(), 3);
+        assert!(matches!(
+            sink.flush(),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock
+        ));
+
+        sink.get_mut().limit = usize::MAX;
+        sink.flush().unwrap();
+        assert_eq!(
+            sent(&sink.get_ref().data),
+            vec![vec![0x01, 0x02, 0x03, 0x04], vec![0x7E], vec![0x05]]
+        );
+    }
+
+    #[test]
+    fn starvation_limit_lets_lower_classes_through() {
+        let mut sink = PrioritySink::new(Vec::new(), SpecialChars::default())
+            .unwrap()
+            .starvation_limit(2);
+        sink.enqueue(&[0x10], 0).unwrap();
+        sink.enqueue(&[0x11], 0).unwrap();
+        for byte in 0x20..0x25 {
+            sink.enqueue(&[byte], 1).unwrap();
+        }
+
+        sink.flush().unwrap();
+
+        assert_eq!(
+            sent(sink.get_ref()),
+            vec![
+                vec![0x20],
+                vec![0x21],
+                vec![0x10],
+                vec![0x22],
+                vec![0x23],
+                vec![0x11],
+                vec![0x24],
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_special_chars() {
+        let chars = SpecialChars::new(0x7E, 0x7E, 0x5E, 0x5D);
+
+        assert_eq!(
+            PrioritySink::new(Vec::new(), chars).err(),
+            Some(HDLCError::DuplicateSpecialChar)
+        );
+    }
+}
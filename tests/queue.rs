@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use hdlc::{FrameQueue, HDLCError};
+
+    #[test]
+    fn queue_rejects_when_full() {
+        let mut queue: FrameQueue<4, 2> = FrameQueue::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        assert_eq!(producer.enqueue(&[0x01]), Ok(()));
+        assert_eq!(producer.enqueue(&[0x02, 0x03]), Ok(()));
+        assert!(producer.is_full());
+        assert_eq!(producer.enqueue(&[0x04]), Err(HDLCError::QueueFull));
+
+        assert_eq!(consumer.peek(), Some(&[0x01][..]));
+        assert!(consumer.pop());
+        assert_eq!(producer.enqueue(&[0x04]), Ok(()));
+        assert_eq!(consumer.peek(), Some(&[0x02, 0x03][..]));
+    }
+
+    #[test]
+    fn queue_rejects_oversized_frames() {
+        let mut queue: FrameQueue<2, 2> = FrameQueue::new();
+        let (mut producer, mut consumer) = queue.split();
+        let mut buf = [0u8; 1];
+
+        assert_eq!(
+            producer.enqueue(&[0x01, 0x02, 0x03]),
+            Err(HDLCError::BufferTooSmall)
+        );
+        assert_eq!(producer.enqueue(&[0x01, 0x02]), Ok(()));
+        assert_eq!(
+            consumer.dequeue_into(&mut buf),
+            Err(HDLCError::BufferTooSmall)
+        );
+        assert_eq!(consumer.peek(), Some(&[0x01, 0x02][..]));
+    }
+
+    #[test]
+    fn queue_hands_frames_across_threads() {
+        let mut queue: FrameQueue<4, 3> = FrameQueue::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..1000u32 {
+                    while producer.enqueue(&i.to_le_bytes()).is_err() {
+                        thread::yield_now();
+                    }
+                }
+            });
+
+            let mut buf = [0u8; 4];
+            for i in 0..1000u32 {
+                let len = loop {
+                    match consumer.dequeue_into(&mut buf) {
+                        Ok(Some(len)) => break len,
+                        _ => thread::yield_now(),
+                    }
+                };
+                assert_eq!(len, 4);
+                assert_eq!(u32::from_le_bytes(buf), i);
+            }
+        });
+    }
+}
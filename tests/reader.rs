@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor, Read};
+
+    use hdlc::{encode, DecodingReader, HDLCError, SpecialChars, FEND, FESC, TFEND};
+
+    /// Reader handing out one byte per call
+    struct Trickle(Cursor<Vec<u8>>);
+
+    impl Read for Trickle {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(1);
+            self.0.read(&mut buf[..len])
+        }
+    }
+
+    /// Reads every frame of `reader` to its end
+    fn read_frames<R: Read>(reader: &mut DecodingReader<R>) -> Vec<Vec<u8>> {
+        let mut frames = Vec::new();
+        while reader.next_frame().unwrap() {
+            let mut frame = Vec::new();
+            reader.read_to_end(&mut frame).unwrap();
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn reads_frames() {
+        let chars = SpecialChars::default();
+        let payloads = vec![vec![0x01, FEND, 0x02], vec![FESC; 3], vec![0x03; 2000]];
+        let mut data = vec![0x55, FEND];
+        for payload in &payloads {
+            data.extend(encode(payload, chars.clone()).unwrap());
+            data.push(0x55);
+        }
+
+        let mut reader = DecodingReader::new(Cursor::new(data.clone()), chars.clone()).unwrap();
+        assert_eq!(read_frames(&mut reader), payloads);
+
+        let mut reader = DecodingReader::new(Trickle(Cursor::new(data)), chars).unwrap();
+        assert_eq!(read_frames(&mut reader), payloads);
+    }
+
+    #[test]
+    fn partial_reads_and_skipping() {
+        let chars = SpecialChars::default();
+        let data = vec![FEND, 0x01, 0x02, FESC, TFEND, FEND, FEND, 0x03, FEND];
+        let mut reader = DecodingReader::new(Cursor::new(data), chars).unwrap();
+
+        let mut buf = [0; 2];
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(buf, [0x01, 0x02]);
+        assert!(reader.next_frame().unwrap());
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x03);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+        assert!(!reader.next_frame().unwrap());
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn reports_malformed_frames() {
+        let chars = SpecialChars::default();
+        let data = vec![FEND, 0x01, FESC, 0x02, FEND, FEND, 0x03, FESC];
+        let mut reader = DecodingReader::new(Cursor::new(data), chars).unwrap();
+
+        let mut buf = [0; 8];
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        let error = reader.read(&mut buf).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            error.into_inner().unwrap().downcast::<HDLCError>().unwrap(),
+            Box::new(HDLCError::MissingTradeChar { offset: 2 })
+        );
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+
+        assert!(reader.next_frame().unwrap());
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        let error = reader.read(&mut buf).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(!reader.next_frame().unwrap());
+    }
+
+    #[test]
+    fn rejects_duplicate_chars() {
+        let chars = SpecialChars::new(FEND, FEND, TFEND, 0x5D);
+
+        assert_eq!(
+            DecodingReader::new(Cursor::new(Vec::new()), chars).err(),
+            Some(HDLCError::DuplicateSpecialChar)
+        );
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use hdlc::{
+        decode_frames_deque, decode_frames_split, decode_frames_with, encode_into, DecodeOptions,
+        FrameReader, Framing, HDLCError, SpecialChars,
+    };
+
+    const STREAM: [u8; 17] = [
+        0x00, 0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x7E, 0x02, 0x7E, 0x03, 0x7D, 0x5D, 0x7E, 0x7E,
+        0x04, 0x05,
+    ];
+
+    fn assert_every_split_matches(data: &[u8], options: DecodeOptions) {
+        let chars = SpecialChars::default();
+        let expected = decode_frames_with(data, chars.clone(), options);
+        for split in 0..=data.len() {
+            let (head, tail) = data.split_at(split);
+            assert_eq!(
+                decode_frames_split(head, tail, chars.clone(), options),
+                expected,
+                "split at {}",
+                split
+            );
+        }
+    }
+
+    #[test]
+    fn split_matches_contiguous_decoding() {
+        assert_every_split_matches(&STREAM, DecodeOptions::default());
+    }
+
+    #[test]
+    fn split_matches_contiguous_decoding_with_empty_frames() {
+        let options = DecodeOptions {
+            empty_frames: true,
+            ..DecodeOptions::default()
+        };
+        assert_every_split_matches(&STREAM, options);
+    }
+
+    #[test]
+    fn split_matches_contiguous_decoding_with_shared_flags() {
+        let options = DecodeOptions {
+            shared_flags: true,
+            ..DecodeOptions::default()
+        };
+        assert_every_split_matches(&STREAM, options);
+        assert_every_split_matches(&[0x7E, 0x01, 0x7E, 0x02, 0x7E, 0x03], options);
+    }
+
+    #[test]
+    fn split_matches_contiguous_decoding_terminator_only() {
+        let options = DecodeOptions {
+            framing: Framing::TerminatorOnly,
+            ..DecodeOptions::default()
+        };
+        assert_every_split_matches(&[0x01, 0x7E, 0x02, 0x7D, 0x5E, 0x7E, 0x03], options);
+    }
+
+    #[test]
+    fn split_reports_offsets_from_start_of_head() {
+        let head = [0x7E, 0x01, 0x7E, 0x7E, 0x02];
+        let tail = [0x7D, 0x03, 0x7E];
+
+        assert_eq!(
+            decode_frames_split(
+                &head,
+                &tail,
+                SpecialChars::default(),
+                DecodeOptions::default()
+            ),
+            Err(HDLCError::MissingTradeChar { offset: 5 })
+        );
+        assert_eq!(
+            decode_frames_split(
+                &[],
+                &tail,
+                SpecialChars::default(),
+                DecodeOptions::default()
+            ),
+            Ok((vec![], 2))
+        );
+    }
+
+    #[test]
+    fn deque_drains_consumed_frames() {
+        let mut ring = VecDeque::from(vec![0x02, 0x7E, 0x7E, 0x03]);
+        // Wrap the frames around the end of the ring
+        for &byte in [0x7E, 0x01, 0x7E, 0x7E].iter().rev() {
+            ring.push_front(byte);
+        }
+        assert!(!ring.as_slices().1.is_empty());
+
+        let payloads =
+            decode_frames_deque(&mut ring, SpecialChars::default(), DecodeOptions::default());
+
+        assert_eq!(payloads, Ok(vec![vec![0x01], vec![0x02]]));
+        assert_eq!(ring, vec![0x7E, 0x03]);
+    }
+
+    #[test]
+    fn deque_is_left_untouched_on_error() {
+        let mut ring = VecDeque::from(vec![0x7E, 0x7D, 0x01, 0x7E]);
+
+        assert_eq!(
+            decode_frames_deque(&mut ring, SpecialChars::default(), DecodeOptions::default()),
+            Err(HDLCError::MissingTradeChar { offset: 1 })
+        );
+        assert_eq!(ring.len(), 4);
+    }
+
+    #[test]
+    fn encode_into_deque_and_read_back() {
+        let chars = SpecialChars::default();
+        let mut ring = VecDeque::new();
+
+        encode_into(&[0x01, 0x7E], chars.clone(), &mut ring).unwrap();
+        encode_into(&[0x02], chars.clone(), &mut ring).unwrap();
+        assert_eq!(ring, vec![0x7E, 0x01, 0x7D, 0x5E, 0x7E, 0x7E, 0x02, 0x7E]);
+
+        let frames: Vec<Vec<u8>> = FrameReader::builder(chars)
+            .decode(true)
+            .build(&mut ring)
+            .collect();
+        assert_eq!(frames, vec![vec![0x01, 0x7E], vec![0x02]]);
+        assert!(ring.is_empty());
+    }
+}
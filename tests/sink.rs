@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    use hdlc::{FrameSink, HDLCError, SpecialChars, FEND};
+
+    /// Non-blocking writer taking at most `limit` bytes until it is opened up again
+    struct SlowWriter {
+        data: Vec<u8>,
+        limit: usize,
+        writes: usize,
+    }
+
+    impl Write for SlowWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.limit == 0 {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            let len = buf.len().min(self.limit);
+            self.data.extend_from_slice(&buf[..len]);
+            self.limit -= len;
+            self.writes += 1;
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Waker recording whether it was woken
+    struct FlagWaker(AtomicBool);
+
+    impl Wake for FlagWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn coalesces_queued_frames() {
+        let writer = SlowWriter {
+            data: vec![],
+            limit: usize::MAX,
+            writes: 0,
+        };
+        let mut sink = FrameSink::new(writer, SpecialChars::default()).unwrap();
+
+        assert!(sink.start_send(&[0x01]).is_ok());
+        assert!(sink.start_send(&[0x02]).is_ok());
+        assert!(sink.flush().is_ok());
+
+        let writer = sink.into_inner();
+        assert_eq!(writer.data, vec![FEND, 0x01, FEND, FEND, 0x02, FEND]);
+        assert_eq!(writer.writes, 1);
+    }
+
+    #[test]
+    fn applies_watermarks() {
+        let writer = SlowWriter {
+            data: vec![],
+            limit: 2,
+            writes: 0,
+        };
+        let mut sink = FrameSink::new(writer, SpecialChars::default())
+            .unwrap()
+            .watermarks(2, 6);
+
+        assert!(sink.start_send(&[0x01, 0x02, 0x03, 0x04]).is_ok());
+        assert!(!sink.is_ready());
+
+        // Drained to 4 bytes, still above the low watermark
+        assert!(!sink.ready().unwrap());
+        assert_eq!(sink.buffered(), 4);
+        assert_eq!(sink.flush().unwrap_err().kind(), io::ErrorKind::WouldBlock);
+    }
+
+    #[test]
+    fn wakes_pending_task() {
+        let writer = SlowWriter {
+            data: vec![],
+            limit: 0,
+            writes: 0,
+        };
+        let mut sink = FrameSink::new(writer, SpecialChars::default())
+            .unwrap()
+            .watermarks(0, 3);
+        let flag = Arc::new(FlagWaker(AtomicBool::new(false)));
+        let waker = Waker::from(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(sink.start_send(&[0x01]).is_ok());
+        assert!(sink.poll_ready(&mut cx).is_pending());
+
+        // The writer still blocks, nobody is woken
+        assert!(sink.writable().is_ok());
+        assert!(!flag.0.load(Ordering::SeqCst));
+
+        sink.get_mut().limit = usize::MAX;
+        assert!(sink.writable().is_ok());
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert!(matches!(sink.poll_ready(&mut cx), Poll::Ready(Ok(()))));
+    }
+
+    #[test]
+    fn rejects_duplicate_chars() {
+        let chars = SpecialChars::new(FEND, FEND, 0x5E, 0x5D);
+
+        assert_eq!(
+            FrameSink::new(Vec::new(), chars).err(),
+            Some(HDLCError::DuplicateSpecialChar)
+        );
+    }
+}
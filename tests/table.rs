@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use hdlc::{encode, EscapeMap, HDLCError, SliceOutput, SpecialChars, TableEncoder};
+
+    #[test]
+    fn table_encoder_matches_encode() {
+        let data: Vec<u8> = (0..=255).chain(0..=255).collect();
+        let mut custom = HashMap::new();
+        custom.insert(0x11, 0x31);
+        custom.insert(0x13, 0x33);
+        let sets = [
+            SpecialChars::default(),
+            SpecialChars::new(0x71, 0x70, 0x51, 0x50),
+            SpecialChars::default()
+                .with_escape_map(EscapeMap::from_accm(0xFFFF_FFFF))
+                .unwrap(),
+            SpecialChars::new_custom(0x71, 0x70, custom),
+        ];
+
+        for chars in sets {
+            let expected = encode(&data, chars.clone()).unwrap();
+            let encoder = TableEncoder::new(chars).unwrap();
+
+            assert_eq!(encoder.encode(&data), expected);
+            assert_eq!(encoder.encoded_len(&data), expected.len());
+
+            let mut out = Vec::new();
+            assert_eq!(encoder.encode_into(&data, &mut out), Ok(expected.len()));
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn table_encoder_rejects_duplicates() {
+        let chars = SpecialChars::new(0x7E, 0x7E, 0x5E, 0x5D);
+
+        assert_eq!(
+            TableEncoder::new(chars).unwrap_err(),
+            HDLCError::DuplicateSpecialChar
+        );
+    }
+
+    #[test]
+    fn table_encoder_into_small_buffer() {
+        let encoder = TableEncoder::new(SpecialChars::default()).unwrap();
+        let mut buf = [0u8; 4];
+
+        assert_eq!(
+            encoder.encode_into(&[0x01, 0x7E, 0x02], &mut SliceOutput::new(&mut buf)),
+            Err(HDLCError::BufferTooSmall)
+        );
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "test-utils")]
+    use hdlc::test_utils::{
+        assert_roundtrip, assert_stream_roundtrip, special_chars, special_chars_with_accm,
+    };
+    #[cfg(feature = "test-utils")]
+    use hdlc::{EncodeOptions, FcsMode, Framing, SpecialChars, FEND, FESC};
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn generators_reject_collisions() {
+        assert_eq!(
+            special_chars([FEND, FESC, 0x5E, 0x5D]),
+            Some(SpecialChars::default())
+        );
+        assert_eq!(special_chars([FEND, FESC, FEND, 0x5D]), None);
+        assert!(special_chars_with_accm([FEND, FESC, 0x5E, 0x5D], 0x000A_0000).is_some());
+        // 0x1E is escaped as 0x3E, which is already the translation of `fend`
+        assert_eq!(
+            special_chars_with_accm([FEND, FESC, 0x3E, 0x5D], 1 << 0x1E),
+            None
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn laws_hold() {
+        let payloads: Vec<Vec<u8>> = vec![
+            vec![],
+            vec![FEND, FESC, 0x5E, 0x5D],
+            (0..=u8::MAX).collect(),
+            vec![0x11; 40],
+        ];
+        let configs = [
+            special_chars([FEND, FESC, 0x5E, 0x5D]),
+            special_chars([0x71, 0x70, 0x51, 0x50]),
+            special_chars_with_accm([FEND, FESC, 0x5E, 0x5D], 0xFFFF_FFFF),
+        ];
+
+        for s_chars in configs.into_iter().flatten() {
+            for payload in &payloads {
+                for fcs in [FcsMode::None, FcsMode::Crc16, FcsMode::Crc32] {
+                    assert_roundtrip(payload, s_chars.clone(), fcs);
+                }
+            }
+            for framing in [
+                Framing::Flags,
+                Framing::TerminatorOnly,
+                Framing::DoubledFlags,
+            ] {
+                for shared_flags in [false, true] {
+                    let options = EncodeOptions {
+                        framing,
+                        shared_flags,
+                        ..EncodeOptions::default()
+                    };
+                    for chunk_size in [1, 7, 1024] {
+                        assert_stream_roundtrip(&payloads, s_chars.clone(), options, chunk_size);
+                    }
+                }
+            }
+        }
+    }
+}
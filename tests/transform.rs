@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{decode_transformed, encode_transformed, HDLCError, PayloadTransform, SpecialChars};
+
+    /// XORs every byte with a key that changes after every frame, so transforms with state are
+    /// covered
+    struct RollingXor {
+        key: u8,
+    }
+
+    impl PayloadTransform for RollingXor {
+        fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+            let output = payload.iter().map(|b| b ^ self.key).collect();
+            self.key = self.key.wrapping_add(1);
+            Ok(output)
+        }
+
+        fn decode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+            if payload.is_empty() {
+                return Err(HDLCError::TransformFailed);
+            }
+            self.encode(payload)
+        }
+    }
+
+    /// Appends a one byte tag standing in for the tag of an AEAD cipher
+    struct Tagged;
+
+    impl PayloadTransform for Tagged {
+        fn encode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+            let tag = payload.iter().fold(0xA5u8, |tag, b| tag.rotate_left(1) ^ b);
+            let mut output = payload.to_vec();
+            output.push(tag);
+            Ok(output)
+        }
+
+        fn decode(&mut self, payload: &[u8]) -> Result<Vec<u8>, HDLCError> {
+            let (&tag, data) = payload
+                .split_last()
+                .ok_or(HDLCError::AuthenticationFailed)?;
+            let output = self.encode(data)?;
+            if output.last() != Some(&tag) {
+                return Err(HDLCError::AuthenticationFailed);
+            }
+            Ok(data.to_vec())
+        }
+    }
+
+    #[test]
+    fn transform_applies_before_escaping() {
+        let chars = SpecialChars::default();
+        let mut tx = RollingXor { key: 0x7E };
+
+        // 0x00 ^ 0x7E needs escaping once transformed
+        let first = encode_transformed(&[0x00], chars.clone(), &mut tx).unwrap();
+        assert_eq!(first, vec![0x7E, 0x7D, 0x5E, 0x7E]);
+        let second = encode_transformed(&[0x02], chars.clone(), &mut tx).unwrap();
+        assert_eq!(second, vec![0x7E, 0x7D, 0x5D, 0x7E]);
+
+        let mut rx = RollingXor { key: 0x7E };
+        assert_eq!(
+            decode_transformed(&first, chars.clone(), &mut rx),
+            Ok(vec![0x00])
+        );
+        assert_eq!(decode_transformed(&second, chars, &mut rx), Ok(vec![0x02]));
+    }
+
+    #[test]
+    fn transform_errors_are_reported() {
+        let chars = SpecialChars::default();
+        let mut rx = RollingXor { key: 0 };
+
+        assert_eq!(
+            decode_transformed(&[0x7E, 0x7E], chars.clone(), &mut rx),
+            Err(HDLCError::TransformFailed)
+        );
+        assert_eq!(
+            decode_transformed(&[0x7E, 0x01], chars, &mut rx),
+            Err(HDLCError::Incomplete { needed: Some(1) })
+        );
+        assert_eq!(
+            HDLCError::TransformFailed.to_string(),
+            "Payload transform failed."
+        );
+    }
+
+    #[test]
+    fn duplicate_special_chars_skip_the_transform() {
+        let mut tx = RollingXor { key: 0 };
+
+        assert_eq!(
+            encode_transformed(&[0x01], SpecialChars::new(0x7E, 0x7E, 0x5E, 0x5D), &mut tx),
+            Err(HDLCError::DuplicateSpecialChar)
+        );
+        assert_eq!(tx.key, 0);
+    }
+
+    #[test]
+    fn sealing_transform_rejects_tampered_frames() {
+        let chars = SpecialChars::default();
+        let mut sealer: Box<dyn PayloadTransform> = Box::new(Tagged);
+
+        let mut frame = encode_transformed(&[0x01, 0x02], chars.clone(), &mut sealer).unwrap();
+        assert_eq!(
+            decode_transformed(&frame, chars.clone(), &mut sealer),
+            Ok(vec![0x01, 0x02])
+        );
+
+        frame[1] ^= 0x10;
+        assert_eq!(
+            decode_transformed(&frame, chars.clone(), &mut sealer),
+            Err(HDLCError::AuthenticationFailed)
+        );
+        assert_eq!(
+            decode_transformed(&[0x7E, 0x7E], chars, &mut sealer),
+            Err(HDLCError::AuthenticationFailed)
+        );
+        assert_ne!(HDLCError::AuthenticationFailed, HDLCError::TransformFailed);
+    }
+
+    #[test]
+    fn paired_transforms_apply_in_order() {
+        let chars = SpecialChars::default();
+        let mut tx = (RollingXor { key: 0x10 }, Tagged);
+        let mut rx = (RollingXor { key: 0x10 }, Tagged);
+
+        let frame = encode_transformed(&[0x01], chars.clone(), &mut tx).unwrap();
+        // The tag covers the transformed payload
+        let sealed = Tagged.encode(&[0x11]).unwrap();
+        assert_eq!(frame, vec![0x7E, sealed[0], sealed[1], 0x7E]);
+
+        let stack: &mut dyn PayloadTransform = &mut rx;
+        assert_eq!(decode_transformed(&frame, chars, stack), Ok(vec![0x01]));
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use hdlc::{decode, encode, HDLCError, SliceOutput, SpecialChars, ValidatedSpecialChars};
+
+    #[test]
+    fn validated_matches_unchecked() {
+        let msg = [0x01, 0x7E, 0x70, 0x71, 0x7D, 0x02];
+        let custom = SpecialChars::new(0x71, 0x70, 0x51, 0x50);
+        let chars = ValidatedSpecialChars::try_from(custom.clone()).unwrap();
+
+        let frame = chars.encode(&msg);
+        assert_eq!(frame, encode(&msg, custom.clone()).unwrap());
+        assert_eq!(chars.decode(&frame), decode(&frame, custom));
+        assert_eq!(
+            chars.decode(&frame[..2]),
+            Err(HDLCError::Incomplete { needed: Some(1) })
+        );
+
+        let mut buf = [0u8; 4];
+        assert_eq!(
+            chars.encode_into(&msg, &mut SliceOutput::new(&mut buf)),
+            Err(HDLCError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn validated_rejects_duplicates() {
+        let dupes = SpecialChars::new(0x7E, 0x7D, 0x5E, 0x5E);
+
+        assert_eq!(
+            ValidatedSpecialChars::new(dupes)
+                .err()
+                .map(|e| e.to_string()),
+            Some(HDLCError::DuplicateSpecialChar.to_string())
+        );
+        assert!(ValidatedSpecialChars::new(SpecialChars::default()).is_ok());
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use hdlc::{decode_frames, EncodingWriter, HDLCError, SpecialChars, FEND, FESC, TFEND, TFESC};
+
+    #[test]
+    fn streams_into_frames() {
+        let chars = SpecialChars::default();
+        let mut writer = EncodingWriter::new(Vec::new(), chars.clone()).unwrap();
+        assert!(!writer.is_open());
+
+        write!(writer, "ab").unwrap();
+        writer.write_all(&[FEND, FESC]).unwrap();
+        assert!(writer.is_open());
+        writer.flush().unwrap();
+        assert_eq!(
+            writer.get_ref(),
+            &vec![FEND, b'a', b'b', FESC, TFEND, FESC, TFESC]
+        );
+
+        writer.finish().unwrap();
+        writer.write_all(&[0x01]).unwrap();
+        writer.finish().unwrap();
+        writer.finish().unwrap();
+
+        let data = writer.into_inner();
+        // The last frame was finished without any data
+        assert!(data.ends_with(&[0x01, FEND, FEND, FEND]));
+        let (payloads, _) = decode_frames(&data, chars).unwrap();
+        assert_eq!(payloads, vec![vec![b'a', b'b', FEND, FESC], vec![0x01]]);
+    }
+
+    #[test]
+    fn rejects_duplicate_chars() {
+        let chars = SpecialChars::new(FEND, FEND, TFEND, TFESC);
+
+        assert_eq!(
+            EncodingWriter::new(Vec::new(), chars).err(),
+            Some(HDLCError::DuplicateSpecialChar)
+        );
+    }
+}